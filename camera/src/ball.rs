@@ -0,0 +1,86 @@
+//! Position de la balle vue par la caméra, dans le repère du robot.
+//!
+//! La caméra regarde dans la direction du kicker. Avec un modèle sténopé, la colonne du centre
+//! de la balle donne sa direction et son diamètre apparent donne sa distance. Le résultat est un
+//! `LocalPoint`, comme `Informations::ball_relative_position` dans les stratégies.
+//!
+//! Les pixels sont ceux de l'image remise à l'endroit (le capteur est monté à 180°, voir
+//! `Camera 0.yaml`).
+
+use robot_common::frames::LocalPoint;
+use robot_common::units::*;
+
+/// Diamètre de la balle orange (taille d'une balle de golf)
+pub const BALL_DIAMETER: Meters = Meters::mm(42.7);
+
+/// Géométrie de l'image, pour passer des pixels au repère du robot
+#[derive(Debug, Clone, Copy)]
+pub struct CameraModel {
+    /// largeur de l'image en pixels
+    pub width: u32,
+    /// champ de vision horizontal
+    pub horizontal_fov: Rad,
+}
+
+impl CameraModel {
+    /// Distance focale en pixels
+    fn focal(&self) -> f64 {
+        f64::from(self.width) / 2.0 / (self.horizontal_fov.val() / 2.0).tan()
+    }
+
+    /// Direction de la colonne `column`, mesurée depuis le kicker dans le sens trigonométrique :
+    /// la droite de l'image donne un angle négatif
+    pub fn angle_of(&self, column: f32) -> Rad {
+        let offset = f64::from(column) - f64::from(self.width) / 2.0;
+        Rad::new(-(offset / self.focal()).atan())
+    }
+
+    /// Position de la balle dont le centre est dans la colonne `column`
+    /// et qui fait `diameter` pixels de large
+    pub fn ball_position(&self, column: f32, diameter: f32) -> LocalPoint {
+        let angle = self.angle_of(column);
+        // profondeur le long de l'axe de la caméra, puis distance le long du rayon
+        let depth = BALL_DIAMETER * (self.focal() / f64::from(diameter));
+        LocalPoint::from_lidar(depth / angle.val().cos(), angle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMIT: Meters = Meters(1e-9);
+
+    fn camera() -> CameraModel {
+        CameraModel {
+            width: 640,
+            horizontal_fov: Rad::QUARTER_TURN,
+        }
+    }
+
+    #[test]
+    fn test_angle_of() {
+        let camera = camera();
+        assert!(camera.angle_of(320.0).val().abs() < 1e-12);
+        // bords de l'image : la moitié du champ de vision
+        let half_fov = Rad::QUARTER_TURN / 2.0;
+        assert!((camera.angle_of(640.0) + half_fov).val().abs() < 1e-12);
+        assert!((camera.angle_of(0.0) - half_fov).val().abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ball_position() {
+        let camera = camera();
+        // focale de 320 pixels : à 1 m de profondeur, la balle fait 320 * BALL_DIAMETER pixels
+        let diameter = (320.0 * BALL_DIAMETER.0) as f32;
+
+        let ahead = camera.ball_position(320.0, diameter);
+        assert!(ahead.x.approx_eq(Meters(0.0), LIMIT));
+        assert!(ahead.y.approx_eq(Meters(1.0), Meters(1e-6)));
+
+        // au bord droit de l'image, à 45° : même profondeur, décalée vers la droite
+        let right = camera.ball_position(640.0, diameter);
+        assert!(right.x.approx_eq(Meters(1.0), Meters(1e-6)));
+        assert!(right.y.approx_eq(Meters(1.0), Meters(1e-6)));
+    }
+}
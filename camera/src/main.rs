@@ -1,12 +1,19 @@
+// pas encore branché : la détection de la balle est dans old_main.rs
+#[allow(dead_code)]
+mod ball;
+
 use std::{env, error::Error};
 
 use libcamera::{
     camera::CameraConfigurationStatus, camera_manager::CameraManager, framebuffer::AsFrameBuffer, framebuffer_allocator::{FrameBuffer, FrameBufferAllocator}, framebuffer_map::MemoryMappedFrameBuffer, request::RequestStatus, stream::{Stream, StreamRole}
 };
 
+use robot_logging::{LogConfig, set_up_logging};
+
 // documentation of libcamera at : https://libcamera.org/guides/application-developer.html
 
 fn main() -> Result<(), Box<dyn Error>> {
+    set_up_logging(&LogConfig::new("camera"))?;
     // let camera_manager = CameraManager::new().expect("cannot initiate camera manager");
     let rec = rerun::RecordingStreamBuilder::new("camera_app").connect_grpc()?;
     rec.log(
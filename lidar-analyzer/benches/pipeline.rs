@@ -0,0 +1,201 @@
+//! Mesure le temps de chaque étape de l'analyse du lidar sur les captures enregistrées.
+//!
+//! Sur l'ordinateur : `cargo bench`.
+//! Sur le robot : copier le dossier `captures` et l'exécutable produit par
+//! `cross bench --no-run --target aarch64-unknown-linux-gnu`, puis lancer
+//! `LIDAR_CAPTURES_DIR=./captures ./pipeline-<hash> --bench`.
+
+use std::hint::black_box;
+use std::path::PathBuf;
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use lidar_analyzer::analyze::*;
+use lidar_analyzer::dataset::{Capture, default_captures_dir, load_captures};
+use lidar_analyzer::parse::{LidarPoint, ScanAssembler};
+
+fn captures() -> Vec<Capture> {
+    let dir = std::env::var_os("LIDAR_CAPTURES_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(default_captures_dir);
+    load_captures(&dir).expect("impossible de charger les captures")
+}
+
+/// Reconstitue les paquets de 47 octets envoyés par le lidar à partir des points d'une capture
+fn to_packets(points: &[LidarPoint]) -> Vec<Box<Vec<u8>>> {
+    let centi_degrees = |point: &LidarPoint| (point.point.angle.deg().val() * 100.0).round() as u16;
+    points
+        .chunks_exact(12)
+        .map(|chunk| {
+            let mut packet = vec![84, 44, 0, 0];
+            packet.extend(centi_degrees(&chunk[0]).to_le_bytes());
+            for point in chunk {
+                let distance = (point.point.distance.0 * 1000.0).round() as u16;
+                packet.extend(distance.to_le_bytes());
+                packet.push(0);
+            }
+            packet.extend(centi_degrees(&chunk[11]).to_le_bytes());
+            packet.extend([0, 0, 0]);
+            Box::new(packet)
+        })
+        .collect()
+}
+
+fn bench_pipeline(c: &mut Criterion) {
+    let captures = captures();
+    let config = HoughConfig::default();
+    let packets: Vec<Box<Vec<u8>>> = captures
+        .iter()
+        .flat_map(|capture| to_packets(&capture.points))
+        .collect();
+    let parsed_packets: Vec<Box<Vec<LidarPoint>>> = packets
+        .iter()
+        .map(|packet| LidarPoint::from_data(packet.clone()).unwrap())
+        .collect();
+    let accumulators: Vec<Box<HoughAccumulator>> = captures
+        .iter()
+        .map(|capture| {
+            let mut accumulator = empty_accumulator();
+            build_hough_accumulator(&mut accumulator, &capture.points);
+            accumulator
+        })
+        .collect();
+    let peaks: Vec<Vec<HoughLine>> = accumulators
+        .iter()
+        .map(|accumulator| hough_peaks(accumulator, &config))
+        .collect();
+    let raw_candidates: Vec<_> = peaks
+        .iter()
+        .map(|peaks| {
+            (
+                search_all_parallel_lines(peaks, FIELD_LENGTH, &config),
+                search_all_parallel_lines(peaks, FIELD_WIDTH, &config),
+            )
+        })
+        .collect();
+    let candidates: Vec<_> = captures
+        .iter()
+        .zip(raw_candidates.iter())
+        .map(|(capture, (width, length))| {
+            (
+                check_support(&capture.points, width.clone(), FIELD_WIDTH),
+                check_support(&capture.points, length.clone(), FIELD_LENGTH),
+            )
+        })
+        .collect();
+    let walls: Vec<_> = captures
+        .iter()
+        .zip(peaks.iter())
+        .zip(candidates.iter())
+        .filter_map(|((capture, peaks), (width, length))| {
+            locate_field_with_4_walls(width, length)
+                .or_else(|| {
+                    fallback_on_3_walls(
+                        peaks,
+                        &capture.points,
+                        width.clone(),
+                        length.clone(),
+                        &config,
+                    )
+                })
+                .map(|found_walls| (found_walls, width, length))
+        })
+        .collect();
+
+    let mut group = c.benchmark_group(format!("lidar ({} captures)", captures.len()));
+
+    group.bench_function("parse_packets", |b| {
+        b.iter_batched(
+            || packets.clone(),
+            |packets| {
+                for packet in packets {
+                    black_box(LidarPoint::from_data(packet).unwrap());
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("assemble_scans", |b| {
+        b.iter(|| {
+            let mut assembler = ScanAssembler::default();
+            for points in parsed_packets.iter() {
+                black_box(assembler.push(points));
+            }
+        })
+    });
+
+    group.bench_function("build_hough_accumulator", |b| {
+        b.iter(|| {
+            for capture in captures.iter() {
+                let mut accumulator = empty_accumulator();
+                build_hough_accumulator(&mut accumulator, &capture.points);
+                black_box(&accumulator);
+            }
+        })
+    });
+
+    group.bench_function("hough_peaks", |b| {
+        b.iter(|| {
+            for accumulator in accumulators.iter() {
+                black_box(hough_peaks(accumulator, &config));
+            }
+        })
+    });
+
+    group.bench_function("search_all_parallel_lines", |b| {
+        b.iter(|| {
+            for peaks in peaks.iter() {
+                black_box(search_all_parallel_lines(peaks, FIELD_LENGTH, &config));
+                black_box(search_all_parallel_lines(peaks, FIELD_WIDTH, &config));
+            }
+        })
+    });
+
+    group.bench_function("check_support", |b| {
+        b.iter_batched(
+            || raw_candidates.clone(),
+            |raw_candidates| {
+                for (capture, (width, length)) in captures.iter().zip(raw_candidates) {
+                    black_box(check_support(&capture.points, width, FIELD_WIDTH));
+                    black_box(check_support(&capture.points, length, FIELD_LENGTH));
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("search_perpendicular_lines_of", |b| {
+        b.iter(|| {
+            for (peaks, (width, _)) in peaks.iter().zip(candidates.iter()) {
+                if let Some(pair) = width.first() {
+                    black_box(search_perpendicular_lines_of(
+                        peaks,
+                        pair.first.line,
+                        &config,
+                    ));
+                }
+            }
+        })
+    });
+
+    group.bench_function("perform_moy", |b| {
+        b.iter(|| {
+            for (found_walls, width, length) in walls.iter() {
+                black_box(perform_moy(*found_walls, width, length));
+            }
+        })
+    });
+
+    group.bench_function("locate_robot", |b| {
+        b.iter(|| {
+            for capture in captures.iter() {
+                let _ = black_box(locate_robot(&capture.points));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_pipeline);
+criterion_main!(benches);
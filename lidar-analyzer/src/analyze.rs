@@ -1,9 +1,13 @@
-use core::{f64, time};
-
-use rerun::external::arrow::datatypes::Field;
+use core::f64;
+use std::error::Error;
+use std::fmt::Display;
 
+use crate::geometry::{Line, LineMean, Point, Transform, Vector, wrap_angle_signed};
+use crate::metrics;
 use crate::parse::{LidarPoint, PolarPoint};
 use crate::prelude::*;
+use robot_common::frames::{Global, GlobalPoint, Pose2};
+use tracing::instrument;
 
 // TODO : virer cette histoire de conventions !
 
@@ -42,12 +46,12 @@ use crate::prelude::*;
 const IS_PERPENDICULAR_TOLERANCE: f64 = 0.2;
 /// en radians
 const IS_PARALLEL_TOLERANCE: f64 = 0.2;
-/// en radians
-const IS_PARALLEL_EXACT_TOLERANCE: f64 = 10e-5;
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PolarLine {
     pub distance: Meters,
+    #[cfg_attr(feature = "serde", serde(with = "robot_common::units::serde_rad"))]
     pub angle: Rad,
 }
 
@@ -55,17 +59,7 @@ impl PolarLine {
     /// Renvoit l'angle aigu entre deux lignes polaires
     /// (faire un schéma pour se convaincre rapidement de la formule)
     fn smallest_angle_between(&self, other: &Self) -> Rad {
-        let alpha = (self.angle - other.angle).mag().val() % Rad::HALF_TURN.val();
-        if alpha >= Rad::QUARTER_TURN.val() {
-            Rad::new(Rad::HALF_TURN.val() - alpha)
-        } else {
-            Rad::new(alpha)
-        }
-    }
-
-    /// Avec une tolérance
-    fn is_parallel_with(&self, other: &Self) -> bool {
-        self.smallest_angle_between(other) <= Rad::new(IS_PARALLEL_EXACT_TOLERANCE)
+        Line::from(*self).angle_with(&Line::from(*other))
     }
 
     /// Avec une tolérance
@@ -82,57 +76,22 @@ impl PolarLine {
     /// N'a du sens que si les lignes sont à peu près parallèles
     fn distance_center_with(&self, other: &Self) -> Meters {
         // Impossible de faire self.distance.abs() + other.distance.abs() car cela donnerait des résultats éloignés si la tolérance est trop élevée
-        let (x1, y1) = self._to_carthesian_point();
-        let (x2, y2) = other._to_carthesian_point();
-        Meters(((x1 - x2).powi(2) + (y1 - y2).powi(2)).sqrt())
-    }
-
-    /// A n'utiliser que si vous êtes sûr de ce que vous faites,
-    /// *Vous transformez une droite en point !*
-    fn _to_carthesian_point(&self) -> (f64, f64) {
-        (
-            self.distance.0 * self.angle.cos(),
-            self.distance.0 * self.angle.sin(),
-        )
+        Line::from(*self)
+            .foot()
+            .distance_to(Line::from(*other).foot())
     }
 
-    /// A n'utiliser que si vous êtes sûr de ce que vous faites,
-    /// *Vous transformez une droite en point !*
-    fn _to_polar_point(&self) -> PolarPoint {
-        PolarPoint {
-            distance: self.distance,
-            angle: self.angle,
-        }
-    }
-
-    /// Calcule le point d'intersection de deux droites
-    /// Les droites ne doivent pas être parallèles pour que le résultat aie du sens
-    /// Redémontrer avec r = r0 / cos(theta - theta0)
-    fn intersect(&self, other: &Self) -> Option<PolarPoint> {
-        // angles between 0-2pi
-        let self_angle_wrap = Rad::new((self.angle + Rad::FULL_TURN).val() % Rad::FULL_TURN.val());
-        let other_angle_wrap =
-            Rad::new((other.angle + Rad::FULL_TURN).val() % Rad::FULL_TURN.val());
-        if self.is_parallel_with(other) {
-            // Les droites sont alignés
-            return None;
-        }
-        let theta = Rad::atan2(
-            other.distance.0 * self_angle_wrap.cos() - self.distance.0 * other_angle_wrap.cos(),
-            self.distance.0 * other_angle_wrap.sin() - other.distance.0 * self_angle_wrap.sin(),
-        );
-
-        let theta = Rad::new((theta + Rad::FULL_TURN).val() % Rad::FULL_TURN.val());
-
-        let r = Meters(self.distance.0 / (theta - self_angle_wrap).cos());
-        Some(PolarPoint {
-            distance: r,
-            angle: theta,
-        })
+    /// Calcule le point d'intersection de deux droites en coordonnées carthésiennes.
+    /// Renvoie `None` si les droites sont parallèles ou confondues.
+    /// Fonctionne avec les deux conventions (distance négative ou angle > 180),
+    /// y compris pour les droites passant par l'origine.
+    fn intersect(&self, other: &Self) -> Option<Point> {
+        Line::from(*self).intersect(&Line::from(*other))
     }
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HoughLine {
     pub line: PolarLine,
     pub weight: u16,
@@ -140,7 +99,7 @@ pub struct HoughLine {
 
 #[inline]
 fn check_around(
-    accumulator: &mut [[u16; ANGLE_TAILLE]; DISTANCE_TAILLE],
+    accumulator: &mut HoughAccumulator,
     distance_case: usize,
     angle_case: usize,
     dist: usize,
@@ -185,7 +144,7 @@ fn check_around(
 const HOUGH_TRANSFORM_MIN_POINT_PER_LINE: u16 = 30;
 
 /// distance maximale en dessous de laquelle les points détectés du lidar sont conservés
-const LIDAR_DISTANCE_MAX: Meters = Meters(3.0);
+pub const LIDAR_DISTANCE_MAX: Meters = Meters(3.0);
 
 /// angle auquel on crée une nouvelle ligne dans la transformation de Hough
 const ANGLE_RESOLUTION: f64 = 1.0 / 180.0 * f64::consts::PI;
@@ -201,6 +160,30 @@ const DISTANCE_RESOLUTION: Meters = Meters::cm(1.0);
 /// On compte 2x car il y a les distances positives et négatives
 const DISTANCE_TAILLE: usize = (LIDAR_DISTANCE_MAX.0 * 2.0 / DISTANCE_RESOLUTION.0) as usize + 1;
 
+/// Matrice de la transformation de Hough, indexée par `[distance][angle]`
+pub type HoughAccumulator = [[u16; ANGLE_TAILLE]; DISTANCE_TAILLE];
+
+/// Nouvel accumulateur vide. Il est placé sur le tas, la matrice étant trop grande pour être copiée
+pub fn empty_accumulator() -> Box<HoughAccumulator> {
+    Box::new([[0; ANGLE_TAILLE]; DISTANCE_TAILLE])
+}
+
+/// Poids de l'accumulateur ramenés entre 0 et 255 (le poids maximal), ligne par ligne
+pub fn accumulator_pixels(accumulator: &HoughAccumulator) -> Vec<u8> {
+    let max = accumulator
+        .iter()
+        .flat_map(|row| row.iter())
+        .copied()
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    accumulator
+        .iter()
+        .flat_map(|row| row.iter())
+        .map(|weight| (u32::from(*weight) * 255 / u32::from(max)) as u8)
+        .collect()
+}
+
 #[inline]
 fn polar_point_to_case_angle_only(angle: Rad) -> usize {
     (angle / Rad::new(ANGLE_RESOLUTION)) as usize
@@ -224,85 +207,14 @@ fn polar_point_to_case(point: PolarPoint) -> (usize, usize) {
     (distance_case, angle_case)
 }
 
-/// renvoit une ligne qui respecte la convention (distance positive, angle > 180)
-fn case_to_polar_line(distance_case: usize, angle_case: usize) -> PolarLine {
-    let mut angle_offset = Rad::ZERO;
-    let distance_corrected = if distance_case >= DISTANCE_TAILLE / 2 {
-        distance_case - DISTANCE_TAILLE / 2
-    } else {
-        angle_offset = Rad::HALF_TURN;
-        distance_case
-    };
-
-    PolarLine {
-        distance: DISTANCE_RESOLUTION * distance_corrected as f64,
-        angle: angle_offset + (Rad::new(ANGLE_RESOLUTION) * angle_case as f64),
-    }
-}
-
 /// Attention compte pour 2, un en positif, un en négatif
 const MARGE_DISTANCE: i32 = 10;
 const MARGE_ANGLE: i32 = 10;
 
-#[inline]
-fn look_around_for_lines_angle_only(
-    accumulator: &[[u16; ANGLE_TAILLE]; DISTANCE_TAILLE],
-    distance_case_applied: usize,
-    wanted_angle_case: usize,
-) -> Vec<HoughLine> {
-    let mut found_lines = Vec::new();
-    for alea_angle in -MARGE_ANGLE..=MARGE_ANGLE {
-        if (wanted_angle_case as i32) + alea_angle < 0
-            || (wanted_angle_case as i32) + alea_angle >= ANGLE_TAILLE as i32
-        {
-            // out of bounds
-            continue;
-        }
-
-        let angle_case_applied = (wanted_angle_case as i32 + alea_angle) as usize;
-        let found_weight = accumulator[distance_case_applied][angle_case_applied];
-        if found_weight > HOUGH_TRANSFORM_MIN_POINT_PER_LINE {
-            let found_line = HoughLine {
-                line: case_to_polar_line(distance_case_applied, angle_case_applied),
-                weight: found_weight,
-            };
-            //println!("found : {:?}", found_line);
-            found_lines.push(found_line);
-        } else {
-            //println!("too small {:?} {} {}", distance_case_applied, angle_case_applied, weight);
-        }
-    }
-    found_lines
-}
-
-#[inline]
-fn look_around_for_lines(
-    accumulator: &[[u16; ANGLE_TAILLE]; DISTANCE_TAILLE],
-    wanted_distance_case: usize,
-    wanted_angle_case: usize,
-) -> Vec<HoughLine> {
-    let mut found_lines = Vec::new();
-    for alea_distance in -MARGE_DISTANCE..=MARGE_DISTANCE {
-        if (wanted_distance_case as i32) + alea_distance < 0
-            || (wanted_distance_case as i32) + alea_distance >= DISTANCE_TAILLE as i32
-        {
-            // out of bounds
-            continue;
-        }
-        let distance_case_applied = (wanted_distance_case as i32 + alea_distance) as usize;
-        found_lines.append(&mut look_around_for_lines_angle_only(
-            accumulator,
-            distance_case_applied,
-            wanted_angle_case,
-        ));
-    }
-    found_lines
-}
-
 /// guide : <https://www.keymolen.com/2013/05/hough-transformation-c-implementation.html>
-fn build_hough_accumulator(
-    accumulator: &mut [[u16; ANGLE_TAILLE]; DISTANCE_TAILLE],
-    points: &Vec<LidarPoint>,
+pub fn build_hough_accumulator(
+    accumulator: &mut HoughAccumulator,
+    points: &[LidarPoint],
 ) {
     // Création de la matrice de la transformation de Hough
     //let mut accumulator = [[0u16; ANGLE_TAILLE]; DISTANCE_TAILLE];
@@ -338,100 +250,470 @@ fn build_hough_accumulator(
     }
 }
 
-fn conv_convention_big_angle_to_negative_distance(line: PolarLine) -> PolarLine {
-    PolarLine {
-        distance: if line.angle > Rad::HALF_TURN {
-            -line.distance
-        } else {
-            line.distance
-        },
-        angle: if line.angle > Rad::HALF_TURN {
-            line.angle - Rad::HALF_TURN
-        } else {
-            line.angle
-        },
+/// nombre de cases d'angle pour un demi-tour, la dernière case de l'accumulateur (180°)
+/// est la même droite que la première avec une distance opposée
+const HALF_TURN_CASES: i32 = ANGLE_TAILLE as i32 - 1;
+
+/// rayon *en cases* de la suppression des non-maxima :
+/// une case n'est un pic que si elle est la plus forte de son voisinage
+const NMS_DISTANCE_RADIUS: i32 = 4;
+const NMS_ANGLE_RADIUS: i32 = 4;
+
+/// rayon *en cases* du voisinage d'un pic utilisé pour affiner sa droite
+const REFINE_RADIUS: i32 = 2;
+
+/// deux pics plus proches que cela sont considérés comme le même mur et fusionnés
+const MERGE_DISTANCE_TOLERANCE: Meters = Meters::cm(6.0);
+/// en radians
+const MERGE_ANGLE_TOLERANCE: f64 = 6.0 * ANGLE_RESOLUTION;
+
+/// écart maximal *en radians* au parallélisme pour une paire de murs opposés,
+/// et à l'angle droit pour un mur perpendiculaire
+const PAIR_ANGLE_TOLERANCE: f64 = MARGE_ANGLE as f64 * ANGLE_RESOLUTION;
+/// écart maximal à la distance attendue entre deux murs opposés
+const PAIR_DISTANCE_TOLERANCE: Meters = Meters(MARGE_DISTANCE as f64 * DISTANCE_RESOLUTION.0);
+
+/// Seuils de la recherche des droites dans l'accumulateur.
+///
+/// `HoughConfig::default()` donne les valeurs utilisées sur le robot (les constantes ci-dessus),
+/// l'explorateur (`hough_explorer`) permet de les modifier pour voir leur effet sur une capture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HoughConfig {
+    /// on exclut les lignes qui contiennent moins de points que cela
+    pub min_points_per_line: u16,
+    /// rayons *en cases* de la suppression des non-maxima
+    pub nms_distance_radius: i32,
+    pub nms_angle_radius: i32,
+    /// rayon *en cases* du voisinage d'un pic utilisé pour affiner sa droite
+    pub refine_radius: i32,
+    /// deux pics plus proches que cela sont considérés comme le même mur et fusionnés
+    pub merge_distance_tolerance: Meters,
+    /// en radians
+    pub merge_angle_tolerance: f64,
+    /// écart maximal *en radians* au parallélisme pour une paire de murs opposés,
+    /// et à l'angle droit pour un mur perpendiculaire
+    pub pair_angle_tolerance: f64,
+    /// écart maximal à la distance attendue entre deux murs opposés
+    pub pair_distance_tolerance: Meters,
+}
+
+impl HoughConfig {
+    pub const DEFAULT: Self = Self {
+        min_points_per_line: HOUGH_TRANSFORM_MIN_POINT_PER_LINE,
+        nms_distance_radius: NMS_DISTANCE_RADIUS,
+        nms_angle_radius: NMS_ANGLE_RADIUS,
+        refine_radius: REFINE_RADIUS,
+        merge_distance_tolerance: MERGE_DISTANCE_TOLERANCE,
+        merge_angle_tolerance: MERGE_ANGLE_TOLERANCE,
+        pair_angle_tolerance: PAIR_ANGLE_TOLERANCE,
+        pair_distance_tolerance: PAIR_DISTANCE_TOLERANCE,
+    };
+}
+
+impl Default for HoughConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
     }
 }
 
-fn search_all_parallel_lines(
-    accumulator: &[[u16; ANGLE_TAILLE]; DISTANCE_TAILLE],
-    distance_between_lines: Meters,
-) -> Vec<(HoughLine, HoughLine)> {
-    let mut found_lines = Vec::new();
+/// Indice de distance signé : la case `s` couvre les distances de `s` à `s + 1` fois `DISTANCE_RESOLUTION`,
+/// les distances négatives ayant un indice négatif
+#[inline]
+fn signed_distance_case(distance_case: usize) -> i32 {
+    if distance_case >= DISTANCE_TAILLE / 2 {
+        (distance_case - DISTANCE_TAILLE / 2) as i32
+    } else {
+        -(distance_case as i32) - 1
+    }
+}
+
+/// Droite au centre de la case `[distance_case][angle_case]` de l'accumulateur
+pub fn accumulator_cell_line(distance_case: usize, angle_case: usize) -> PolarLine {
+    Line::new(
+        DISTANCE_RESOLUTION * (f64::from(signed_distance_case(distance_case)) + 0.5),
+        Rad::new(ANGLE_RESOLUTION) * angle_case as f64,
+    )
+    .into()
+}
+
+/// Case `(distance, angle)` de l'accumulateur la plus proche de `line`,
+/// `None` si la droite est trop loin du robot pour y être
+pub fn accumulator_cell(line: PolarLine) -> Option<(usize, usize)> {
+    let line = Line::from(line);
+    let (mut distance, mut angle) = (line.distance(), line.angle());
+    // l'accumulateur ne contient que les angles de [0, 180°[, avec des distances négatives
+    if angle >= Rad::HALF_TURN {
+        distance = -distance;
+        angle -= Rad::HALF_TURN;
+    }
+    let mut signed_distance = (distance / DISTANCE_RESOLUTION).floor() as i32;
+    let mut angle_case = (angle / Rad::new(ANGLE_RESOLUTION)).round() as i32;
+    if angle_case >= HALF_TURN_CASES {
+        angle_case -= HALF_TURN_CASES;
+        signed_distance = -signed_distance - 1;
+    }
+    let distance_case = if signed_distance >= 0 {
+        DISTANCE_TAILLE / 2 + signed_distance as usize
+    } else {
+        let distance_case = (-signed_distance - 1) as usize;
+        if distance_case >= DISTANCE_TAILLE / 2 {
+            return None;
+        }
+        distance_case
+    };
+    (distance_case < DISTANCE_TAILLE).then_some((distance_case, angle_case as usize))
+}
+
+/// Renvoit le poids et la position de la case (distance signée, angle) en prolongeant l'accumulateur
+/// au-delà de [0, 180°[ : la droite (s, a + 180°) est la droite (-s - 1, a).
+/// `None` si la case est hors de l'accumulateur.
+fn weight_at(
+    accumulator: &HoughAccumulator,
+    signed_distance: i32,
+    angle_case: i32,
+) -> Option<(u16, usize, usize)> {
+    let (mut signed_distance, mut angle_case) = (signed_distance, angle_case);
+    while angle_case < 0 {
+        angle_case += HALF_TURN_CASES;
+        signed_distance = -signed_distance - 1;
+    }
+    while angle_case >= HALF_TURN_CASES {
+        angle_case -= HALF_TURN_CASES;
+        signed_distance = -signed_distance - 1;
+    }
+    let distance_case = if signed_distance >= 0 {
+        DISTANCE_TAILLE / 2 + signed_distance as usize
+    } else {
+        let distance_case = (-signed_distance - 1) as usize;
+        if distance_case >= DISTANCE_TAILLE / 2 {
+            return None;
+        }
+        distance_case
+    };
+    let weight = accumulator.get(distance_case)?[angle_case as usize];
+    Some((weight, distance_case, angle_case as usize))
+}
 
-    // TODO diviser le nombre par deux ? (for p_distance in (DISTANCE_TAILLE/2)..DISTANCE_TAILLE)
-    for p_angle in 0..ANGLE_TAILLE {
-        for p_distance in (DISTANCE_TAILLE/2)..DISTANCE_TAILLE {
-            let weight = accumulator[p_distance][p_angle];
-            if weight < HOUGH_TRANSFORM_MIN_POINT_PER_LINE {
+/// Moyenne pondérée des cases autour d'un pic, en ne gardant que celles d'au moins la moitié du pic
+fn refine_peak(
+    accumulator: &HoughAccumulator,
+    signed_distance: i32,
+    angle_case: i32,
+    refine_radius: i32,
+) -> Line {
+    let (_, peak_distance_case, peak_angle_case) =
+        weight_at(accumulator, signed_distance, angle_case).unwrap();
+    let threshold = accumulator[peak_distance_case][peak_angle_case] / 2;
+    let (mut distance_sum, mut angle_sum, mut weight_sum) = (0.0, 0.0, 0.0);
+    for alea_distance in -refine_radius..=refine_radius {
+        for alea_angle in -refine_radius..=refine_radius {
+            let (distance, angle) = (signed_distance + alea_distance, angle_case + alea_angle);
+            let Some((weight, _, _)) = weight_at(accumulator, distance, angle) else {
+                continue;
+            };
+            if weight < threshold {
                 continue;
             }
-            // trace!("line at {} {}", p_angle, p_distance);
-            let line = case_to_polar_line(p_distance, p_angle);
+            let weight = f64::from(weight);
+            // centre de la case
+            distance_sum += weight * (f64::from(distance) + 0.5);
+            angle_sum += weight * f64::from(angle);
+            weight_sum += weight;
+        }
+    }
+    // la case du pic est toujours comptée donc weight_sum > 0
+    Line::new(
+        DISTANCE_RESOLUTION * (distance_sum / weight_sum),
+        Rad::new(ANGLE_RESOLUTION) * (angle_sum / weight_sum),
+    )
+}
 
-            // 0 <= angle <= 180 donc pas besoin de bound check
-            // penser à prendre la distance en négatif quand cal_angle > 180
-            // let wanted_angle_for_perpendicular = angle.to_deg().rad() + Rad::QUARTER_TURN;
+/// Fusionne les pics qui représentent le même mur, le plus fort donne son poids au résultat
+fn merge_close_peaks(mut peaks: Vec<HoughLine>, config: &HoughConfig) -> Vec<HoughLine> {
+    peaks.sort_by_key(|peak| std::cmp::Reverse(peak.weight));
+    let mut merged: Vec<(HoughLine, LineMean)> = Vec::with_capacity(peaks.len());
+    for peak in peaks {
+        let line = Line::from(peak.line);
+        let same_wall = merged.iter_mut().find(|(kept, _)| {
+            let kept = Line::from(kept.line);
+            kept.is_parallel_with(&line, Rad::new(config.merge_angle_tolerance))
+                && line.distance_to(kept.foot()) <= config.merge_distance_tolerance
+        });
+        match same_wall {
+            Some((_, mean)) => mean.add(line, f64::from(peak.weight)),
+            None => {
+                let mut mean = LineMean::new(line);
+                mean.add(line, f64::from(peak.weight));
+                merged.push((peak, mean));
+            }
+        }
+    }
+    merged
+        .into_iter()
+        .map(|(peak, mean)| HoughLine {
+            line: mean.mean().into(),
+            weight: peak.weight,
+        })
+        .collect()
+}
+
+/// Renvoit les droites de l'accumulateur, une seule par mur, triées par poids décroissant.
+///
+/// Une case n'est gardée que si elle est la plus forte de son voisinage (suppression des non-maxima),
+/// sa droite est affinée avec les cases voisines puis les pics proches sont fusionnés.
+pub fn hough_peaks(accumulator: &HoughAccumulator, config: &HoughConfig) -> Vec<HoughLine> {
+    let mut peaks = Vec::new();
+    for distance_case in 0..DISTANCE_TAILLE {
+        let signed_distance = signed_distance_case(distance_case);
+        for angle_case in 0..HALF_TURN_CASES as usize {
+            let weight = accumulator[distance_case][angle_case];
+            if weight < config.min_points_per_line {
+                continue;
+            }
+            // à poids égal on départage par la position pour n'avoir qu'un pic par plateau
+            let key = (weight, distance_case, angle_case);
+            let (distance_radius, angle_radius) =
+                (config.nms_distance_radius, config.nms_angle_radius);
+            let is_peak = (-distance_radius..=distance_radius).all(|alea_distance| {
+                (-angle_radius..=angle_radius).all(|alea_angle| {
+                    weight_at(
+                        accumulator,
+                        signed_distance + alea_distance,
+                        angle_case as i32 + alea_angle,
+                    )
+                    .is_none_or(|neighbour| neighbour <= key)
+                })
+            });
+            if is_peak {
+                peaks.push(HoughLine {
+                    line: refine_peak(
+                        accumulator,
+                        signed_distance,
+                        angle_case as i32,
+                        config.refine_radius,
+                    )
+                    .into(),
+                    weight,
+                });
+            }
+        }
+    }
+    merge_close_peaks(peaks, config)
+}
 
-            // convention distance négative angle < 180
-            let sign_line = conv_convention_big_angle_to_negative_distance(line);
+/// Une paire de droites parallèles, candidate pour deux murs opposés du terrain
+#[derive(Debug, Clone, Copy)]
+pub struct ParallelCandidate {
+    /// la plus forte des deux
+    pub first: HoughLine,
+    pub second: HoughLine,
+    /// accord avec le modèle du terrain entre 0 et 1 :
+    /// 1 si les droites sont exactement parallèles et séparées de la distance attendue
+    pub consistency: f64,
+    /// répartition des points le long des droites entre 0 et 1, voir `check_support`.
+    /// Vaut 1 tant que les points n'ont pas été examinés.
+    pub support: f64,
+}
 
-            // rappel angle est entre 0 et 180
-            let wanted_angle_for_parallel = sign_line.angle;
+impl ParallelCandidate {
+    #[inline]
+    pub fn lines(&self) -> [HoughLine; 2] {
+        [self.first, self.second]
+    }
 
-            // on teste uniquement "en dessous" car le robot (l'origine) doit toujours être à l'intérieur du terrain
-            let wanted_distance_for_parallel = sign_line.distance - distance_between_lines;
-            // TODO assert!(distance.to_meters().0 * wanted_distance_for_parallel.0 > 0.0)
+    /// Plus le score est élevé, plus la paire a de chances d'être une paire de murs
+    #[inline]
+    pub fn score(&self) -> f64 {
+        (f64::from(self.first.weight) + f64::from(self.second.weight))
+            * self.consistency
+            * self.support
+    }
+}
 
-            let (wanted_distance_case, wanted_angle_case) = polar_point_to_case(PolarPoint {
-                distance: wanted_distance_for_parallel,
-                angle: wanted_angle_for_parallel,
-            });
+/// Accord de deux droites avec deux murs opposés séparés de `expected_gap`,
+/// `None` si elles ne peuvent pas être deux murs opposés
+fn parallel_consistency(
+    first: PolarLine,
+    second: PolarLine,
+    expected_gap: Meters,
+    config: &HoughConfig,
+) -> Option<f64> {
+    let (first, second) = (Line::from(first), Line::from(second));
+    // le robot (l'origine) doit toujours être à l'intérieur du terrain, donc entre les deux murs :
+    // les normales des deux droites sont opposées
+    if wrap_angle_signed(first.angle() - second.angle()).mag() < Rad::QUARTER_TURN {
+        return None;
+    }
+    let angle_error = first.angle_with(&second);
+    let gap_error = (first.distance() + second.distance() - expected_gap).abs();
+    let (angle_tolerance, distance_tolerance) =
+        (config.pair_angle_tolerance, config.pair_distance_tolerance);
+    if angle_error > Rad::new(angle_tolerance) || gap_error > distance_tolerance {
+        return None;
+    }
+    Some((1.0 - angle_error.val() / angle_tolerance) * (1.0 - gap_error / distance_tolerance))
+}
 
-            found_lines.extend(
-                look_around_for_lines(&accumulator, wanted_distance_case, wanted_angle_case)
-                    .iter()
-                    .map(|e| (HoughLine { line, weight }, *e)),
-            );
+/// Renvoit les paires de `peaks` (voir `hough_peaks`) pouvant être deux murs opposés séparés
+/// de `distance_between_lines`, triées par score décroissant.
+pub fn search_all_parallel_lines(
+    peaks: &[HoughLine],
+    distance_between_lines: Meters,
+    config: &HoughConfig,
+) -> Vec<ParallelCandidate> {
+    let mut found_lines = Vec::new();
+    for (n_first, first) in peaks.iter().enumerate() {
+        for second in peaks[n_first + 1..].iter() {
+            if let Some(consistency) =
+                parallel_consistency(first.line, second.line, distance_between_lines, config)
+            {
+                found_lines.push(ParallelCandidate {
+                    first: *first,
+                    second: *second,
+                    consistency,
+                    support: 1.0,
+                });
+            }
         }
     }
+    found_lines.sort_by(|a, b| b.score().total_cmp(&a.score()));
     found_lines
 }
 
-fn search_perpendicular_lines_of(
-    accumulator: &[[u16; ANGLE_TAILLE]; DISTANCE_TAILLE],
+/// Renvoit les droites de `peaks` (voir `hough_peaks`) perpendiculaires à `line`,
+/// dans le même ordre (poids décroissant)
+pub fn search_perpendicular_lines_of(
+    peaks: &[HoughLine],
     line: PolarLine,
+    config: &HoughConfig,
 ) -> Vec<HoughLine> {
-    // let (distance_case, angle_case) = polar_point_to_case(line._to_polar_point());
-    let mut found_lines = Vec::new();
-    let line = conv_convention_big_angle_to_negative_distance(line);
-    let wanted_angle = line.angle + Rad::QUARTER_TURN; // ICI si wangle > 90
-    let PolarLine {
-        angle: wanted_angle_corrected,
-        distance: _,
-    } = conv_convention_big_angle_to_negative_distance(PolarLine {
-        distance: line.distance,
-        angle: wanted_angle,
-    });
-    let wanted_angle_case = polar_point_to_case_angle_only(wanted_angle_corrected);
-
-    for p_distance in 0..DISTANCE_TAILLE {
-        found_lines.append(&mut look_around_for_lines_angle_only(
-            accumulator,
-            p_distance,
-            wanted_angle_case,
-        ));
+    let line = Line::from(line);
+    peaks
+        .iter()
+        .filter(|peak| {
+            Line::from(peak.line)
+                .is_perpendicular_with(&line, Rad::new(config.pair_angle_tolerance))
+        })
+        .copied()
+        .collect()
+}
+
+/// un point appartient à une droite s'il en est plus proche que cela
+pub const SUPPORT_DISTANCE_TOLERANCE: Meters = Meters::cm(3.0);
+/// au-delà de cet écart entre deux points consécutifs d'une droite, on commence un nouveau segment
+const SUPPORT_MAX_GAP: Meters = Meters::cm(12.0);
+/// les segments de moins de points que cela sont du bruit qui croise la droite par hasard
+const SUPPORT_MIN_POINTS_PER_SEGMENT: usize = 3;
+/// les segments plus courts que cela sont des murs qui croisent la droite, pas la droite elle-même
+const SUPPORT_MIN_SEGMENT_LENGTH: Meters = Meters::cm(5.0);
+/// fraction minimale de l'étendue visible d'un mur que ses points doivent couvrir.
+/// Sur les captures, les buts et les robots cachent jusqu'à la moitié d'un mur,
+/// alors que la face d'un robot ne dépasse pas 15 % de la largeur du terrain.
+const SUPPORT_MIN_SPREAD_RATIO: f64 = 0.4;
+
+/// Répartition des points du lidar le long d'une droite.
+///
+/// Un robot placé entre nous et un mur forme une droite courte, et masque une partie du mur :
+/// la droite d'un vrai mur est soutenue par des points répartis sur toute sa longueur.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineSupport {
+    /// nombre de points des segments retenus
+    pub points: usize,
+    /// longueur du plus long segment de points contigus
+    pub length: Meters,
+    /// distance entre les extrémités du premier et du dernier segment
+    pub spread: Meters,
+    /// part de `spread` réellement couverte par des segments, entre 0 et 1
+    pub coverage: f64,
+}
+
+impl LineSupport {
+    /// Découpe les points proches de la droite en segments de points contigus
+    pub fn measure(points: &[LidarPoint], line: PolarLine) -> Self {
+        let line = Line::from(line);
+        let direction = Vector::from_polar(Meters(1.0), line.direction());
+        let mut abscissas: Vec<f64> = points
+            .iter()
+            .filter(|point| point.point.distance <= LIDAR_DISTANCE_MAX)
+            .map(|point| Point::from(&point.point))
+            .filter(|point| line.distance_to(*point) <= SUPPORT_DISTANCE_TOLERANCE)
+            .map(|point| point.to_vector().dot(direction))
+            .collect();
+        abscissas.sort_by(f64::total_cmp);
+
+        let segments: Vec<&[f64]> = abscissas
+            .chunk_by(|a, b| b - a <= SUPPORT_MAX_GAP.0)
+            .filter(|segment| {
+                segment.len() >= SUPPORT_MIN_POINTS_PER_SEGMENT
+                    && segment[segment.len() - 1] - segment[0] >= SUPPORT_MIN_SEGMENT_LENGTH.0
+            })
+            .collect();
+        let segment_length = |segment: &&[f64]| segment[segment.len() - 1] - segment[0];
+        let (Some(first), Some(last)) = (segments.first(), segments.last()) else {
+            return Self {
+                points: 0,
+                length: Meters(0.0),
+                spread: Meters(0.0),
+                coverage: 0.0,
+            };
+        };
+        let spread = last[last.len() - 1] - first[0];
+        let covered: f64 = segments.iter().map(segment_length).sum();
+        Self {
+            points: segments.iter().map(|segment| segment.len()).sum(),
+            length: Meters(segments.iter().map(segment_length).fold(0.0, f64::max)),
+            spread: Meters(spread),
+            coverage: if spread > 0.0 { covered / spread } else { 0.0 },
+        }
     }
 
-    found_lines
+    /// Facteur entre 0 et 1 comparant les points à un mur de longueur `wall_extent` porté par `line`,
+    /// `None` si les points ne couvrent pas l'essentiel de la partie du mur à portée du lidar
+    pub fn factor(&self, line: PolarLine, wall_extent: Meters) -> Option<f64> {
+        // seule la partie de la droite à moins de LIDAR_DISTANCE_MAX du robot peut être vue
+        let distance = line.distance.abs().0.min(LIDAR_DISTANCE_MAX.0);
+        let visible = Meters(2.0 * (LIDAR_DISTANCE_MAX.0.powi(2) - distance.powi(2)).sqrt());
+        let expected = if visible < wall_extent { visible } else { wall_extent };
+        if self.spread < expected * SUPPORT_MIN_SPREAD_RATIO {
+            return None;
+        }
+        Some(self.coverage * (self.spread / expected).min(1.0))
+    }
+}
+
+/// Pondère chaque paire par la répartition des points le long de ses deux droites
+/// et retire celles dont une droite ne couvre pas l'essentiel d'un mur de longueur `wall_extent`.
+/// Les paires restantes sont triées par score décroissant.
+pub fn check_support(
+    points: &[LidarPoint],
+    candidates: Vec<ParallelCandidate>,
+    wall_extent: Meters,
+) -> Vec<ParallelCandidate> {
+    let factor = |line: &HoughLine| LineSupport::measure(points, line.line).factor(line.line, wall_extent);
+    let mut checked: Vec<ParallelCandidate> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let support = factor(&candidate.first)? * factor(&candidate.second)?;
+            Some(ParallelCandidate {
+                support,
+                ..candidate
+            })
+        })
+        .collect();
+    checked.sort_by(|a, b| b.score().total_cmp(&a.score()));
+    checked
 }
 
 // TODO should not be here
-const FIELD_LENGTH: Meters = Meters::cm(243.0);
+pub const FIELD_LENGTH: Meters = Meters::cm(243.0);
 // TODO should not be here
-const FIELD_WIDTH: Meters = Meters::cm(182.0);
+pub const FIELD_WIDTH: Meters = Meters::cm(182.0);
 
+/// Sérialisé comme `{"found_as_parallel_line": {..}}`, les noms ne doivent plus changer
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum WallLine {
     FoundAsParallelLine(HoughLine),
     FoundAsPerpendicular(HoughLine),
@@ -449,179 +731,67 @@ impl WallLine {
     }
 }
 
-fn _anc_locate_field_with_4_walls(
-    candidate_line_width: &Vec<(HoughLine, HoughLine)>,
-    candidate_line_length: &Vec<(HoughLine, HoughLine)>,
+pub fn locate_field_with_4_walls(
+    candidate_line_width: &[ParallelCandidate],
+    candidate_line_length: &[ParallelCandidate],
 ) -> Option<FieldWalls> {
     let mut score = 0.;
     let mut field: Option<FieldWalls> = None;
     for parallel_width in candidate_line_width.iter() {
         for parallel_length in candidate_line_length.iter() {
             if parallel_width
-                .0
+                .first
                 .line
-                .is_approx_perpendicular_with(&parallel_length.0.line)
+                .is_approx_perpendicular_with(&parallel_length.first.line)
             {
                 // la paire fonctionne
-                let this_score = f64::from(parallel_width.0.weight)
-                    * parallel_width.0.line.distance.0
-                    + f64::from(parallel_width.1.weight) * parallel_width.1.line.distance.0
-                    + f64::from(parallel_length.0.weight) * parallel_length.0.line.distance.0
-                    + f64::from(parallel_length.1.weight) * parallel_length.1.line.distance.0;
+                let this_score = parallel_width.score() + parallel_length.score();
                 if this_score > score {
                     score = this_score;
                     field = Some(FieldWalls {
-                        width1: WallLine::FoundAsParallelLine(parallel_width.0),
-                        width2: WallLine::FoundAsParallelLine(parallel_width.1),
-                        length1: WallLine::FoundAsParallelLine(parallel_length.0),
-                        length2: WallLine::FoundAsParallelLine(parallel_length.1),
+                        width1: WallLine::FoundAsParallelLine(parallel_width.first),
+                        width2: WallLine::FoundAsParallelLine(parallel_width.second),
+                        length1: WallLine::FoundAsParallelLine(parallel_length.first),
+                        length2: WallLine::FoundAsParallelLine(parallel_length.second),
                     })
                 }
             }
         }
     }
-    field
-}
 
-fn locate_field_with_4_walls(
-    candidate_line_width: &Vec<(HoughLine, HoughLine)>,
-    candidate_line_length: &Vec<(HoughLine, HoughLine)>,
-) -> Option<FieldWalls> {
-    let mut score = 0.;
-    let mut field: Option<FieldWalls> = None;
-    for parallel_width in candidate_line_width.iter() {
-        for parallel_length in candidate_line_length.iter() {
-            if parallel_width
-                .0
-                .line
-                .is_approx_perpendicular_with(&parallel_length.0.line)
-            {
-                // la paire fonctionne
-                let this_score = f64::from(parallel_width.0.weight)
-                    * parallel_width.0.line.distance.0
-                    + f64::from(parallel_width.1.weight) * parallel_width.1.line.distance.0
-                    + f64::from(parallel_length.0.weight) * parallel_length.0.line.distance.0
-                    + f64::from(parallel_length.1.weight) * parallel_length.1.line.distance.0;
-                if this_score > score {
-                    score = this_score;
-                    field = Some(FieldWalls {
-                        width1: WallLine::FoundAsParallelLine(parallel_width.0),
-                        width2: WallLine::FoundAsParallelLine(parallel_width.1),
-                        length1: WallLine::FoundAsParallelLine(parallel_length.0),
-                        length2: WallLine::FoundAsParallelLine(parallel_length.1),
-                    })
-                }
-            }
-        }
-    }
+    let walls = field?;
 
-    let Some(walls) = field else {
-        return None;
-    };
-    
-    let time1 = std::time::Instant::now();
-    let moy_walls = perform_moy(walls, candidate_line_width, candidate_line_length);
-    info!("perf perform_moy for 4 walls, took : {:?}", time1.elapsed());
-    Some(moy_walls)
+    Some(metrics::time("perform_moy", || {
+        perform_moy(walls, candidate_line_width, candidate_line_length)
+    }))
 }
 
-fn perform_moy(
+/// Moyenne chaque mur avec les droites candidates qui en sont proches, pondérées par leur poids
+pub fn perform_moy(
     walls: FieldWalls,
-    candidate_line_width: &Vec<(HoughLine, HoughLine)>,
-    candidate_line_length: &Vec<(HoughLine, HoughLine)>,
+    candidate_line_width: &[ParallelCandidate],
+    candidate_line_length: &[ParallelCandidate],
 ) -> FieldWalls {
-    let mut moy_parallel_width1 = (
-        walls.width1.line().distance,
-        walls.width1.line().angle,
-        1.,
-    );
-    let mut moy_parallel_width2 = (
-        walls.width2.line().distance,
-        walls.width2.line().angle,
-        1.,
-    );
-    let mut moy_parallel_length1 = (
-        walls.length1.line().distance,
-        walls.length1.line().angle,
-        1.,
-    );
-    let mut moy_parallel_length2 = (
-        walls.length2.line().distance,
-        walls.length2.line().angle,
-        1.,
-    );
-
-    for parallel_width in candidate_line_width.iter().map(|e| [e.0, e.1]).flatten() {
-        if _is_near(&parallel_width.line, &walls.width1.line()) {
-            moy_parallel_width1 = (
-                moy_parallel_width1.0
-                    + parallel_width.line.distance * f64::from(parallel_width.weight),
-                moy_parallel_width1.1
-                    + parallel_width.line.angle * f64::from(parallel_width.weight),
-                moy_parallel_width1.2 + f64::from(parallel_width.weight),
-            );
-        }
-        if _is_near(&parallel_width.line, &walls.width2.line()) {
-            moy_parallel_width2 = (
-                moy_parallel_width2.0
-                    + parallel_width.line.distance * f64::from(parallel_width.weight),
-                moy_parallel_width2.1
-                    + parallel_width.line.angle * f64::from(parallel_width.weight),
-                moy_parallel_width2.2 + f64::from(parallel_width.weight),
-            );
-        }
-    }
-
-    for parallel_length in candidate_line_length.iter().map(|e| [e.0, e.1]).flatten() {
-        if _is_near(&parallel_length.line, &walls.length1.line()) {
-            moy_parallel_length1 = (
-                moy_parallel_length1.0
-                    + parallel_length.line.distance * f64::from(parallel_length.weight),
-                moy_parallel_length1.1
-                    + parallel_length.line.angle * f64::from(parallel_length.weight),
-                moy_parallel_length1.2 + f64::from(parallel_length.weight),
-            );
-        }
-        if _is_near(&parallel_length.line, &walls.length2.line()) {
-            moy_parallel_length2 = (
-                moy_parallel_length2.0
-                    + parallel_length.line.distance * f64::from(parallel_length.weight),
-                moy_parallel_length2.1
-                    + parallel_length.line.angle * f64::from(parallel_length.weight),
-                moy_parallel_length2.2 + f64::from(parallel_length.weight),
-            );
+    let moy = |wall: &WallLine, candidates: &[ParallelCandidate]| {
+        let wall = wall.line();
+        let mut mean = LineMean::new(Line::from(wall));
+        mean.add(Line::from(wall), 1.);
+        for candidate in candidates.iter().flat_map(ParallelCandidate::lines) {
+            if _is_near(&candidate.line, &wall) {
+                mean.add(Line::from(candidate.line), f64::from(candidate.weight));
+            }
         }
-    }
+        WallLine::FoundAsParallelLine(HoughLine {
+            line: mean.mean().into(),
+            weight: 500, // TODO
+        })
+    };
 
     FieldWalls {
-        width1: WallLine::FoundAsParallelLine(HoughLine {
-            line: PolarLine {
-                distance: moy_parallel_width1.0 / moy_parallel_width1.2,
-                angle: moy_parallel_width1.1 / moy_parallel_width1.2,
-            },
-            weight: 500, // TODO
-        }),
-        width2: WallLine::FoundAsParallelLine(HoughLine {
-            line: PolarLine {
-                distance: moy_parallel_width2.0 / moy_parallel_width2.2,
-                angle: moy_parallel_width2.1 / moy_parallel_width2.2,
-            },
-            weight: 500,
-        }),
-        length1: WallLine::FoundAsParallelLine(HoughLine {
-            line: PolarLine {
-                distance: moy_parallel_length1.0 / moy_parallel_length1.2,
-                angle: moy_parallel_length1.1 / moy_parallel_length1.2,
-            },
-            weight: 500,
-        }),
-        length2: WallLine::FoundAsParallelLine(HoughLine {
-            line: PolarLine {
-                distance: moy_parallel_length2.0 / moy_parallel_length2.2,
-                angle: moy_parallel_length2.1 / moy_parallel_length2.2,
-            },
-            weight: 500,
-        }),
+        width1: moy(&walls.width1, candidate_line_width),
+        width2: moy(&walls.width2, candidate_line_width),
+        length1: moy(&walls.length1, candidate_line_length),
+        length2: moy(&walls.length2, candidate_line_length),
     }
 }
 
@@ -637,13 +807,15 @@ enum _LineSize {
     Length,
 }
 
-fn fallback_on_3_walls(
-    accumulator: &[[u16; ANGLE_TAILLE]; DISTANCE_TAILLE],
-    candidate_line_width: Vec<(HoughLine, HoughLine)>,
-    candidate_line_length: Vec<(HoughLine, HoughLine)>,
+pub fn fallback_on_3_walls(
+    peaks: &[HoughLine],
+    points: &[LidarPoint],
+    candidate_line_width: Vec<ParallelCandidate>,
+    candidate_line_length: Vec<ParallelCandidate>,
+    config: &HoughConfig,
 ) -> Option<FieldWalls> {
-    // search if a perpendicular line exists for all the parallel lines detected
-    let mut all_detected_lines: Vec<(_LineSize, (HoughLine, HoughLine))> = candidate_line_width
+    // on cherche une droite perpendiculaire à la meilleure des paires détectées
+    let (line_size, pair) = candidate_line_width
         .iter()
         .map(|e| (_LineSize::Width, *e))
         .chain(
@@ -651,170 +823,391 @@ fn fallback_on_3_walls(
                 .iter()
                 .map(|e| (_LineSize::Length, *e)),
         )
-        .collect();
-    all_detected_lines.sort_by_key(|(_, (l1, l2))| l1.weight + l2.weight);
+        .max_by(|(_, a), (_, b)| a.score().total_cmp(&b.score()))?;
 
-    if all_detected_lines.is_empty() {
-        return None;
+    // les murs perpendiculaires à une paire de murs de largeur sont des murs de longueur
+    let perpendicular_extent = match line_size {
+        _LineSize::Width => FIELD_LENGTH,
+        _LineSize::Length => FIELD_WIDTH,
+    };
+    let perpendicular = search_perpendicular_lines_of(peaks, pair.first.line, config)
+        .into_iter()
+        .find(|line| {
+            LineSupport::measure(points, line.line)
+                .factor(line.line, perpendicular_extent)
+                .is_some()
+        })?;
+    let prop = match line_size {
+        _LineSize::Width => FieldWalls {
+            width1: WallLine::FoundAsParallelLine(pair.first),
+            width2: WallLine::FoundAsParallelLine(pair.second),
+            length1: WallLine::FoundAsPerpendicular(perpendicular),
+            length2: WallLine::GuessedLine(guess_last_wall(perpendicular.line, FIELD_WIDTH)),
+        },
+        _LineSize::Length => FieldWalls {
+            length1: WallLine::FoundAsParallelLine(pair.first),
+            length2: WallLine::FoundAsParallelLine(pair.second),
+            width1: WallLine::FoundAsPerpendicular(perpendicular),
+            width2: WallLine::GuessedLine(guess_last_wall(perpendicular.line, FIELD_LENGTH)),
+        },
+    };
+
+    Some(metrics::time("perform_moy", || {
+        perform_moy(prop, &candidate_line_width, &candidate_line_length)
+    }))
+}
+
+/// Le mur manquant est parallèle au mur connu, de l'autre côté du robot
+fn guess_last_wall(parallel_known_wall: PolarLine, distance_between_lines: Meters) -> PolarLine {
+    Line::from(parallel_known_wall)
+        .parallel_at(-distance_between_lines)
+        .into()
+}
+
+/// Les quatre murs du terrain. Les murs de largeur sont séparés de la longueur du terrain
+/// et les murs de longueur de la largeur du terrain.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldWalls {
+    pub width1: WallLine,
+    pub width2: WallLine,
+    pub length1: WallLine,
+    pub length2: WallLine,
+}
+
+/// écart maximal *en radians* à l'angle droit pour chacun des coins du terrain
+const CORNER_RIGHT_ANGLE_TOLERANCE: f64 = 0.2;
+
+/// Représente l'ensemble des raisons pour lesquelles les murs trouvés ne forment pas un terrain valide
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum FieldError {
+    /// Deux murs qui devraient se croiser sont parallèles,
+    /// ils ont probablement été mal étiquetés (largeur / longueur)
+    ParallelWalls,
+    /// Un coin du terrain est trop éloigné de l'angle droit (écart à l'angle droit donné)
+    NotRightAngle(
+        #[cfg_attr(feature = "serde", serde(with = "robot_common::units::serde_rad"))] Rad,
+    ),
+    /// Un côté du rectangle formé par les coins n'a pas la longueur attendue par le modèle du terrain
+    WrongSideLength { expected: Meters, found: Meters },
+    /// Aucun ensemble de murs compatible avec le terrain n'a été trouvé
+    WallsNotFound,
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
     }
-    let perpendiculars =
-        search_perpendicular_lines_of(&accumulator, all_detected_lines.first().unwrap().1.0.line);
-    if !perpendiculars.is_empty() {
-        let perpendicular = perpendiculars.first().unwrap();
-        let prop = match all_detected_lines.first().unwrap() {
-            (_LineSize::Width, pair) => FieldWalls {
-                length1: WallLine::FoundAsParallelLine(pair.0),
-                length2: WallLine::FoundAsParallelLine(pair.1),
-                width1: WallLine::FoundAsPerpendicular(*perpendicular),
-                width2: WallLine::GuessedLine(guess_last_wall(perpendicular.line, FIELD_WIDTH)),
-            },
-            (_LineSize::Length, pair) => FieldWalls {
-                width1: WallLine::FoundAsParallelLine(pair.0),
-                width2: WallLine::FoundAsParallelLine(pair.1),
-                length1: WallLine::FoundAsPerpendicular(*perpendicular),
-                length2: WallLine::GuessedLine(guess_last_wall(perpendicular.line, FIELD_LENGTH)),
-            },
+}
+
+impl Error for FieldError {}
+
+/// Les quatre coins du terrain en coordonnées carthésiennes dans le repère du lidar.
+/// Chaque coin est nommé d'après les deux murs qui le forment.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldCorners {
+    pub width1_length1: Point,
+    pub width1_length2: Point,
+    pub width2_length1: Point,
+    pub width2_length2: Point,
+}
+
+impl FieldCorners {
+    #[inline]
+    pub fn as_array(&self) -> [Point; 4] {
+        [
+            self.width1_length1,
+            self.width1_length2,
+            self.width2_length1,
+            self.width2_length2,
+        ]
+    }
+
+    /// Le centre du terrain est la moyenne des quatre coins
+    pub fn center(&self) -> Point {
+        let sum = self
+            .as_array()
+            .iter()
+            .fold(Vector::ZERO, |sum, corner| sum + corner.to_vector());
+        Point::ORIGIN + sum / 4.0
+    }
+}
+
+impl FieldWalls {
+    #[inline]
+    pub fn as_array(&self) -> [WallLine; 4] {
+        [self.width1, self.width2, self.length1, self.length2]
+    }
+
+    /// Le mur le plus proche du robot (l'origine du repère du lidar)
+    pub fn nearest(&self) -> WallLine {
+        self.as_array()
+            .into_iter()
+            .min_by(|a, b| a.line().distance.abs().0.total_cmp(&b.line().distance.abs().0))
+            .unwrap()
+    }
+
+    /// Calcule les quatre coins du terrain et vérifie qu'ils forment bien le rectangle attendu :
+    /// angles droits à la tolérance près et côtés de la longueur du terrain à 10% près.
+    pub fn corners(&self) -> Result<FieldCorners, FieldError> {
+        let corner = |width: &WallLine, length: &WallLine| {
+            let (width, length) = (width.line(), length.line());
+            let point = width
+                .intersect(&length)
+                .ok_or(FieldError::ParallelWalls)?;
+            let gap = (width.smallest_angle_between(&length) - Rad::QUARTER_TURN).mag();
+            if gap > Rad::new(CORNER_RIGHT_ANGLE_TOLERANCE) {
+                return Err(FieldError::NotRightAngle(gap));
+            }
+            Ok(point)
         };
+        let corners = FieldCorners {
+            width1_length1: corner(&self.width1, &self.length1)?,
+            width1_length2: corner(&self.width1, &self.length2)?,
+            width2_length1: corner(&self.width2, &self.length1)?,
+            width2_length2: corner(&self.width2, &self.length2)?,
+        };
+
+        // Les murs de largeur sont séparés de la longueur du terrain,
+        // un côté le long d'un mur de largeur mesure donc la largeur du terrain
+        let sides = [
+            (corners.width1_length1, corners.width1_length2, FIELD_WIDTH),
+            (corners.width2_length1, corners.width2_length2, FIELD_WIDTH),
+            (corners.width1_length1, corners.width2_length1, FIELD_LENGTH),
+            (corners.width1_length2, corners.width2_length2, FIELD_LENGTH),
+        ];
+        for (point1, point2, expected) in sides {
+            let found = point1.distance_to(point2);
+            if !found.in_the_aera_of(expected) {
+                return Err(FieldError::WrongSideLength { expected, found });
+            }
+        }
+        Ok(corners)
+    }
+}
 
-        let time1 = std::time::Instant::now();
-        let moy = perform_moy(prop, &candidate_line_width, &candidate_line_length);
-        debug!("perf moy in 3 walls, took {:?}", time1.elapsed());
+/// Position et orientation du robot sur le terrain.
+///
+/// Le repère du terrain a pour origine le centre du terrain, l'axe x suit les murs de longueur
+/// et l'axe y suit les murs de largeur. L'orientation est l'angle de l'axe 0° du lidar dans ce repère.
+///
+/// Le terrain étant symétrique, la pose n'est connue qu'à un demi-tour près :
+/// on renvoit toujours celle dont l'orientation est dans [-90°, 90°[.
+#[derive(Debug, Clone, Copy)]
+pub struct RobotPose {
+    pub x: Meters,
+    pub y: Meters,
+    pub orientation: Rad,
+}
 
-        Some(moy)
-    } else {
-        None
+impl RobotPose {
+    pub fn from_walls(walls: &FieldWalls) -> Result<Self, FieldError> {
+        let center = walls.corners()?.center();
+
+        // Direction de l'axe x du terrain dans le repère du lidar, connue à 180° près.
+        // On fait la moyenne des normales des murs de largeur et des murs de longueur (tournées de 90°)
+        // en doublant les angles pour que deux directions opposées comptent pour la même.
+        let (sin_sum, cos_sum) = [
+            walls.width1.line().angle,
+            walls.width2.line().angle,
+            walls.length1.line().angle + Rad::QUARTER_TURN,
+            walls.length2.line().angle + Rad::QUARTER_TURN,
+        ]
+        .iter()
+        .fold((0.0, 0.0), |(sin_sum, cos_sum), angle| {
+            let double = *angle * 2.0;
+            (sin_sum + double.sin(), cos_sum + double.cos())
+        });
+        let field_axis = Rad::atan2(sin_sum, cos_sum) / 2.0;
+
+        // Passage du repère du lidar à celui du terrain : on ramène le centre à l'origine
+        // puis on aligne l'axe du terrain sur l'axe x. Le robot est à l'origine du repère du lidar.
+        let lidar_to_field = Transform::new(-center.to_vector(), Rad::ZERO)
+            .then(&Transform::new(Vector::ZERO, -field_axis));
+        let position = lidar_to_field.apply(Point::ORIGIN);
+        Ok(Self {
+            x: position.x,
+            y: position.y,
+            orientation: lidar_to_field.rotation,
+        })
+    }
+
+    /// Déplacement qui passe du repère du lidar au repère du terrain
+    #[inline]
+    pub fn transform(&self) -> Transform {
+        Transform::new(Vector::new(self.x, self.y), self.orientation)
+    }
+
+    /// Pose dans le repère `Global` des stratégies, où l'axe y suit les murs de longueur
+    /// (vers un des goals) : c'est le repère du terrain tourné d'un quart de tour.
+    /// L'orientation ne change pas, l'angle 0° du lidar étant la direction du kicker.
+    #[inline]
+    pub fn to_pose2(&self) -> Pose2<Global> {
+        Pose2::new(GlobalPoint::new(-self.y, self.x), self.orientation)
     }
 }
 
-fn guess_last_wall(parallel_known_wall: PolarLine, distance_between_lines: Meters) -> PolarLine {
-    let patched_line = conv_convention_big_angle_to_negative_distance(parallel_known_wall);
-    PolarLine {
-        distance: if patched_line.distance >= Meters(0.0) {
-            patched_line.distance - distance_between_lines
-        } else {
-            patched_line.distance + distance_between_lines
-        },
-        angle: patched_line.angle,
+/// Incertitude de la pose : variances (m², rad²) dans le repère du terrain.
+///
+/// Estimation simple à partir de l'écart des points aux murs trouvés : chaque axe ne dépend que
+/// de la paire de murs qui lui est perpendiculaire, et les corrélations sont ignorées.
+/// Un mur deviné n'apporte aucune information, il reprend celle du mur opposé.
+#[derive(Debug, Clone, Copy)]
+pub struct PoseCovariance {
+    pub x: f64,
+    pub y: f64,
+    pub orientation: f64,
+}
+
+/// Écart des points proches d'une droite à cette droite
+#[derive(Debug, Clone, Copy)]
+struct WallFit {
+    /// variance de la position de la droite le long de sa normale (m²)
+    distance_variance: f64,
+    /// variance de la direction de la droite (rad²)
+    angle_variance: f64,
+}
+
+impl WallFit {
+    /// `None` si trop peu de points sont sur la droite pour estimer quoi que ce soit
+    fn measure(points: &[LidarPoint], line: PolarLine) -> Option<Self> {
+        let line = Line::from(line);
+        let direction = Vector::from_polar(Meters(1.0), line.direction());
+        let (residuals, abscissas): (Vec<f64>, Vec<f64>) = points
+            .iter()
+            .filter(|point| point.point.distance <= LIDAR_DISTANCE_MAX)
+            .map(|point| Point::from(&point.point))
+            .filter(|point| line.distance_to(*point) <= SUPPORT_DISTANCE_TOLERANCE)
+            .map(|point| (line.signed_distance_to(point).0, point.to_vector().dot(direction)))
+            .unzip();
+        let n = residuals.len() as f64;
+        if residuals.len() < SUPPORT_MIN_POINTS_PER_SEGMENT {
+            return None;
+        }
+        let mean = residuals.iter().sum::<f64>() / n;
+        let variance = residuals.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        let mean_abscissa = abscissas.iter().sum::<f64>() / n;
+        let abscissa_spread: f64 = abscissas.iter().map(|t| (t - mean_abscissa).powi(2)).sum();
+        Some(Self {
+            // le décalage moyen vient de la discrétisation de Hough et ne diminue pas avec le nombre de points
+            distance_variance: mean.powi(2) + variance / n,
+            // variance de la pente d'une régression linéaire
+            angle_variance: variance / abscissa_spread.max(f64::EPSILON),
+        })
     }
 }
 
-#[derive(Debug)]
-struct FieldWalls {
-    width1: WallLine,
-    width2: WallLine,
-    length1: WallLine,
-    length2: WallLine,
+impl PoseCovariance {
+    pub fn estimate(points: &[LidarPoint], walls: &FieldWalls) -> Self {
+        let fit = |wall: &WallLine| match wall {
+            WallLine::GuessedLine(_) => None,
+            _ => WallFit::measure(points, wall.line()),
+        };
+        let fits = walls.as_array().map(|wall| fit(&wall));
+        // la coordonnée est la moitié de la différence des distances aux deux murs
+        let axis_variance = |first: Option<WallFit>, second: Option<WallFit>| match (first, second) {
+            (Some(first), Some(second)) => (first.distance_variance + second.distance_variance) / 4.0,
+            (Some(fit), None) | (None, Some(fit)) => fit.distance_variance,
+            (None, None) => f64::INFINITY,
+        };
+        let information: f64 = fits.iter().flatten().map(|fit| 1.0 / fit.angle_variance).sum();
+        Self {
+            // les murs de largeur sont perpendiculaires à l'axe x du terrain
+            x: axis_variance(fits[0], fits[1]),
+            y: axis_variance(fits[2], fits[3]),
+            orientation: 1.0 / information,
+        }
+    }
 }
 
-#[inline]
-fn _moyenne_point_carthesian(point1: (f64, f64), point2: (f64, f64)) -> (f64, f64) {
-    ((point1.0 + point2.0) / 2.0, (point1.1 + point2.1) / 2.0)
+/// Étapes intermédiaires de la recherche des murs, gardées pour les afficher
+#[derive(Debug)]
+pub struct WallsAnalysis {
+    /// seuils utilisés pour cette analyse
+    pub config: HoughConfig,
+    pub accumulator: Box<HoughAccumulator>,
+    /// pics de l'accumulateur triés par poids décroissant, calculés une seule fois par tour
+    pub peaks: Vec<HoughLine>,
+    pub width_candidates: Vec<ParallelCandidate>,
+    pub length_candidates: Vec<ParallelCandidate>,
+    pub walls: Option<FieldWalls>,
 }
 
-fn calculate_center_of_field_in_carthesian(
-    rec: rerun::RecordingStream,
-    walls: &FieldWalls,
-) -> (f64, f64) {
-    let inter1 = walls
-        .width1
-        .line()
-        .intersect(&walls.length1.line())
-        .unwrap();
-    let inter2 = walls
-        .width1
-        .line()
-        .intersect(&walls.length2.line())
-        .unwrap()
-        .to_carthesian_point();
-    let inter3 = walls
-        .width2
-        .line()
-        .intersect(&walls.length1.line())
-        .unwrap()
-        .to_carthesian_point();
-    let inter4 = walls
-        .width2
-        .line()
-        .intersect(&walls.length2.line())
-        .unwrap()
-        .to_carthesian_point();
-
-    rec.log(
-        "lidar/inter1",
-        &rerun::Points2D::new([(
-            inter1.to_carthesian_point().0 as f32,
-            inter1.to_carthesian_point().1 as f32,
-        )])
-        .with_colors([colors::ORANGE])
-        .with_radii([0.05])
-        .with_labels(["inter1"])
-        .with_show_labels(rerun::components::ShowLabels(rerun::datatypes::Bool(false))),
-    )
-    .unwrap();
-
-    rec.log(
-        "lidar/inter2",
-        &rerun::Points2D::new([(inter2.0 as f32, inter2.1 as f32)])
-            .with_colors([colors::ORANGE])
-            .with_radii([0.05])
-            .with_labels(["inter2"])
-            .with_show_labels(rerun::components::ShowLabels(rerun::datatypes::Bool(false))),
-    )
-    .unwrap();
-
-    rec.log(
-        "lidar/inter3",
-        &rerun::Points2D::new([(inter3.0 as f32, inter3.1 as f32)])
-            .with_colors([colors::ORANGE])
-            .with_radii([0.05])
-            .with_labels(["inter3"])
-            .with_show_labels(rerun::components::ShowLabels(rerun::datatypes::Bool(false))),
-    )
-    .unwrap();
-
-    rec.log(
-        "lidar/inter4",
-        &rerun::Points2D::new([(inter4.0 as f32, inter4.1 as f32)])
-            .with_colors([colors::ORANGE])
-            .with_radii([0.05])
-            .with_labels(["inter4"])
-            .with_show_labels(rerun::components::ShowLabels(rerun::datatypes::Bool(false))),
-    )
-    .unwrap();
+impl WallsAnalysis {
+    /// Analyse avec les seuils utilisés sur le robot (`HoughConfig::default()`)
+    #[inline]
+    pub fn run(points: &[LidarPoint]) -> Self {
+        Self::run_with(points, &HoughConfig::DEFAULT)
+    }
 
-    let m1 = _moyenne_point_carthesian(inter1.to_carthesian_point(), inter2);
-    let m2 = _moyenne_point_carthesian(inter1.to_carthesian_point(), inter3);
-    let m3 = _moyenne_point_carthesian(inter3, inter4);
-    let m4 = _moyenne_point_carthesian(inter2, inter4);
+    /// Chaque étape est mesurée dans `metrics` sous le nom de sa fonction
+    #[instrument(skip_all, fields(points = points.len()))]
+    pub fn run_with(points: &[LidarPoint], config: &HoughConfig) -> Self {
+        let start = std::time::Instant::now();
+        let mut accumulator = empty_accumulator();
+        metrics::time("build_hough_accumulator", || {
+            build_hough_accumulator(&mut accumulator, points)
+        });
+        let peaks = metrics::time("hough_peaks", || hough_peaks(&accumulator, config));
+        // les murs de largeur sont séparés de la longueur du terrain et mesurent sa largeur
+        let (width_candidates, length_candidates) = metrics::time("search_all_parallel_lines", || {
+            (
+                search_all_parallel_lines(&peaks, FIELD_LENGTH, config),
+                search_all_parallel_lines(&peaks, FIELD_WIDTH, config),
+            )
+        });
+        let (width_candidates, length_candidates) = metrics::time("check_support", || {
+            (
+                check_support(points, width_candidates, FIELD_WIDTH),
+                check_support(points, length_candidates, FIELD_LENGTH),
+            )
+        });
+        let walls = metrics::time("locate_field_with_4_walls", || {
+            locate_field_with_4_walls(&width_candidates, &length_candidates)
+        })
+        .or_else(|| {
+            debug!("Détection 4 murs échouée, tente avec 3 murs");
+            metrics::time("fallback_on_3_walls", || {
+                fallback_on_3_walls(
+                    &peaks,
+                    points,
+                    width_candidates.clone(),
+                    length_candidates.clone(),
+                    config,
+                )
+            })
+        });
+        metrics::record("locate_field_walls", start.elapsed());
+        Self {
+            config: *config,
+            accumulator,
+            peaks,
+            width_candidates,
+            length_candidates,
+            walls,
+        }
+    }
+}
+
+/// Trouve les murs du terrain dans un tour complet de lidar
+#[inline]
+pub fn locate_field_walls(points: &[LidarPoint]) -> Result<FieldWalls, FieldError> {
+    WallsAnalysis::run(points).walls.ok_or(FieldError::WallsNotFound)
+}
 
-    ((m1.0 + m3.0) / 2.0, (m2.1 + m4.1) / 2.0)
+/// Détermine la position du robot sur le terrain à partir d'un tour complet de lidar
+pub fn locate_robot(points: &[LidarPoint]) -> Result<RobotPose, FieldError> {
+    RobotPose::from_walls(&locate_field_walls(points)?)
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
-    use crate::complex_viewport::{log_lidar_lines, log_lidar_points};
-    use crate::{analyze_tests_data::lidar_test_data::*, parse::PolarPoint};
-    use std::time::{Duration, Instant};
-
-    fn load_log(from: &str) -> Box<Vec<LidarPoint>> {
-        let mut vector = Box::new(Vec::with_capacity(from.len() / 10));
-        for point in from.split(';') {
-            let mut iter_num = point.trim_matches(|c| c == '(' || c == ')').split(',');
-            let angle = iter_num.next().unwrap().parse::<u16>().unwrap();
-            let distance = iter_num.next().unwrap().parse::<u16>().unwrap();
-            assert!(iter_num.next().is_none());
-            vector.push(LidarPoint {
-                point: PolarPoint {
-                    distance: Meters::mm(f64::from(distance)),
-                    angle: Deg::new(f64::from(angle) / 100.0).rad(),
-                },
-                intensity: Intensity::NULL,
-            });
-        }
-        vector
-    }
+    use crate::dataset::{default_captures_dir, load_captures};
+    use crate::geometry::Segment;
+    use crate::simulation::{synthetic_scan, synthetic_walls};
+    use robot_common::frames::LocalPoint;
 
     fn approx_equal_rad(a: Rad, b: Rad, epsilon: Rad) -> Result<(), String> {
         let mut diff = a - b;
@@ -865,9 +1258,10 @@ mod tests {
             distance: Meters(1.0),
             angle: Rad::new(std::f64::consts::PI),
         };
+        // droites à 0 et π : parallèles
         approx_equal_rad(
             line1.smallest_angle_between(&line2),
-            Rad::new(std::f64::consts::PI),
+            Rad::new(0.0),
             Rad::new(APPROX_LIMIT),
         )
         .unwrap();
@@ -1034,7 +1428,7 @@ mod tests {
             angle: Rad::new(std::f64::consts::FRAC_PI_4),
         }; // Ligne à 45 degrés
 
-        let intersection = line1.intersect(&line2);
+        let intersection = line1.intersect(&line2).map(|point| point.to_polar_point());
         assert!(intersection.is_some());
 
         let point = intersection.unwrap();
@@ -1054,7 +1448,7 @@ mod tests {
             angle: Rad::new(5.0),
         }; // Ligne horizontale parallèle
 
-        let intersection = line1.intersect(&line2);
+        let intersection = line1.intersect(&line2).map(|point| point.to_polar_point());
         dbg!(&intersection);
         assert!(intersection.is_none()); // Pas d'intersection
     }
@@ -1070,7 +1464,7 @@ mod tests {
             angle: Rad::new(0.0),
         }; // Même ligne
 
-        let intersection = line1.intersect(&line2);
+        let intersection = line1.intersect(&line2).map(|point| point.to_polar_point());
         assert!(intersection.is_none()); // Pas d'intersection unique
     }
 
@@ -1085,7 +1479,7 @@ mod tests {
             angle: Rad::new(std::f64::consts::FRAC_PI_2),
         }; // Ligne verticale
 
-        let intersection = line1.intersect(&line2);
+        let intersection = line1.intersect(&line2).map(|point| point.to_polar_point());
         assert!(intersection.is_some());
 
         let point = intersection.unwrap();
@@ -1104,7 +1498,7 @@ mod tests {
             angle: Rad::new(std::f64::consts::PI + 0.1),
         }; // Ligne verticale
 
-        let intersection = line1.intersect(&line2);
+        let intersection = line1.intersect(&line2).map(|point| point.to_polar_point());
         assert!(intersection.is_some());
 
         let point = intersection.unwrap();
@@ -1123,7 +1517,7 @@ mod tests {
             angle: Rad::HALF_TURN,
         }; // Ligne verticale
 
-        let intersection = line1.intersect(&line2);
+        let intersection = line1.intersect(&line2).map(|point| point.to_polar_point());
         assert!(intersection.is_some());
 
         let point = intersection.unwrap();
@@ -1142,7 +1536,7 @@ mod tests {
             angle: Rad::HALF_TURN,
         }; // Ligne verticale
 
-        let intersection = line1.intersect(&line2);
+        let intersection = line1.intersect(&line2).map(|point| point.to_polar_point());
         assert!(intersection.is_some());
 
         let point = intersection.unwrap();
@@ -1151,35 +1545,529 @@ mod tests {
     }
 
     #[test]
-    fn bench_hough_accumulator() {
-        let nb_essais: u32 = 500;
-        let mut moyenne = Duration::ZERO;
-        for test in TESTS_DETECTION {
-            let data = load_log(test);
-            for _ in 0..nb_essais {
-                let before = Instant::now();
-                let mut accumulator: [[u16; ANGLE_TAILLE]; DISTANCE_TAILLE] = [[0; ANGLE_TAILLE]; DISTANCE_TAILLE];
-                let _ = build_hough_accumulator(&mut accumulator, &data);
-                moyenne += before.elapsed();
-            }
+    fn test_intersection_through_origin() {
+        let line1 = PolarLine {
+            distance: Meters(0.0),
+            angle: Rad::new(0.3),
+        }; // Passe par l'origine
+        let line2 = PolarLine {
+            distance: Meters(2.0),
+            angle: Rad::QUARTER_TURN,
+        }; // y = 2
+
+        let point = line1.intersect(&line2).unwrap();
+        approx_equal_meters(point.y, Meters(2.0), Meters(APPROX_LIMIT)).unwrap();
+        approx_equal_meters(
+            point.x,
+            Meters(-2.0 * 0.3_f64.tan()),
+            Meters(APPROX_LIMIT),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_intersection_both_conventions() {
+        let line = PolarLine {
+            distance: Meters(1.0),
+            angle: Rad::new(0.2),
+        };
+        let big_angle = PolarLine {
+            distance: Meters(0.5),
+            angle: Rad::new(0.2) + Rad::HALF_TURN + Rad::QUARTER_TURN,
+        };
+        let negative_distance = PolarLine {
+            distance: -big_angle.distance,
+            angle: big_angle.angle - Rad::HALF_TURN,
+        };
+
+        let point1 = line.intersect(&big_angle).unwrap();
+        let point2 = line.intersect(&negative_distance).unwrap();
+        approx_equal_meters(point1.x, point2.x, Meters(APPROX_LIMIT)).unwrap();
+        approx_equal_meters(point1.y, point2.y, Meters(APPROX_LIMIT)).unwrap();
+    }
+
+    #[test]
+    fn test_intersection_carthesian_matches_polar() {
+        let line1 = PolarLine {
+            distance: Meters(10.0),
+            angle: Rad::new(0.5),
+        };
+        let line2 = PolarLine {
+            distance: Meters(8.0),
+            angle: Rad::new(std::f64::consts::PI + 0.1),
+        };
+
+        let point = line1.intersect(&line2).unwrap();
+        let polar = point.to_polar_point();
+        approx_equal_meters(polar.distance, Meters(45.31), Meters(GEOGEBRA_LIMIT)).unwrap();
+        let back = Point::from(&polar);
+        approx_equal_meters(point.x, back.x, Meters(APPROX_LIMIT)).unwrap();
+        approx_equal_meters(point.y, back.y, Meters(APPROX_LIMIT)).unwrap();
+    }
+
+    /// Murs d'un terrain aligné avec le repère du lidar, le centre du terrain étant en `center`.
+    /// Les murs de largeur sont verticaux (x constant), les murs de longueur horizontaux.
+    fn aligned_field_walls(center: (f64, f64)) -> FieldWalls {
+        let wall = |distance: f64, angle: Rad| {
+            WallLine::GuessedLine(PolarLine {
+                distance: Meters(distance),
+                angle,
+            })
+        };
+        FieldWalls {
+            width1: wall(center.0 + FIELD_LENGTH.0 / 2.0, Rad::ZERO),
+            width2: wall(FIELD_LENGTH.0 / 2.0 - center.0, Rad::HALF_TURN),
+            length1: wall(center.1 + FIELD_WIDTH.0 / 2.0, Rad::QUARTER_TURN),
+            length2: wall(
+                FIELD_WIDTH.0 / 2.0 - center.1,
+                Rad::HALF_TURN + Rad::QUARTER_TURN,
+            ),
         }
-        let nb_tests = nb_essais * (u32::try_from(TESTS_DETECTION.len()).unwrap());
-        panic!(
-            "perf Bench Hough Accumulator (on {} values): {:#?}",
-            nb_tests,
-            moyenne / nb_tests
+    }
+
+    #[test]
+    fn test_corners_aligned_field() {
+        let walls = aligned_field_walls((0.3, -0.2));
+        let corners = walls.corners().unwrap();
+        let corner = corners.width1_length1;
+        approx_equal_meters(
+            corner.x,
+            Meters(0.3) + FIELD_LENGTH / 2.0,
+            Meters(APPROX_LIMIT),
         )
+        .unwrap();
+        approx_equal_meters(
+            corner.y,
+            Meters(-0.2) + FIELD_WIDTH / 2.0,
+            Meters(APPROX_LIMIT),
+        )
+        .unwrap();
+        let center = corners.center();
+        approx_equal_meters(center.x, Meters(0.3), Meters(APPROX_LIMIT)).unwrap();
+        approx_equal_meters(center.y, Meters(-0.2), Meters(APPROX_LIMIT)).unwrap();
+    }
+
+    #[test]
+    fn test_corners_parallel_walls() {
+        let mut walls = aligned_field_walls((0.0, 0.0));
+        // Mur mal étiqueté : un mur de largeur à la place d'un mur de longueur
+        walls.length1 = walls.width2;
+        assert!(matches!(walls.corners(), Err(FieldError::ParallelWalls)));
+    }
+
+    #[test]
+    fn test_corners_not_right_angle() {
+        let mut walls = aligned_field_walls((0.0, 0.0));
+        walls.length1 = WallLine::GuessedLine(PolarLine {
+            distance: FIELD_WIDTH / 2.0,
+            angle: Rad::QUARTER_TURN + Rad::new(0.4),
+        });
+        assert!(matches!(walls.corners(), Err(FieldError::NotRightAngle(_))));
+    }
+
+    #[test]
+    fn test_corners_wrong_side_length() {
+        let mut walls = aligned_field_walls((0.0, 0.0));
+        walls.width2 = WallLine::GuessedLine(PolarLine {
+            distance: Meters(0.2),
+            angle: Rad::HALF_TURN,
+        });
+        assert!(matches!(
+            walls.corners(),
+            Err(FieldError::WrongSideLength { .. })
+        ));
+    }
+
+    fn is_close_to(found: PolarLine, expected: &Line) -> bool {
+        let found = Line::from(found);
+        found.is_parallel_with(expected, Deg::new(2.0).rad())
+            && expected.distance_to(found.foot()) < Meters::cm(3.0)
+    }
+
+    #[test]
+    fn test_hough_peaks_one_per_wall() {
+        let robot = Point::new(Meters(0.3), Meters(-0.2));
+        let mut accumulator = empty_accumulator();
+        build_hough_accumulator(&mut accumulator, &synthetic_scan(robot, &[]));
+        let peaks = hough_peaks(&accumulator, &HoughConfig::default());
+        for wall in synthetic_walls(robot).iter() {
+            let found = peaks.iter().filter(|peak| is_close_to(peak.line, wall)).count();
+            assert_eq!(found, 1, "{:?} trouvé {} fois dans {:#?}", wall, found, peaks);
+        }
+        assert!(peaks.windows(2).all(|pair| pair[0].weight >= pair[1].weight));
+    }
+
+    #[test]
+    fn test_parallel_candidates_scored() {
+        let robot = Point::new(Meters(0.3), Meters(-0.2));
+        let [width1, width2, length1, length2] = synthetic_walls(robot);
+        let mut accumulator = empty_accumulator();
+        build_hough_accumulator(&mut accumulator, &synthetic_scan(robot, &[]));
+        let peaks = hough_peaks(&accumulator, &HoughConfig::default());
+
+        let width = search_all_parallel_lines(&peaks, FIELD_LENGTH, &HoughConfig::default());
+        let best = width.first().unwrap();
+        assert!(best.consistency > 0.8, "{:?}", best);
+        assert!(best.lines().iter().any(|line| is_close_to(line.line, &width1)));
+        assert!(best.lines().iter().any(|line| is_close_to(line.line, &width2)));
+        assert!(width.windows(2).all(|pair| pair[0].score() >= pair[1].score()));
+
+        let length = search_all_parallel_lines(&peaks, FIELD_WIDTH, &HoughConfig::default());
+        let best = length.first().unwrap();
+        assert!(best.lines().iter().any(|line| is_close_to(line.line, &length1)));
+        assert!(best.lines().iter().any(|line| is_close_to(line.line, &length2)));
+
+        let perpendiculars = search_perpendicular_lines_of(&peaks, width1.into(), &HoughConfig::default());
+        assert!(perpendiculars.len() >= 2);
+        assert!(perpendiculars.iter().take(2).all(|line| {
+            is_close_to(line.line, &length1) || is_close_to(line.line, &length2)
+        }));
+    }
+
+    #[test]
+    fn test_line_support() {
+        let point = |x: f64, y: f64| LidarPoint {
+            point: Point::new(Meters(x), Meters(y)).to_polar_point(),
+            intensity: Intensity::NULL,
+        };
+        // droite y = 1 : un segment de 50 cm, un trou de 30 cm, un segment de 20 cm,
+        // un point isolé et un point hors de la droite
+        let mut points: Vec<LidarPoint> = (0..=50).map(|n| point(-0.5 + f64::from(n) * 0.01, 1.0)).collect();
+        points.extend((0..=20).map(|n| point(0.3 + f64::from(n) * 0.01, 1.0)));
+        points.push(point(1.2, 1.0));
+        points.push(point(0.0, 1.5));
+        let line = PolarLine {
+            distance: Meters(1.0),
+            angle: Rad::QUARTER_TURN,
+        };
+        let support = LineSupport::measure(&points, line);
+        assert_eq!(support.points, 72);
+        approx_equal_meters(support.length, Meters(0.5), Meters(1e-9)).unwrap();
+        approx_equal_meters(support.spread, Meters(1.0), Meters(1e-9)).unwrap();
+        assert!((support.coverage - 0.7).abs() < 1e-9);
+
+        assert!(support.factor(line, Meters(1.5)).is_some());
+        assert!(support.factor(line, Meters(3.0)).is_none());
+    }
+
+    #[test]
+    fn test_robot_in_front_of_wall_is_not_a_wall() {
+        let robot = Point::new(Meters(-0.6), Meters(-0.2));
+        // face d'un robot adverse de 22 cm, 8 cm devant un mur de largeur
+        let face_x = -FIELD_LENGTH.0 / 2.0 + 0.08;
+        let face = Segment::new(
+            Point::new(Meters(face_x), Meters(-0.31)),
+            Point::new(Meters(face_x), Meters(-0.09)),
+        );
+        let face_line = Line::new(robot.x - Meters(face_x), Rad::HALF_TURN);
+        let points = synthetic_scan(robot, &[face]);
+        let mut accumulator = empty_accumulator();
+        build_hough_accumulator(&mut accumulator, &points);
+        let peaks = hough_peaks(&accumulator, &HoughConfig::default());
+
+        let unchecked = search_all_parallel_lines(&peaks, FIELD_LENGTH, &HoughConfig::default());
+        assert!(unchecked
+            .iter()
+            .any(|pair| pair.lines().iter().any(|line| is_close_to(line.line, &face_line))));
+        let checked = check_support(&points, unchecked, FIELD_WIDTH);
+        assert!(!checked.is_empty());
+        assert!(checked
+            .iter()
+            .all(|pair| pair.lines().iter().all(|line| !is_close_to(line.line, &face_line))));
+
+        let pose = locate_robot(&points).unwrap();
+        let expected = RobotPose {
+            x: robot.x,
+            y: robot.y,
+            orientation: Rad::ZERO,
+        };
+        let (position_error, orientation_error) = pose_errors(&pose, &expected);
+        assert!(position_error < Meters::cm(2.0), "{:?}", pose);
+        assert!(orientation_error < Deg::new(1.0).rad(), "{:?}", pose);
+    }
+
+    #[test]
+    fn test_accumulator_cell() {
+        let cells = [
+            (0, 0),
+            (12, 90),
+            (DISTANCE_TAILLE / 2, 0),
+            (450, 179),
+            (DISTANCE_TAILLE - 1, 45),
+        ];
+        for (distance_case, angle_case) in cells {
+            let line = accumulator_cell_line(distance_case, angle_case);
+            assert_eq!(accumulator_cell(line), Some((distance_case, angle_case)), "{:?}", line);
+        }
+        let far = PolarLine {
+            distance: LIDAR_DISTANCE_MAX * 2.0,
+            angle: Rad::new(1.0),
+        };
+        assert_eq!(accumulator_cell(far), None);
+    }
+
+    #[test]
+    fn test_parallel_consistency() {
+        let config = HoughConfig::default();
+        let line = |distance: f64, angle: f64| PolarLine {
+            distance: Meters(distance),
+            angle: Rad::new(angle),
+        };
+        let exact = parallel_consistency(line(1.0, 0.0), line(0.82, f64::consts::PI), FIELD_WIDTH, &config);
+        assert!((exact.unwrap() - 1.0).abs() < APPROX_LIMIT);
+        let off = parallel_consistency(line(1.0, 0.0), line(0.87, f64::consts::PI), FIELD_WIDTH, &config);
+        assert!(off.unwrap() < 0.6);
+        // même côté du robot
+        assert!(parallel_consistency(line(1.0, 0.0), line(2.82, 0.0), FIELD_WIDTH, &config).is_none());
+        // trop loin du modèle
+        assert!(parallel_consistency(line(1.0, 0.0), line(1.5, f64::consts::PI), FIELD_WIDTH, &config).is_none());
+    }
+
+    #[test]
+    fn test_pose_transform_maps_center_to_origin() {
+        let center = Point::new(Meters(0.3), Meters(-0.2));
+        let pose = RobotPose::from_walls(&aligned_field_walls((center.x.0, center.y.0))).unwrap();
+        approx_equal_meters(pose.x, Meters(-0.3), Meters(APPROX_LIMIT)).unwrap();
+        approx_equal_meters(pose.y, Meters(0.2), Meters(APPROX_LIMIT)).unwrap();
+        let field_center = pose.transform().apply(center);
+        approx_equal_meters(field_center.x, Meters(0.0), Meters(APPROX_LIMIT)).unwrap();
+        approx_equal_meters(field_center.y, Meters(0.0), Meters(APPROX_LIMIT)).unwrap();
+    }
+
+    #[test]
+    fn test_pose2_matches_field_transform() {
+        let center = Point::new(Meters(0.3), Meters(-0.2));
+        let pose = RobotPose::from_walls(&aligned_field_walls((center.x.0, center.y.0))).unwrap();
+        let pose2 = pose.to_pose2();
+        approx_equal_meters(pose2.position.x, Meters(-0.2), Meters(APPROX_LIMIT)).unwrap();
+        approx_equal_meters(pose2.position.y, Meters(-0.3), Meters(APPROX_LIMIT)).unwrap();
+
+        for orientation in [0.0, 0.7, -1.4, 3.0] {
+            let pose = RobotPose {
+                x: Meters(0.4),
+                y: Meters(-0.1),
+                orientation: Rad::new(orientation),
+            };
+            let seen = PolarPoint {
+                distance: Meters(0.8),
+                angle: Rad::new(1.1),
+            };
+            let on_field = pose.transform().apply(Point::from(&seen));
+            let global = LocalPoint::from(&seen).to_global(pose.to_pose2());
+            approx_equal_meters(global.x, -on_field.y, Meters(APPROX_LIMIT)).unwrap();
+            approx_equal_meters(global.y, on_field.x, Meters(APPROX_LIMIT)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_pose_covariance() {
+        let robot = Point::new(Meters(0.3), Meters(-0.2));
+        let [width1, width2, length1, length2] = synthetic_walls(robot);
+        let found = |line: Line| WallLine::FoundAsParallelLine(HoughLine {
+            line: line.into(),
+            weight: 100,
+        });
+        // le lidar mesure au millimètre : bruit alterné de ±1 cm le long de chaque mur
+        let points: Vec<LidarPoint> = synthetic_scan(robot, &[])
+            .into_iter()
+            .enumerate()
+            .map(|(n_point, mut point)| {
+                let noise = if n_point % 2 == 0 { 0.01 } else { -0.01 };
+                point.point.distance = point.point.distance + Meters(noise);
+                point
+            })
+            .collect();
+        let walls = FieldWalls {
+            width1: found(width1),
+            width2: found(width2),
+            length1: found(length1),
+            length2: WallLine::GuessedLine(length2.into()),
+        };
+        let covariance = PoseCovariance::estimate(&points, &walls);
+        for variance in [covariance.x, covariance.y] {
+            assert!(variance > 0.0 && variance.sqrt() < 0.005, "{:?}", covariance);
+        }
+        assert!(covariance.orientation.sqrt() < Deg::new(1.0).rad().val(), "{:?}", covariance);
+        // y ne dépend que du seul mur de longueur trouvé, x de la moyenne des deux murs de largeur
+        assert!(covariance.y > covariance.x, "{:?}", covariance);
+
+        let guessed = FieldWalls {
+            width1: WallLine::GuessedLine(width1.into()),
+            width2: WallLine::GuessedLine(width2.into()),
+            ..walls
+        };
+        assert!(PoseCovariance::estimate(&points, &guessed).x.is_infinite());
+    }
+
+    /// erreur de position maximale tolérée par les tests de régression
+    const REGRESSION_POSITION_TOLERANCE: Meters = Meters::cm(6.0);
+    /// erreur d'orientation maximale *en degrés* tolérée par les tests de régression
+    const REGRESSION_ORIENTATION_TOLERANCE: f64 = 6.0;
+    /// tolérances pour les poses approximatives déduites du nom des captures :
+    /// elles ne vérifient que la zone du terrain et la direction générale du robot
+    const APPROX_POSITION_TOLERANCE: Meters = Meters::cm(35.0);
+    /// *en degrés*
+    const APPROX_ORIENTATION_TOLERANCE: f64 = 45.0;
+
+    /// Renvoit les erreurs de position et d'orientation entre deux poses.
+    /// Le terrain étant symétrique, on compare aussi avec la pose attendue tournée d'un demi-tour.
+    fn pose_errors(found: &RobotPose, expected: &RobotPose) -> (Meters, Rad) {
+        let symmetric = RobotPose {
+            x: -expected.x,
+            y: -expected.y,
+            orientation: expected.orientation + Rad::HALF_TURN,
+        };
+        [expected, &symmetric]
+            .iter()
+            .map(|candidate| {
+                let diff = (found.orientation - candidate.orientation)
+                    .val()
+                    .rem_euclid(Rad::FULL_TURN.val());
+                (
+                    Meters((found.x - candidate.x).0.hypot((found.y - candidate.y).0)),
+                    Rad::new(diff.min(Rad::FULL_TURN.val() - diff)),
+                )
+            })
+            .min_by(|(_, angle1), (_, angle2)| angle1.partial_cmp(angle2).unwrap())
+            .unwrap()
+    }
+
+    /// rotations *en degrés* appliquées aux points des captures pour vérifier la cohérence du localiseur
+    const CONSISTENCY_ROTATIONS: [f64; 3] = [15.0, 45.0, -70.0];
+
+    /// Tourne tous les points du lidar de `angle`, comme si le robot avait tourné de `-angle`
+    fn rotate_scan(points: &[LidarPoint], angle: Rad) -> Vec<LidarPoint> {
+        points
+            .iter()
+            .map(|point| LidarPoint {
+                point: PolarPoint {
+                    distance: point.point.distance,
+                    angle: Rad::new(
+                        (point.point.angle + angle)
+                            .val()
+                            .rem_euclid(Rad::FULL_TURN.val()),
+                    ),
+                },
+                intensity: point.intensity,
+            })
+            .collect()
+    }
+
+    /// Compare le localiseur à la pose mesurée sur le terrain de chaque capture, ou à défaut à sa
+    /// pose approximative (zone et direction déduites du nom), et vérifie qu'il retrouve la même
+    /// pose quand le scan est tourné. Une capture sans aucune pose attendue fait échouer le test.
+    #[test]
+    fn test_regression_poses() {
+        let mut failures = Vec::new();
+        println!(
+            "{:<30} | {:>18} | {:>17} | {:>8} | {:>8} | {:>16}",
+            "capture", "attendu (cm, °)", "trouvé (cm, °)", "err cm", "err °", "rotation (cm, °)"
+        );
+        for capture in load_captures(&default_captures_dir()).unwrap() {
+            if capture
+                .field_size
+                .is_some_and(|size| size != (FIELD_LENGTH, FIELD_WIDTH))
+            {
+                println!("{:<30} | autre terrain, ignorée", capture.name);
+                continue;
+            }
+            let found = match locate_robot(&capture.points) {
+                Ok(found) => found,
+                Err(err) => {
+                    println!("{:<30} | échec : {}", capture.name, err);
+                    failures.push(capture.name);
+                    continue;
+                }
+            };
+
+            // même pose, à la rotation près, quand tous les points sont tournés
+            let mut worst_rotation_error = (Meters::ZERO, Rad::ZERO);
+            for rotation in CONSISTENCY_ROTATIONS {
+                let rotation = Deg::new(rotation).rad();
+                let expected = RobotPose {
+                    orientation: found.orientation - rotation,
+                    ..found
+                };
+                let (position_error, orientation_error) =
+                    match locate_robot(&rotate_scan(&capture.points, rotation)) {
+                        Ok(rotated) => pose_errors(&rotated, &expected),
+                        Err(_) => (Meters(f64::INFINITY), Rad::HALF_TURN),
+                    };
+                worst_rotation_error.0 = worst_rotation_error.0.max(position_error);
+                if orientation_error > worst_rotation_error.1 {
+                    worst_rotation_error.1 = orientation_error;
+                }
+            }
+            let consistent = worst_rotation_error.0 <= REGRESSION_POSITION_TOLERANCE
+                && worst_rotation_error.1 <= Deg::new(REGRESSION_ORIENTATION_TOLERANCE).rad();
+
+            let (expected, position_tolerance, orientation_tolerance) =
+                match (capture.expected_pose, capture.approx_pose) {
+                    (Some(expected), _) => (
+                        expected,
+                        REGRESSION_POSITION_TOLERANCE,
+                        REGRESSION_ORIENTATION_TOLERANCE,
+                    ),
+                    (None, Some(approx)) => (
+                        approx,
+                        APPROX_POSITION_TOLERANCE,
+                        APPROX_ORIENTATION_TOLERANCE,
+                    ),
+                    (None, None) => {
+                        println!("{:<30} | aucune pose attendue", capture.name);
+                        failures.push(capture.name);
+                        continue;
+                    }
+                };
+            let (position_error, orientation_error) = pose_errors(&found, &expected);
+            if position_error > position_tolerance
+                || orientation_error > Deg::new(orientation_tolerance).rad()
+            {
+                failures.push(capture.name.clone());
+            }
+            let expected = format!(
+                "{}{:>5.1} {:>5.1} {:>5.1}",
+                if capture.expected_pose.is_some() { " " } else { "~" },
+                expected.x.0 * 100.0,
+                expected.y.0 * 100.0,
+                expected.orientation.deg().val()
+            );
+            let errors = format!(
+                "{:>8.1} | {:>8.1}",
+                position_error.0 * 100.0,
+                orientation_error.deg().val()
+            );
+            println!(
+                "{:<30} | {:>18} | {:>5.1} {:>5.1} {:>5.1} | {} | {:>7.1} {:>8.1}",
+                capture.name,
+                expected,
+                found.x.0 * 100.0,
+                found.y.0 * 100.0,
+                found.orientation.deg().val(),
+                errors,
+                worst_rotation_error.0.0 * 100.0,
+                worst_rotation_error.1.deg().val(),
+            );
+            if !consistent {
+                failures.push(capture.name);
+            }
+        }
+        assert!(
+            failures.is_empty(),
+            "localisation hors tolérance pour : {:?}",
+            failures
+        );
     }
 
     #[test]
     fn test_1() {
-        use crate::complex_viewport::ViewportLine;
-        let rec = rerun::RecordingStreamBuilder::new("test_1")
-            .spawn()
-            .unwrap();
+        use crate::recording::{Recording, RecordingMode};
+        // rien n'est envoyé à rerun par défaut, pour que `cargo test` passe sans viewer :
+        // LIDAR_RERUN=spawn pour voir les tours dans le viewer, LIDAR_RERUN=save pour les enregistrer
+        let mode = RecordingMode::from_env(RecordingMode::Disabled).unwrap();
+        let recording = Recording::new("test_1", &mode).unwrap();
+        let rec = recording.stream().clone();
         crate::log_manager::set_up_logging(rec.clone()).unwrap();
-        // TODO distance + cas : TEST_BAS_GAUCHE_ORIENTE_GAUCHE
-        // TODO améliorer l'algo en prenant en compte la proximité des points entre eux. TEST_HAUT_DROITE_ORIENTE_DROITE
         rec.log(
             "lidar/real_robot_pos",
             &rerun::Points2D::new([(0., 0.)])
@@ -1190,71 +2078,22 @@ mod tests {
         )
         .unwrap();
 
-        for log_data in TESTS_DETECTION {
-            info!("NEXT");
-            // notes : fonctionne en 2*2 : TEST_BAS_GAUCHE_ORIENTE_GAUCHE
-            let data = load_log(&log_data);
-            let time1 = std::time::Instant::now();
-            let mut accumulator: [[u16; ANGLE_TAILLE]; DISTANCE_TAILLE] = [[0; ANGLE_TAILLE]; DISTANCE_TAILLE];
-            let time2 = std::time::Instant::now();
-            debug!("perf accumulator init {:?}", time2 - time1);
-            build_hough_accumulator(&mut accumulator, &data);
-            let time3 = std::time::Instant::now();
-            debug!("perf build_hough_accumulator {:?}", time3 - time2);
-            let candidate_line_width = search_all_parallel_lines(&accumulator, FIELD_LENGTH);
-            let candidate_line_length = search_all_parallel_lines(&accumulator, FIELD_WIDTH);
-            let time4 = std::time::Instant::now();
-            debug!("perf candidate_line {:?}", time4 - time3);
-            let field = locate_field_with_4_walls(&candidate_line_width, &candidate_line_length)
-                .or_else(|| {
-                    let time5 = std::time::Instant::now();
-                    debug!("perf locate_field_with_4_walls {:?}", time5 - time4);
-                    info!("Détection 4 murs échouée, tente avec 3 murs");
-                    fallback_on_3_walls(&accumulator, candidate_line_width, candidate_line_length)
-                });
-            let time5 = std::time::Instant::now();
-            debug!("perf locate_field_with_4_walls {:?}", time5 - time4);
-            let mut vl = Vec::with_capacity(50);
-
-            log_lidar_points(&rec, &data).unwrap();
-            if let Some(field_found) = field {
-                vl.push(ViewportLine {
-                    line: field_found.length1.line(),
-                    color: colors::BLUE,
-                });
-                vl.push(ViewportLine {
-                    line: field_found.length2.line(),
-                    color: colors::BLUE,
-                });
-                vl.push(ViewportLine {
-                    line: field_found.width1.line(),
-                    color: colors::BLUE,
-                });
-                vl.push(ViewportLine {
-                    line: field_found.width2.line(),
-                    color: colors::BLUE,
-                });
-
-                log_lidar_lines(&rec, vl).unwrap();
-
-                let center = calculate_center_of_field_in_carthesian(rec.clone(), &field_found);
-                rec.log(
-                    "lidar/cal_center_field",
-                    &rerun::Points2D::new([(center.0 as f32, center.1 as f32)])
-                        .with_colors([colors::MAGENTA])
-                        .with_radii([0.01])
-                        .with_labels(["centre du terrain calculé"])
-                        .with_show_labels(rerun::components::ShowLabels(rerun::datatypes::Bool(
-                            false,
-                        ))),
-                )
-                .unwrap();
-            } else {
-                error!("Echoue à détecter les murs")
+        crate::scan_log::log_field_model(&rec).unwrap();
+
+        let start = std::time::Instant::now();
+        for (n_capture, capture) in load_captures(&default_captures_dir()).unwrap().into_iter().enumerate() {
+            info!("NEXT {} {:?}", capture.name, capture.notes);
+            let data = capture.points;
+            let analysis = WallsAnalysis::run(&data);
+            match analysis.walls.as_ref().map(RobotPose::from_walls) {
+                Some(Ok(pose)) => info!("pose trouvée : {:?}", pose),
+                Some(Err(err)) => error!("Les murs trouvés ne forment pas un terrain : {}", err),
+                None => error!("Echoue à détecter les murs"),
             }
-
-            debug!("perf all lidar_analyzer : {:?}", time1.elapsed());
+            crate::scan_log::log_scan(&rec, n_capture as u64, start.elapsed(), &data, &analysis).unwrap();
+            metrics::global().log_to_rerun(&rec).unwrap();
         }
+        info!("{}", metrics::global().summary());
 
         // for ((first, second), color) in pl.iter().zip(COLORS.iter().cycle()) {
         //     vl.push(ViewportLine {
@@ -1281,6 +2120,40 @@ mod tests {
         //     });
         // }
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_wall_line_serde_round_trip() {
+        let hough = HoughLine {
+            line: PolarLine {
+                distance: Meters(-0.75),
+                angle: Rad::new(1.5),
+            },
+            weight: 42,
+        };
+        let walls = [
+            WallLine::FoundAsParallelLine(hough),
+            WallLine::FoundAsPerpendicular(hough),
+            WallLine::GuessedLine(hough.line),
+        ];
+        let json = serde_json::to_string(&walls).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"found_as_parallel_line":{"line":{"distance":-0.75,"angle":1.5},"weight":42}},"#
+                .to_owned()
+                + r#"{"found_as_perpendicular":{"line":{"distance":-0.75,"angle":1.5},"weight":42}},"#
+                + r#"{"guessed_line":{"distance":-0.75,"angle":1.5}}]"#
+        );
+        let read: [WallLine; 3] = serde_json::from_str(&json).unwrap();
+        for (read, wall) in read.iter().zip(&walls) {
+            assert_eq!(read.line().distance, wall.line().distance);
+            assert_eq!(read.line().angle, wall.line().angle);
+            assert_eq!(
+                std::mem::discriminant(read),
+                std::mem::discriminant(wall)
+            );
+        }
+    }
 }
 
 pub fn test_call() {
@@ -1,33 +0,0 @@
-#[cfg(test)]
-pub(crate) mod lidar_test_data {
-
-    pub const TEST_HAUT_GAUCHE_ORIENTE_GAUCHE: &str = "(5564,1480);(5635,1489);(5706,1497);(5777,1506);(5848,1517);(5919,1526);(5990,1533);(6061,1540);(6132,1551);(6203,1565);(6274,1580);(6345,1596);(31547,464);(31620,466);(31693,467);(31766,469);(31839,471);(31912,473);(31985,475);(32058,478);(32131,480);(32204,482);(32277,485);(32350,487);(32424,490);(32494,492);(32564,496);(32634,499);(32704,503);(32774,506);(32844,510);(32914,514);(32984,522);(33054,527);(33124,533);(33194,539);(33275,545);(33346,551);(33417,556);(33488,561);(33559,566);(33630,571);(33701,573);(33772,576);(33843,580);(33914,584);(33985,591);(34056,596);(34131,601);(34207,608);(34283,617);(34359,625);(34435,633);(34511,640);(34587,647);(34663,655);(34739,663);(34815,671);(34891,678);(34967,686);(35047,695);(35118,704);(35189,712);(35260,719);(35331,725);(776,1090);(847,1110);(918,1140);(989,1169);(1060,1221);(1131,1255);(1202,1289);(1273,1325);(1344,1326);(1415,1328);(1486,1326);(1565,1322);(1641,1319);(1717,1316);(1793,1313);(1869,1311);(1945,1309);(2021,1307);(2097,1305);(2173,1304);(2249,1302);(2325,1301);(2401,1300);(2477,1300);(2544,1299);(2611,1299);(2678,1299);(2745,1299);(2812,1300);(2879,1300);(2946,1301);(3013,1302);(3080,1303);(3147,1304);(3214,1306);(3279,1308);(3350,1310);(3421,1310);(3492,1308);(3563,1306);(3634,1308);(3705,1312);(3776,1316);(3847,1320);(3918,1324);(3989,1328);(4060,1332);(4138,1339);(4209,1344);(4280,1350);(4351,1355);(4422,1361);(4493,1367);(4564,1373);(4635,1380);(4706,1387);(4777,1394);(4848,1402);(4919,1409);(4990,1416);(5061,1424);(5132,1431);(5203,1439);(5274,1446);(5345,1454);(5416,1461);(5487,1468);(5558,1476);(5629,1484);(5700,1492);(5771,1503);(5850,1514);(5921,1525);(5992,1533);(6063,1539);(6134,1546);(6205,1558);(6276,1571);(6347,1590);(6418,1606);(6489,1620);(6560,1638);(6631,1655);(6705,1672);(6776,1689);(6847,1707);(6918,1728);(6989,1747);(7060,1764);(7131,1779);(7202,1797);(7273,1822);(7344,1848);(7415,1874);(7486,1902);(7568,1930);(7639,1957);(7710,1989);(7781,2021);(7852,2050);(7923,2085);(7994,2119);(8065,2161);(8136,2201);(8207,2242);(8278,2278);(8349,2317);(8424,2364);(8499,2415);(8574,2409);(8649,239);(8724,246);(8799,2334);(8874,2327);(8949,2318);(9024,2305);(9099,2291);(9174,2278);(9249,2267);(9333,2253);(9404,2240);(9475,2228);(9546,2217);(9617,2207);(9688,2195);(9759,2184);(9830,2172);(9901,2161);(9972,2152);(10043,2142);(10114,2134);(10192,2127);(10263,2120);(10334,2113);(10405,2106);(10476,2099);(10547,2093);(10618,2088);(10689,2082);(10760,2076);(10831,2070);(10902,2064);(10973,732);(11044,731);(11116,729);(11188,729);(11260,729);(11332,731);(11404,733);(11476,735);(11548,736);(11620,737);(11692,737);(11764,739);(11836,754);(11907,774);(11979,792);(12051,2030);(12123,2036);(12195,2038);(12267,2040);(12339,2042);(12411,2045);(12483,2048);(12555,2051);(12627,2054);(12699,2057);(12777,2059);(12848,2063);(12919,2067);(12990,2074);(13061,2075);(13132,2076);(13203,1984);(13274,1858);(13345,1791);(13416,1694);(13487,1643);(13558,1590);(13633,1530);(13709,1485);(13785,1437);(13861,1406);(13937,1363);(14013,1328);(14089,1302);(14165,1285);(14241,1239);(14317,1192);(14393,1158);(14469,1121);(14542,1089);(14613,1067);(14684,1043);(14755,1031);(14826,1021);(14897,998);(14968,966);(15039,949);(15110,930);(15181,910);(15252,891);(15323,874);(15404,857);(15475,842);(15546,834);(15617,827);(15688,819);(15759,807);(15830,793);(15901,780);(15972,768);(16043,756);(16114,745);(16185,735);(16260,726);(16332,717);(16404,709);(16476,702);(16548,694);(16620,686);(16692,677);(16764,670);(16836,662);(16908,655);(16980,647);(17052,640);(17133,633);(17204,623);(17275,610);(18830,521);(18902,519);(18974,518);(19046,520);(19118,521);(19190,521);(19262,519);(19334,518);(19406,516);(19478,515);(19550,515);(19622,514);(19694,513);(19764,512);(19834,511);(19904,510);(19974,509);(20044,509);(20114,508);(20184,507);(20254,507);(20324,506);(20394,506);(20464,506);(20549,506);(20619,506);(20689,506);(20759,506);(20829,506);(20899,506);(20969,506);(21039,506);(21109,507);(21179,507);(21249,508);(21319,508);(21401,509);(21472,510);(21543,511);(21614,512);(21685,514);(21756,515);(21827,517);(21898,518);(21969,519);(22040,521);(22111,523);(22182,525);(22260,527);(22326,529);(22392,531);(22458,533);(22524,535);(22590,537);(22656,539);(22722,541);(22788,543);(22854,545);(22920,547);(22986,550);(23065,553);(23136,556);(23207,559);(23278,563);(23349,567);(23420,571);(23491,575);(23562,579);(23633,583);(23704,587);(23775,591);(23846,598);(23925,603);(23994,609);(24063,614);(24132,620);(24201,626);(24270,633);(24339,639);(24408,646);(24477,652);(24546,659);(24615,666);(24684,673);(24762,680);(24834,688);(24906,695);(24978,700);(25050,701);(25122,701);(25194,696);(25266,688);(25338,678);(25410,668);(25482,658);(25554,649);(25626,641);(25696,633);(25766,625);(25836,618);(25906,610);(25976,603);(26046,596);(26116,589);(26186,583);(26256,577);(26326,572);(26396,567);(26477,562);(26548,556);(26619,551);(26690,549);(26761,545);(26832,541);(26903,537);(26974,533);(27045,529);(27116,525);(27187,521);(27258,517);(27333,514);(27404,510);(27475,506);(27546,503);(27617,500);(27688,497);(27759,494);(27830,492);(27901,489);(27972,487);(28043,485);(28114,483);(28192,480);(28264,478);(28336,476);(28408,474);(28480,472);(28552,470);(28624,468);(28696,466);(28768,464);(28840,462);(28912,461);(28984,460);(29062,459);(29133,457);(29204,456);(29275,454);(29346,453);(29417,452);(29488,451);(29559,450);(29630,449);(29701,448);(29772,448);(29843,448);(29925,447);(29994,447);(30063,446);(30132,446);(30201,446);(30270,446);(30339,446);(30408,446);(30477,446);(30546,446);(30615,447);(30684,447);(30766,448);(30837,449);(30908,450);(30979,451);(31050,452);(31121,453);(31192,454);(31263,456);(31334,458);(31405,460);(31476,462);(31547,464);(31622,465);(31699,467);(31776,469);(31853,471);(31930,473);(32007,475);(32084,477);(32161,480);(32238,483);(32315,485);(32392,488);(32469,490);(32549,493);(32620,496);(32691,499);(32762,503);(32833,507);(32904,511);(32975,515);(33046,522);(33117,527);(33188,534);(33259,541);(33330,547);(33409,553);(33480,559);(33551,564);(33622,566);(33693,569);(33764,573);(33835,577);(33906,580);(33977,584);(34048,591);(34119,597);(34190,603);(34268,610);(34338,619);(34408,627);(34478,635);(34548,642);(34618,649);(34688,656);(34758,663);(34828,671);(34898,679);(34968,687);(35038,694);(35119,703);(35190,713);(35261,720);(35332,721);(772,1090);(843,1112);(914,1139);(985,1167);(1056,1220);(1127,1253);(1198,1281);(1269,1323);(1340,1325);(1411,1327);(1482,1326);(1553,1322);(1638,1319);(1714,1315);(1790,1313);(1866,1310);(1942,1307);(2018,1305);(2094,1304);(2170,1303);(2246,1302);(2322,1301);(2398,1300);(2474,1300);(2548,1300);(2614,1299);(2680,1299);(2746,1299);(2812,1300);(2878,1300);(2944,1301);(3010,1302);(3076,1303);(3142,1304);(3208,1305);(3274,1307);(3347,1308);(3418,1308);(3489,1306);(3560,1305);(3631,1307);(3702,1314);(3773,1315);(3844,1319);(3915,1323);(3986,1327);(4057,1334);(4128,1339);(4205,1344);(4274,1349);(4343,1354);(4412,1360);(4481,1366);(4550,1372);(4619,1379);(4688,1386);(4757,1393);(4826,1400);(4895,1408);(4964,1416)";
-    pub const TEST_HAUT_GAUCHE_ORIENTE_DROITE: &str =  "(26991,638);(27062,645);(27133,652);(27204,659);(27275,666);(27346,674);(27417,682);(27488,690);(27559,698);(27630,707);(27701,718);(27772,727);(27847,736);(27918,748);(27989,760);(28060,773);(28131,785);(28202,799);(28273,815);(28344,831);(28415,847);(28486,864);(28557,881);(28628,900);(28768,921);(28839,943);(28910,966);(28981,989);(29052,1010);(29123,1031);(29194,1054);(29265,1075);(29336,1099);(29407,1123);(29478,1147);(29549,1189);(29630,1221);(29701,1253);(29772,1287);(29843,1310);(29914,1311);(29985,1311);(30056,1308);(30127,1305);(30198,1302);(30269,1300);(30340,1297);(30411,1293);(30490,1290);(30561,1288);(30632,1285);(30703,1283);(30774,1283);(30845,1283);(30916,1284);(30987,1284);(31058,1284);(31129,1284);(31200,1283);(31271,1282);(31352,1282);(31416,1282);(31480,1282);(31544,1284);(31608,1286);(31672,1288);(31736,1291);(31800,1294);(31864,1295);(31928,1294);(31992,1290);(32056,1289);(32133,1297);(32204,1298);(32275,1301);(32346,1304);(32417,1308);(32488,1312);(32559,1315);(32630,1322);(32701,1327);(32772,1333);(32843,1335);(32914,1343);(32995,1349);(33066,1355);(33137,1361);(33208,1367);(33279,1375);(33350,1383);(33421,1392);(33492,1402);(33563,1410);(33634,1417);(33705,1426);(33776,1434);(33853,1443);(33924,1451);(33995,1459);(34066,1468);(34137,1477);(34208,1487);(34279,1497);(34350,1507);(34421,1516);(34492,1525);(34563,1535);(34634,1547);(34779,1560);(34849,1573);(34919,1591);(34989,1606);(35059,1620);(35129,1636);(35199,1652);(35269,1668);(774,2244);(845,2295);(916,2336);(987,2379);(1058,2426);(1129,2410);(1200,2393);(1271,2376);(1348,2358);(1419,2342);(1490,2326);(1561,2311);(1632,2296);(1703,2282);(1774,2266);(1845,259);(1916,265);(1987,2221);(2058,2213);(2129,2206);(2204,2196);(2275,2186);(2346,2177);(2417,2168);(2488,2158);(2559,2150);(2630,2142);(2701,2134);(2772,2126);(2843,2118);(2914,2111);(2985,2105);(3063,2099);(3133,2093);(3203,1452);(3273,1451);(3343,1450);(3413,1449);(3483,1448);(3553,1447);(3623,1446);(3693,1448);(3763,778);(3833,763);(3905,755);(3976,753);(4047,751);(4118,751);(4189,752);(4260,754);(4331,757);(4402,761);(4473,768);(4544,773);(4615,782);(4686,796);(4761,831);(4832,2059);(4903,2058);(4974,2058);(5045,2061);(5116,2065);(5187,2069);(5258,2073);(5329,2077);(5400,2081);(5471,2084);(5542,2086);(5619,2069);(5691,1933);(5763,1848);(5835,1779);(5907,1713);(5979,1646);(6051,1582);(6123,1535);(6195,1483);(6267,1446);(6339,1406);(6411,1367);(6493,1332);(6564,1296);(6635,1283);(6706,1259);(6777,1207);(6848,1167);(6919,1134);(6990,1102);(7061,1077);(7132,1058);(7203,1050);(7274,1043);(7352,1011);(7421,985);(7490,960);(7559,939);(7628,919);(7697,899);(7766,882);(7835,867);(7904,855);(7973,848);(8042,841);(8111,829);(8190,814);(8261,800);(8332,788);(8403,776);(8474,765);(8545,754);(8616,745);(8687,736);(8758,727);(8829,720);(8900,712);(8971,704);(9049,697);(9121,689);(9193,682);(9265,675);(9337,668);(9409,661);(9481,654);(9553,647);(9625,640);(9697,633);(9769,626);(9841,620);(9923,615);(9994,610);(10065,605);(10136,600);(10207,595);(10278,593);(10349,586);(10420,584);(10491,580);(10562,576);(10633,572);(10704,568);(10779,565);(10850,561);(10921,557);(10992,554);(11063,551);(11134,548);(11205,546);(11276,544);(11347,541);(11418,539);(11489,537);(11560,535);(11637,533);(11708,531);(11779,530);(11850,529);(11921,527);(11992,526);(12063,526);(12134,525);(12205,524);(12276,523);(12347,522);(12418,521);(12490,520);(12561,520);(12632,519);(12703,519);(12774,518);(12845,518);(12916,518);(12987,518);(13058,518);(13129,518);(13200,518);(13271,518);(13348,518);(13420,519);(13492,519);(13564,519);(13636,520);(13708,520);(13780,521);(13852,522);(13924,523);(13996,524);(14068,525);(14140,526);(14208,528);(14279,529);(14350,530);(14421,531);(14492,532);(14563,534);(14634,536);(14705,538);(14776,540);(14847,542);(14918,544);(14989,546);(15070,548);(15146,550);(15222,553);(15298,556);(15374,559);(15450,562);(15526,565);(15602,568);(15678,571);(15754,574);(15830,578);(15906,581);(15980,585);(16052,592);(16124,597);(16196,602);(16268,607);(16340,609);(16412,616);(16484,621);(16556,626);(16628,632);(16700,637);(16772,643);(16850,649);(16921,655);(16992,660);(17063,665);(17134,670);(17205,675);(18849,546);(18920,545);(18991,544);(19062,542);(19133,537);(19204,535);(19275,531);(19346,527);(19420,524);(19490,521);(19560,518);(19630,514);(19700,510);(19770,507);(19840,503);(19910,500);(19980,497);(20050,493);(20120,490);(20190,488);(20262,485);(20333,482);(20404,480);(20475,477);(20546,475);(20617,473);(20688,470);(20759,468);(20830,465);(20901,463);(20972,461);(21043,459);(21120,457);(21193,456);(21266,455);(21339,453);(21412,452);(21485,451);(21558,449);(21631,448);(21704,446);(21777,445);(21850,444);(21923,444);(21998,443);(22069,442);(22140,442);(22211,441);(22282,441);(22353,440);(22424,440);(22495,440);(22566,440);(22637,440);(22708,440);(22779,439);(22850,439);(22921,439);(22992,439);(23063,439);(23134,439);(23205,439);(23276,440);(23347,440);(23418,441);(23489,442);(23560,443);(23631,444);(23709,445);(23778,446);(23847,448);(23916,450);(23985,452);(24054,454);(24123,456);(24192,458);(24261,460);(24330,462);(24399,464);(24468,467);(24543,469);(24614,471);(24685,474);(24756,476);(24827,479);(24898,482);(24969,485);(25040,488);(25111,492);(25182,495);(25253,499);(25324,503);(25402,507);(25468,515);(25534,522);(25600,529);(25666,536);(25732,543);(25798,549);(25864,554);(25930,556);(25996,560);(26062,567);(26128,569);(26208,573);(26279,580);(26350,582);(26421,589);(26492,595);(26563,601);(26634,607);(26705,613);(26776,620);(26847,627);(26918,634);(26989,641);(27066,647);(27142,654);(27218,661);(27294,669);(27370,676);(27446,684);(27522,692);(27598,700);(27674,708);(27750,717);(27826,728);(27902,739);(27976,750);(28047,763);(28118,776);(28189,789);(28260,803);(28331,817);(28402,834);(28473,851);(28544,868);(28615,886);(28686,904);(28757,924);(28829,944);(28900,966);(28971,988);(29042,1009);(29113,1030);(29184,1051);(29255,1072);(29326,1096);(29397,1120);(29468,1145);(29539,1190);(29610,1218);(29684,1254);(29757,1285);(29830,1316);(29903,1314);(29976,1310);(30049,1307);(30122,1305);(30195,1303);(30268,1300);(30341,1297);(30414,1293);(30487,1290);(30565,1287);(30636,1285);(30707,1284);(30778,1283);(30849,1283);(30920,1283);(30991,1283);(31062,1283);(31133,1283);(31204,1282);(31275,1282);(31346,1282);(31420,1282);(31489,1282);(31558,1284);(31627,1286);(31696,1289);(31765,1293);(31834,1296);(31903,1298);(31972,1297);(32041,1289);(32110,1289);(32179,1295);(32258,1297);(32330,1300);(32402,1303);(32474,1306);(32546,1310);(32618,1313);(32690,1320);(32762,1326);(32834,1332);(32906,1337);(32978,1342);(33050,1348);(33124,1355);(33195,1361);(33266,1368);(33337,1377);(33408,1383);(33479,1390);(33550,1398);(33621,1406);(33692,1415);(33763,1424);(33834,1432);(33905,1440);(33982,1449);(34054,1457);(34126,1465);(34198,1475);(34270,1485);(34342,1495);(34414,1505);(34486,1514);(34558,1524);(34630,1533);(34702,1544);(34774,1556);(34847,1569);(34917,1584);(34987,1600);(35057,1616);(35127,1630);(35197,1646);(35267,1661);(35337,1674)";
-    pub const TEST_HAUT_MILIEU_ORIENTE_GAUCHE: &str = "(703,132);(25918,953);(25989,956);(26060,960);(26131,964);(26202,968);(26273,975);(26344,980);(26415,985);(26486,990);(26557,995);(26628,1001);(26699,1006);(26775,1011);(26844,1017);(26913,1023);(26982,1030);(27051,1035);(27120,1042);(27189,1042);(27258,1023);(27327,989);(27396,962);(27465,936);(27534,914);(27614,892);(27685,874);(27756,855);(27827,839);(27898,821);(27969,805);(28040,789);(28111,774);(28182,760);(28253,746);(28324,733);(28395,722);(28475,711);(28554,700);(28633,688);(28712,677);(28791,666);(28870,656);(28949,647);(29028,637);(29107,629);(29186,621);(29265,614);(29344,606);(29418,598);(29489,590);(29560,582);(29631,574);(29702,567);(29773,561);(29844,555);(29915,549);(29986,544);(30057,539);(30128,534);(30199,529);(30261,527);(30332,523);(30403,519);(30474,516);(30545,512);(30616,509);(30687,506);(30758,502);(30829,499);(30900,496);(30971,493);(31042,490);(31118,487);(31190,484);(31262,482);(31334,479);(31406,477);(31478,475);(31550,472);(31622,470);(31694,468);(31766,466);(31838,464);(31910,464);(31989,465);(32060,467);(32131,468);(32202,468);(32273,468);(32344,467);(32415,464);(32486,462);(32557,460);(32628,459);(32699,458);(32770,457);(32847,455);(32918,454);(32989,453);(33060,452);(33131,451);(33202,450);(33273,449);(33344,448);(33415,447);(33486,447);(33557,446);(33628,445);(33700,444);(33771,442);(33842,439);(33913,436);(33984,434);(34055,432);(34126,430);(34197,430);(34268,433);(34339,440);(34410,442);(34481,445);(34558,446);(34628,447);(34698,448);(34768,448);(34838,448);(34908,449);(34978,449);(35048,449);(35118,450);(35188,451);(35258,451);(754,477);(825,488);(896,498);(967,505);(1038,507);(1117,511);(1188,515);(1259,519);(1330,526);(1401,531);(1472,533);(1543,540);(1614,545);(1685,550);(1756,556);(1827,562);(1898,568);(1976,574);(2047,580);(2118,586);(2189,593);(2260,599);(2331,606);(2402,614);(2473,622);(2544,630);(2615,638);(2686,646);(2757,654);(2835,662);(2907,669);(2979,677);(3051,685);(3123,693);(3195,702);(3267,712);(3339,722);(3411,733);(3483,743);(3555,755);(3627,767);(3701,780);(3772,797);(3843,818);(3914,839);(3985,856);(4056,874);(4127,890);(4198,907);(4269,926);(4340,936);(4411,938);(4482,940);(4564,938);(4635,934);(4706,931);(4777,927);(4848,924);(4919,921);(4990,918);(5061,915);(5132,912);(5203,910);(5274,907);(5345,905);(5423,903);(5494,901);(5565,900);(5636,898);(5707,897);(5778,896);(5849,895);(5920,894);(5991,893);(6062,892);(6133,892);(6204,892);(6261,893);(6332,893);(6403,893);(6474,893);(6545,893);(6616,893);(6687,894);(6758,894);(6829,895);(6900,896);(6971,897);(7042,899);(7120,900);(7192,902);(7264,904);(7336,906);(7408,906);(7480,899);(7552,898);(7624,898);(7696,904);(7768,905);(7840,908);(7912,911);(7994,914);(8065,918);(8136,921);(8207,925);(8278,929);(8349,933);(8420,937);(8491,944);(8562,949);(8633,954);(8704,959);(8775,965);(8849,971);(8919,977);(8989,984);(9059,990);(9129,997);(9199,1004);(9269,1012);(9339,1019);(9409,1026);(9479,1034);(9549,1042);(9619,1049);(9690,1057);(9761,1065);(9832,1073);(9903,1081);(9974,1090);(10045,1099);(10116,1109);(10187,1119);(10258,1128);(10329,1138);(10400,1149);(10471,1162);(10550,1175);(10622,1188);(10694,1199);(10766,1209);(10838,1218);(10910,1231);(10982,1245);(11054,1256);(11126,139);(11198,139);(11270,1293);(11342,1332);(11420,1349);(11497,1371);(11574,1393);(11651,1418);(11728,1443);(11805,1468);(11882,1492);(11959,1509);(12036,1525);(12113,1556);(12190,1598);(12267,1627);(12347,1657);(12418,1690);(12489,1726);(12560,1767);(12631,1807);(12702,1849);(12773,1895);(12844,1943);(12915,1996);(12986,2048);(13057,2102);(13128,2191);(13206,2225);(13276,2213);(13346,2201);(13416,2191);(13486,2183);(13556,1112);(13626,1090);(13696,1082);(13766,1081);(13836,1081);(13906,1083);(13976,1084);(14058,1086);(14129,1087);(14200,1086);(14271,2089);(14342,2084);(14413,2079);(14484,2077);(14555,2073);(14626,2066);(14697,2064);(14768,2060);(14839,2056);(14921,2053);(14990,2051);(15059,2049);(15128,2047);(15197,2046);(15266,2044);(15335,2042);(15404,2040);(15473,2039);(15542,2038);(15611,2038);(15680,1289);(15759,1239);(15830,1244);(15901,1246);(15972,1249);(16043,1250);(16114,1250);(16185,1252);(16256,1259);(16327,2042);(16398,2053);(16469,2061);(16540,2067);(16614,2073);(16693,2078);(16772,2083);(16851,2088);(16930,2093);(17009,2099);(17088,2105);(17167,2113);(17246,2121);(18847,1654);(18918,1625);(18989,1597);(19060,1570);(19131,1546);(19213,1524);(19290,1507);(19367,1476);(19444,1428);(19521,1427);(19598,1419);(19675,1398);(19752,1373);(19829,1348);(19906,1326);(19983,1308);(20060,1293);(20130,1283);(20201,1274);(20272,1261);(20343,1246);(20414,1230);(20485,1215);(20556,1200);(20627,1184);(20698,1169);(20769,1155);(20840,1143);(20911,1135);(20992,1129);(21055,1122);(21118,1115);(21181,1109);(21244,1101);(21307,1091);(21370,1082);(21433,1074);(21496,1066);(21559,1058);(21622,1051);(21685,1044);(21762,1038);(21833,1031);(21904,1025);(21975,1019);(22046,1013);(22117,1007);(22188,1001);(22259,995);(22330,989);(22401,983);(22472,977);(22543,971);(22621,966);(22692,964);(22763,960);(22834,956);(22905,952);(22976,948);(23047,944);(23118,941);(23189,938);(23260,935);(23331,932);(23402,929);(23477,927);(23547,925);(23617,923);(23687,921);(23757,919);(23827,918);(23897,918);(23967,917);(24037,917);(24107,917);(24177,917);(24247,918);(24332,918);(24403,918);(24474,918);(24545,918);(24616,919);(24687,919);(24758,920);(24829,921);(24900,922);(24971,923);(25042,925);(25113,926);(25188,928);(25254,930);(25320,932);(25386,934);(25452,936);(25518,938);(25584,940);(25650,943);(25716,945);(25782,948);(25848,950);(25914,954);(25994,957);(26065,961);(26136,965);(26207,969);(26278,976);(26349,981);(26420,986);(26491,991);(26562,997);(26633,1003);(26704,1008);(26775,1014);(26853,1019);(26923,1026);(26993,1031);(27063,1037);(27133,1042);(27203,1047);(27273,1012);(27343,983);(27413,955);(27483,930);(27553,908);(27623,887);(27705,869);(27776,851);(27847,833);(27918,815);(27989,800);(28060,785);(28131,771);(28202,757);(28273,743);(28344,730);(28415,717);(28486,707);(28568,696);(28644,686);(28720,676);(28796,665);(28872,656);(28948,646);(29024,638);(29100,630);(29176,622);(29252,613);(29328,604);(29404,597);(29477,589);(29548,581);(29619,574);(29690,567);(29761,560);(29832,554);(29903,548);(29974,543);(30045,538);(30116,536);(30187,532);(30258,528);(30336,524);(30407,520);(30478,517);(30549,513);(30620,509);(30691,506);(30762,502);(30833,499);(30904,496);(30975,493);(31046,490);(31117,487);(31195,485);(31267,482);(31339,480);(31411,477);(31483,475);(31555,473);(31627,471);(31699,469);(31771,467);(31843,465);(31915,465);(31987,466);(32065,467);(32136,468);(32207,469);(32278,469);(32349,467);(32420,465);(32491,463);(32562,461);(32633,459);(32704,458);(32775,456);(32846,456);(32921,455);(32992,454);(33063,453);(33134,452);(33205,451);(33276,450);(33347,449);(33418,449);(33489,448);(33560,447);(33631,446);(33702,445);(33784,443);(33855,440);(33926,437);(33997,435);(34068,434);(34139,434);(34210,436);(34281,438);(34352,440);(34423,443);(34494,444);(34565,445);(34643,446);(34713,447);(34783,448);(34853,448);(34923,449);(34993,449);(35063,449);(35133,450);(35203,450);(35273,448)";
-    pub const TEST_HAUT_MILIEU_ORIENTE_DROITE: &str = "(26136,901);(26207,899);(26278,897);(26349,895);(26420,893);(26491,891);(26562,889);(26633,887);(26704,886);(26775,885);(26846,884);(26917,883);(26990,882);(27067,881);(27144,880);(27221,879);(27298,879);(27375,879);(27452,879);(27529,879);(27606,879);(27683,880);(27760,881);(27837,882);(27918,882);(27989,883);(28060,885);(28131,887);(28202,889);(28273,891);(28344,893);(28415,894);(28486,896);(28557,896);(28628,885);(28699,885);(28772,890);(28844,892);(28916,896);(28988,899);(29060,902);(29132,905);(29204,908);(29276,912);(29348,916);(29420,919);(29492,923);(29564,927);(29636,934);(29707,939);(29778,944);(29849,950);(29920,955);(29991,961);(30062,967);(30133,974);(30204,980);(30275,987);(30346,994);(30417,1002);(30493,1009);(30564,1017);(30635,1024);(30706,1032);(30777,1041);(30848,1049);(30919,1058);(30990,1066);(31061,1073);(31132,1082);(31203,1091);(31274,1099);(31350,1108);(31421,1117);(31492,1126);(31563,1135);(31634,1146);(31705,1159);(31776,1172);(31847,1185);(31918,1197);(31989,1209);(32060,1222);(32131,1235);(32207,1250);(32278,1265);(32349,1280);(32420,1298);(32491,1317);(32562,1336);(32633,1355);(32704,1378);(32775,1398);(32846,1429);(32917,1448);(32988,1471);(33065,1490);(33136,1514);(33207,1540);(33278,1582);(33349,1610);(33420,1643);(33491,1678);(33562,1712);(33633,1752);(33704,1799);(33775,1840);(33846,1889);(33915,1944);(33991,1999);(34067,2054);(34143,2107);(34219,2183);(34295,2223);(34371,2213);(34447,2202);(34523,2192);(34599,2182);(34675,2173);(34751,2164);(34832,2154);(34903,2145);(34974,2136);(35045,2128);(35116,2119);(35187,2111);(35258,2104);(35329,2099);(781,2038);(852,2039);(923,2041);(994,2042);(1065,2044);(1136,2046);(1207,2048);(1278,2051);(1356,2053);(1427,2057);(1498,2061);(1569,2065);(1640,2069);(1711,2076);(1782,2081);(1853,2086);(1924,2092);(1995,2098);(2066,2104);(2137,2111);(2208,2118);(2278,2125);(2348,2133);(2418,2141);(2488,2149);(2558,2158);(2628,2169);(2698,2178);(2768,2186);(2838,1460);(2908,1450);(2978,1448);(3060,1444);(3136,246);(3212,1447);(3288,1448);(3364,1450);(3440,1458);(3516,1490);(3592,1489);(3668,1789);(3744,1752);(3820,1721);(3896,1689);(3973,1661);(4044,1634);(4115,1610);(4186,1585);(4257,1559);(4328,1534);(4399,1510);(4470,1494);(4541,1481);(4612,1449);(4683,840);(4754,825);(4822,815);(4893,810);(4964,809);(5035,809);(5106,811);(5177,819);(5248,826);(5319,834);(5390,845);(5461,860);(5532,879);(5603,1208);(5684,1190);(5751,1175);(5818,1162);(5885,1146);(5952,1139);(6019,1134);(6086,1129);(6153,1123);(6220,1114);(6287,1104);(6354,1095);(6421,1086);(6494,1079);(6565,1071);(6636,1064);(6707,1057);(6778,1050);(6849,1043);(6920,1037);(6991,1030);(7062,1024);(7133,1018);(7204,1012);(7275,1006);(7352,999);(7421,993);(7490,987);(7559,980);(7628,975);(7697,969);(7766,967);(7835,963);(7904,956);(7973,954);(8042,950);(8111,947);(8190,944);(8261,941);(8332,938);(8403,935);(8474,933);(8545,931);(8616,929);(8687,927);(8758,926);(8829,924);(8900,923);(8971,922);(9049,922);(9121,922);(9193,921);(9265,921);(9337,920);(9409,920);(9481,920);(9553,920);(9625,920);(9697,920);(9769,921);(9841,922);(9919,923);(9995,924);(10071,925);(10147,927);(10223,928);(10299,930);(10375,932);(10451,934);(10527,936);(10603,938);(10679,941);(10755,943);(10832,946);(10903,948);(10974,951);(11045,954);(11116,958);(11187,962);(11258,966);(11329,970);(11400,977);(11471,982);(11542,987);(11613,992);(11691,998);(11762,1004);(11833,1009);(11904,1015);(11975,1021);(12046,1028);(12117,1034);(12188,1036);(12259,1040);(12330,1040);(12401,1026);(12472,999);(12544,971);(12615,947);(12686,923);(12757,901);(12828,880);(12899,862);(12970,844);(13041,826);(13112,810);(13183,794);(13254,778);(13325,764);(13406,749);(13477,735);(13548,723);(13619,713);(13690,702);(13761,690);(13832,679);(13903,669);(13974,658);(14045,648);(14116,638);(14187,630);(14255,622);(14326,615);(14397,607);(14468,599);(14539,591);(14610,583);(14681,575);(14752,569);(14823,562);(14894,556);(14965,551);(15036,546);(15117,541);(15190,536);(15263,534);(15336,530);(15409,526);(15482,522);(15555,518);(15628,514);(15701,511);(15774,507);(15847,504);(15920,501);(15995,498);(16066,495);(16137,492);(16208,489);(16279,486);(16350,483);(16421,480);(16492,478);(16563,476);(16634,473);(16705,471);(16776,469);(16850,468);(16921,468);(16992,468);(17063,468);(17134,468);(17205,457);(18828,142);(18899,429);(18970,430);(19041,437);(19112,438);(19183,440);(19254,441);(19325,441);(19396,442);(19474,443);(19540,444);(19606,445);(19672,446);(19738,446);(19804,446);(19870,447);(19936,447);(20002,448);(20068,449);(20134,450);(20200,451);(20280,452);(20351,453);(20422,454);(20493,455);(20564,456);(20635,457);(20706,458);(20777,460);(20848,461);(20919,463);(20990,464);(21061,466);(21139,468);(21210,471);(21281,473);(21352,476);(21423,478);(21494,481);(21565,483);(21636,486);(21707,489);(21778,492);(21849,495);(21920,498);(21991,502);(22062,505);(22133,509);(22204,512);(22275,516);(22346,520);(22417,524);(22488,528);(22559,532);(22630,539);(22701,544);(22772,549);(22847,555);(22918,560);(22989,566);(23060,572);(23131,578);(23202,584);(23273,591);(23344,598);(23415,605);(23486,613);(23557,620);(23628,628);(23706,637);(23776,645);(23846,652);(23916,659);(23986,666);(24056,674);(24126,681);(24196,690);(24266,699);(24336,708);(24406,718);(24476,729);(24547,740);(24618,752);(24689,764);(24760,777);(24831,793);(24902,811);(24973,829);(25044,845);(25115,860);(25186,875);(25257,893);(25328,910);(25403,922);(25474,927);(25545,928);(25616,928);(25687,925);(25758,922);(25829,918);(25900,914);(25971,911);(26042,908);(26113,905);(26184,902);(26262,899);(26333,897);(26404,895);(26475,893);(26546,891);(26617,890);(26688,888);(26759,887);(26830,885);(26901,884);(26972,882);(27043,882);(27121,881);(27193,880);(27265,879);(27337,879);(27409,879);(27481,879);(27553,879);(27625,880);(27697,880);(27769,881);(27841,881);(27913,882);(27991,883);(28060,884);(28129,886);(28198,888);(28267,890);(28336,892);(28405,894);(28474,896);(28543,898);(28612,886);(28681,886);(28750,888);(28829,892);(28901,895);(28973,899);(29045,902);(29117,905);(29189,908);(29261,912);(29333,916);(29405,920);(29477,924);(29549,928);(29621,932);(29695,939);(29767,944);(29839,950);(29911,955);(29983,961);(30055,967);(30127,974);(30199,980);(30271,987);(30343,994);(30415,1002);(30487,1009);(30562,1016);(30633,1024);(30704,1032);(30775,1039);(30846,1047);(30917,1056);(30988,1064);(31059,1072);(31130,1081);(31201,1090);(31272,1099);(31343,1108);(31420,1117);(31490,1127);(31560,1137);(31630,1148);(31700,1159);(31770,1171);(31840,1183);(31910,1194);(31980,1205);(32050,1218);(32120,1232);(32190,1247);(32266,1263);(32337,1280);(32408,1297);(32479,1313);(32550,1332);(32621,1355);(32692,1377);(32763,1397);(32834,1420);(32905,1444);(32976,1468);(33047,1490);(33124,1506);(33195,1528);(33266,1567);(33337,1604);(33408,1640);(33479,1668);(33550,1703);(33621,1749);(33692,1791);(33763,1836);(33834,1877);(33905,1926);(33980,1989);(34058,2044);(34136,2095);(34214,2156);(34292,2222);(34370,2214);(34448,2205);(34526,2196);(34604,2183);(34682,2170);(34760,2160);(34838,2152);(34918,2145);(34988,2138);(35058,2129);(35128,2119);(35198,2110);(35268,2101);(35338,2093)";
-    pub const TEST_HAUT_DROITE_ORIENTE_GAUCHE: &str = "(747,552);(27757,489);(27828,485);(27899,481);(27970,477);(28041,473);(28112,469);(28183,466);(28254,462);(28325,459);(28396,455);(28467,452);(28538,448);(28615,445);(28687,442);(28759,439);(28831,436);(28903,434);(28975,432);(29047,430);(29119,428);(29191,426);(29263,424);(29335,421);(29407,419);(29479,417);(29551,415);(29623,413);(29695,412);(29767,410);(29839,409);(29911,408);(29983,407);(30055,406);(30127,405);(30199,404);(30271,403);(30350,402);(30421,402);(30492,401);(30563,401);(30634,401);(30705,401);(30776,400);(30847,400);(30918,400);(30989,400);(31060,400);(31131,400);(31208,400);(31279,400);(31350,400);(31421,400);(31492,400);(31563,401);(31634,401);(31705,401);(31776,402);(31847,402);(31918,402);(31989,403);(32065,403);(32136,403);(32207,404);(32278,405);(32349,407);(32420,409);(32491,410);(32562,411);(32633,412);(32704,413);(32775,414);(32846,415);(32922,416);(32993,417);(33064,418);(33135,419);(33206,420);(33277,422);(33348,424);(33419,425);(33490,427);(33561,428);(33632,429);(33703,430);(33780,430);(33851,430);(33922,430);(33993,430);(34064,431);(34135,433);(34206,439);(34277,445);(34348,451);(34419,456);(34490,457);(34561,461);(34637,465);(34714,469);(34791,476);(34868,481);(34945,487);(35022,493);(35099,500);(35176,506);(35253,506);(35330,505);(775,558);(847,563);(919,563);(991,563);(1063,560);(1135,556);(1210,553);(1281,551);(1352,548);(1423,545);(1494,542);(1565,540);(1636,537);(1707,535);(1778,533);(1849,531);(1920,529);(1991,527);(2065,525);(2136,523);(2207,522);(2278,520);(2349,519);(2420,518);(2491,517);(2562,515);(2633,514);(2704,514);(2775,513);(2846,512);(2925,512);(2996,511);(3067,511);(3138,510);(3209,510);(3280,510);(3351,510);(3422,510);(3493,510);(3564,510);(3635,510);(3706,510);(3777,510);(3849,511);(3921,511);(3993,511);(4065,512);(4137,512);(4209,513);(4281,513);(4353,514);(4425,515);(4497,516);(4569,517);(4640,518);(4711,520);(4782,521);(4853,522);(4924,523);(4995,524);(5066,526);(5137,528);(5208,530);(5279,532);(5350,536);(5421,545);(5492,559);(5563,521);(5634,528);(5705,533);(5776,535);(5847,538);(5918,541);(5989,544);(6060,547);(6131,551);(6202,554);(6273,558);(6347,561);(6419,565);(6491,569);(6563,573);(6635,580);(6707,585);(6779,590);(6851,595);(6923,600);(6995,605);(7067,610);(7139,616);(7210,622);(7279,627);(7348,633);(7417,640);(7486,647);(7555,654);(7624,661);(7693,668);(7762,675);(7831,683);(7900,690);(7969,698);(8048,706);(8119,713);(8190,721);(8261,730);(8332,740);(8403,751);(8474,762);(8545,773);(8616,784);(8687,796);(8758,809);(8829,821);(8907,834);(8977,847);(9047,860);(9117,873);(9187,888);(9257,903);(9327,920);(9397,936);(9467,952);(9537,966);(9607,979);(9677,999);(9759,1021);(9830,1044);(9901,1069);(9972,1092);(10043,1124);(10114,1152);(10185,1184);(10256,1220);(10327,1266);(10398,1303);(10469,1319);(10540,1352);(10615,1403);(10694,1452);(10773,1514);(10852,1570);(10931,1635);(11010,1702);(11089,1784);(11168,1902);(11247,1975);(11326,2107);(11405,2109);(11484,2109);(11560,2101);(11632,2096);(11704,2090);(11776,2088);(11848,2081);(11920,2074);(11992,2067);(12064,212);(12136,214);(12208,2052);(12280,2052);(12352,2054);(12433,2055);(12505,2054);(12577,2051);(12649,2047);(12721,2045);(12793,2043);(12865,2042);(12937,2042);(13009,2042);(13081,2044);(13153,2046);(13225,2049);(13296,2053);(13365,2057);(13434,2060);(13503,2062);(13572,2064);(13641,2066);(13710,2070);(13779,2074);(13848,2078);(13917,2086);(13986,2092);(14055,2097);(14137,2102);(14208,2110);(14279,2117);(14350,2125);(14421,2134);(14492,2142);(14563,2149);(14634,2157);(14705,2167);(14776,2177);(14847,2187);(14918,2196);(14997,2207);(15068,2219);(15139,2231);(15210,2243);(15281,2253);(15352,2265);(15423,2281);(15494,2294);(15565,2308);(15636,2325);(15707,2340);(15778,2356);(15849,2372);(15920,2384);(15991,2389);(16062,2390);(16133,678);(16204,656);(16275,648);(16346,646);(16417,644);(16488,643);(16559,640);(16630,637);(16757,634);(16828,630);(16899,628);(16970,628);(17041,634);(17112,642);(17183,650);(17254,652);(18831,1016);(18902,1016);(18973,1018);(19044,1031);(19115,1052);(19186,1080);(19257,1443);(19332,1441);(19398,1435);(19464,1427);(19530,1420);(19596,1413);(19662,1405);(19728,1398);(19794,1391);(19860,1385);(19926,1379);(19992,1373);(20058,1367);(20137,1362);(20208,1360);(20279,1353);(20350,1351);(20421,1347);(20492,1343);(20563,1340);(20634,1337);(20705,1334);(20776,1331);(20847,1329);(20918,1327);(20997,1325);(21067,1323);(21137,1321);(21207,1319);(21277,1317);(21347,1316);(21417,1316);(21487,1316);(21557,1316);(21627,1316);(21697,1317);(21767,1317);(21852,1318);(21923,1319);(21994,1321);(22065,1322);(22136,1322);(22207,1324);(22278,1325);(22349,1327);(22420,1329);(22491,1332);(22562,1335);(22633,1337);(22712,1340);(22782,1343);(22852,1346);(22922,1349);(22992,1353);(23062,1357);(23132,1364);(23202,1369);(23272,1374);(23342,1379);(23412,1386);(23482,1391);(23567,1397);(23642,1397);(23717,1389);(23792,1314);(23867,1261);(23942,1208);(24017,1172);(24092,1133);(24167,1099);(24242,1065);(24317,1036);(24392,1003);(24469,975);(24541,948);(24613,921);(24685,898);(24757,873);(24829,851);(24901,833);(24973,816);(25045,800);(25117,784);(25189,770);(25261,756);(25329,743);(25394,730);(25459,718);(25524,708);(25589,697);(25654,690);(25719,685);(25784,680);(25849,675);(25914,669);(25979,662);(26044,651);(26112,639);(26184,629);(26256,620);(26328,611);(26400,602);(26472,594);(26544,586);(26616,579);(26688,571);(26760,564);(26832,557);(26904,550);(26976,544);(27042,538);(27108,532);(27174,526);(27240,521);(27306,515);(27372,510);(27438,505);(27504,500);(27570,495);(27636,493);(27702,489);(27780,485);(27851,481);(27922,477);(27993,473);(28064,469);(28135,465);(28206,461);(28277,457);(28348,454);(28419,451);(28490,448);(28561,445);(28640,442);(28716,439);(28792,436);(28868,434);(28944,431);(29020,429);(29096,427);(29172,425);(29248,423);(29324,421);(29400,419);(29476,417);(29549,415);(29621,413);(29693,412);(29765,410);(29837,409);(29909,408);(29981,407);(30053,406);(30125,405);(30197,404);(30269,403);(30341,402);(30422,401);(30493,401);(30564,400);(30635,400);(30706,400);(30777,400);(30848,400);(30919,400);(30990,400);(31061,400);(31132,400);(31203,400);(31282,400);(31353,400);(31424,400);(31495,400);(31566,400);(31637,400);(31708,401);(31779,401);(31850,402);(31921,403);(31992,404);(32063,405);(32137,405);(32208,405);(32279,406);(32350,408);(32421,409);(32492,410);(32563,411);(32634,411);(32705,412);(32776,412);(32847,413);(32918,414);(32997,415);(33068,416);(33139,417);(33210,419);(33281,421);(33352,423);(33423,424);(33494,426);(33565,427);(33636,428);(33707,429);(33778,429);(33857,429);(33928,429);(33999,430);(34070,432);(34141,434);(34212,437);(34283,441);(34354,449);(34425,454);(34496,459);(34567,461);(34638,465);(34713,468);(34785,475);(34857,477);(34929,485);(35001,491);(35073,497);(35145,504);(35217,505);(35289,504);(777,559);(848,561);(919,563);(990,562);(1061,559);(1132,556);(1203,553)";
-    pub const TEST_HAUT_DROITE_ORIENTE_DROITE: &str = "(34565,763);(34636,775);(34707,787);(34778,799);(34849,812);(34920,826);(34991,841);(35062,856);(35133,871);(35204,886);(35275,897);(35346,898);(25189,566);(25255,572);(25321,579);(25387,586);(25453,591);(25519,593);(25585,595);(25651,595);(25717,593);(25783,587);(25849,581);(25915,576);(25990,571);(26062,566);(26134,564);(26206,560);(26278,553);(26350,551);(26422,547);(26494,543);(26566,539);(26638,535);(26710,532);(26782,529);(26853,526);(26922,523);(26991,521);(27060,519);(27129,516);(27198,514);(27267,512);(27336,509);(27405,507);(27474,505);(27543,503);(27612,501);(27691,500);(27762,498);(27833,496);(27904,495);(27975,494);(28046,492);(28117,491);(28188,490);(28259,489);(28330,487);(28401,486);(28472,485);(28550,484);(28622,484);(28694,483);(28766,483);(28838,483);(28910,482);(28982,482);(29054,482);(29126,481);(29198,481);(29270,481);(29342,481);(29424,481);(29500,481);(29576,481);(29652,482);(29728,482);(29804,483);(29880,483);(29956,484);(30032,485);(30108,486);(30184,486);(30260,487);(30333,488);(30404,489);(30475,490);(30546,492);(30617,493);(30688,495);(30759,497);(30830,499);(30901,501);(30972,502);(31043,493);(31114,487);(31185,489);(31256,496);(31327,497);(31398,500);(31469,502);(31540,505);(31611,507);(31682,510);(31753,513);(31824,515);(31895,518);(31966,520);(32047,523);(32118,527);(32189,531);(32260,535);(32331,539);(32402,543);(32473,547);(32544,551);(32615,558);(32686,563);(32757,568);(32828,573);(32907,579);(32978,585);(33049,591);(33120,597);(33191,602);(33262,608);(33333,614);(33404,621);(33475,628);(33546,636);(33617,643);(33688,651);(33767,657);(33838,664);(33909,671);(33980,679);(34051,686);(34122,695);(34193,704);(34264,715);(34335,726);(34406,739);(34477,750);(34548,761);(34623,773);(34695,785);(34767,797);(34839,811);(34911,825);(34983,838);(35055,853);(35127,868);(35199,883);(35271,895);(35343,900);(751,1593);(822,1669);(893,1764);(964,1865);(1035,1953);(1106,2072);(1184,2071);(1257,2069);(1330,2062);(1403,2057);(1476,2052);(1549,2047);(1622,2045);(1695,2041);(1768,2037);(1841,2033);(1914,2030);(1987,2027);(2058,2025);(2129,2023);(2200,2021);(2271,2019);(2342,2018);(2413,2018);(2484,2018);(2555,2018);(2626,2018);(2697,2018);(2768,2019);(2839,2020);(2917,2021);(2987,2022);(3057,2024);(3127,2026);(3197,2028);(3267,2032);(3337,2035);(3407,2039);(3477,2046);(3547,2051);(3617,2056);(3687,2062);(3758,2068);(3829,2074);(3900,2081);(3971,2087);(4042,2094);(4113,2101);(4184,2109);(4255,2117);(4326,2126);(4397,2136);(4468,2146);(4539,2155);(4617,2164);(4683,2175);(4749,2187);(4815,2199);(4881,2210);(4947,2218);(5013,2224);(5079,236);(5145,235);(5211,2250);(5277,2280);(5343,2301);(5419,2318);(5495,2334);(5571,2346);(5647,2356);(5723,2374);(5799,2314);(5875,2268);(5951,2226);(6027,2189);(6103,2174);(6179,974);(6255,975);(6325,977);(6396,975);(6467,974);(6538,975);(6609,977);(6680,979);(6751,981);(6822,982);(6893,984);(6964,994);(7035,1830);(7106,1811);(7184,1791);(7256,1776);(7328,1763);(7400,1749);(7472,1726);(7544,1705);(7616,1687);(7688,1668);(7760,1651);(7832,1636);(7904,1621);(7976,1607);(8047,1596);(8118,1588);(8189,1577);(8260,1560);(8331,1545);(8402,1531);(8473,829);(8544,812);(8615,797);(8686,787);(8757,780);(8828,775);(8902,774);(8966,773);(9030,775);(9094,787);(9158,800);(9222,811);(9286,818);(9350,825);(9414,1391);(9478,1397);(9542,1396);(9606,1389);(9690,1384);(9761,1378);(9832,1373);(9903,1367);(9974,1361);(10045,1355);(10116,1350);(10187,1348);(10258,1344);(10329,1341);(10400,1338);(10471,1335);(10553,1332);(10630,1329);(10707,1327);(10784,1325);(10861,1323);(10938,1321);(11015,1320);(11092,1320);(11169,1320);(11246,1321);(11323,1321);(11400,1321);(11473,1321);(11546,1322);(11619,1323);(11692,1325);(11765,1327);(11838,1329);(11911,1331);(11984,1333);(12057,1336);(12130,1338);(12203,1341);(12276,1343);(12350,1345);(12421,1348);(12492,1352);(12563,1355);(12634,1359);(12705,1366);(12776,1368);(12847,1375);(12918,1381);(12989,1387);(13060,1393);(13131,1398);(13205,1404);(13277,1411);(13349,1416);(13421,1421);(13493,1427);(13565,1378);(13637,1311);(13709,1263);(13781,1228);(13853,1189);(13925,1147);(13997,1116);(14076,1084);(14147,1056);(14218,1030);(14289,997);(14360,972);(14431,946);(14502,922);(14573,898);(14644,878);(14715,859);(14786,844);(14857,829);(14938,814);(15007,799);(15076,786);(15145,772);(15214,758);(15283,746);(15352,736);(15421,728);(15490,722);(15559,720);(15628,711);(15697,705);(15780,696);(15851,686);(15922,676);(15993,666);(16064,657);(16135,648);(16206,639);(16277,631);(16348,623);(16419,615);(16490,608);(16561,600);(16638,593);(16713,585);(16788,578);(16863,571);(16938,563);(17013,555);(17088,547);(17163,539);(17238,531);(18763,137);(18834,451);(18905,449);(18976,448);(19047,450);(19118,451);(19189,451);(19263,450);(19334,449);(19405,448);(19476,447);(19547,446);(19618,445);(19689,444);(19760,443);(19831,442);(19902,441);(19973,439);(20044,438);(20115,437);(20186,436);(20257,435);(20328,434);(20399,434);(20470,433);(20541,433);(20612,432);(20683,432);(20754,432);(20825,432);(20896,432);(20970,432);(21037,432);(21104,432);(21171,432);(21238,432);(21305,433);(21372,433);(21439,434);(21506,435);(21573,436);(21640,437);(21707,438);(21783,439);(21854,439);(21925,440);(21996,440);(22067,440);(22138,440);(22209,441);(22280,442);(22351,443);(22422,444);(22493,445);(22564,446);(22638,447);(22713,449);(22788,450);(22863,452);(22938,453);(23013,455);(23088,457);(23163,458);(23238,460);(23313,462);(23388,464);(23463,466);(23544,468);(23610,470);(23676,472);(23742,475);(23808,477);(23874,480);(23940,483);(24006,486);(24072,493);(24138,495);(24204,502);(24270,507);(24350,509);(24421,516);(24492,518);(24563,522);(24634,526);(24705,533);(24776,538);(24847,543);(24918,548);(24989,553);(25060,559);(25131,565);(25209,571);(25279,577);(25349,583);(25419,589);(25489,594);(25559,595);(25629,596);(25699,595);(25769,589);(25839,583);(25909,577);(25979,572);(26061,567);(26132,565);(26203,561);(26274,557);(26345,553);(26416,549);(26487,545);(26558,541);(26629,538);(26700,534);(26771,531);(26842,527);(26920,525);(26989,522);(27058,520);(27127,517);(27196,515);(27265,513);(27334,510);(27403,508);(27472,506);(27541,504);(27610,502);(27679,500);(27758,498);(27829,496);(27900,495);(27971,493);(28042,492);(28113,491);(28184,489);(28255,488);(28326,487);(28397,486);(28468,486);(28539,485);(28617,484);(28688,483);(28759,483);(28830,482);(28901,482);(28972,482);(29043,482);(29114,482);(29185,482);(29256,481);(29327,481);(29398,481);(29480,481);(29552,481);(29624,482);(29696,482);(29768,483);(29840,483);(29912,484);(29984,485);(30056,485);(30128,486);(30200,487);(30272,488);(30347,489);(30418,490);(30489,491);(30560,492);(30631,494);(30702,496);(30773,498);(30844,501);(30915,504);(30986,504);(31057,488);(31128,489);(31202,493);(31278,496);(31354,499);(31430,501);(31506,504);(31582,506);(31658,509);(31734,512);(31810,515);(31886,518);(31962,520);(32038,522);(32115,525);(32186,529);(32257,533);(32328,537);(32399,541);(32470,545);(32541,552);(32612,557);(32683,562);(32754,567);(32825,572);(32896,577);(32974,582);(33045,588);(33116,594);(33187,600);(33258,607);(33329,613);(33400,620);(33471,627);(33542,634);(33613,641);(33684,648);(33755,655);(33833,661);(33904,668);(33975,675);(34046,683);(34117,692);(34188,700);(34259,710);(34330,721);(34401,733);(34472,745);(34543,756);(34614,768)";
-    pub const TEST_CENTRE_DROIT_ORIENTE_DROITE: &str = "(25550,750);(25621,743);(25692,736);(25763,730);(25834,724);(25905,718);(25976,712);(26047,707);(26118,702);(26189,697);(26260,692);(26331,687);(26409,685);(26480,678);(26551,676);(26622,672);(26693,668);(26764,664);(26835,661);(26906,657);(26977,653);(27048,649);(27119,646);(27190,643);(27279,639);(27350,636);(27421,633);(27492,630);(27563,627);(27634,624);(27705,621);(27776,619);(27847,617);(27918,615);(27989,613);(28060,611);(28138,610);(28209,608);(28280,606);(28351,604);(28422,602);(28493,600);(28564,599);(28635,597);(28706,596);(28777,595);(28848,593);(28919,592);(28994,591);(29065,590);(29136,590);(29207,589);(29278,588);(29349,587);(29420,586);(29491,585);(29562,583);(29633,581);(29704,581);(29775,581);(29853,581);(29922,582);(29991,583);(30060,584);(30129,584);(30198,585);(30267,586);(30336,587);(30405,589);(30474,591);(30543,593);(30612,595);(30691,596);(30762,598);(30833,600);(30904,602);(30975,605);(31046,607);(31117,609);(31188,612);(31259,614);(31330,617);(31401,620);(31472,623);(31550,625);(31622,629);(31694,633);(31766,636);(31838,640);(31910,644);(31982,648);(32054,652);(32126,656);(32198,660);(32270,667);(32342,669);(32423,676);(32494,681);(32565,687);(32636,693);(32707,698);(32778,703);(32849,705);(32920,709);(32991,712);(33062,719);(33133,724);(33204,732);(33283,740);(33354,748);(33425,756);(33496,765);(33567,773);(33638,782);(33709,790);(33780,800);(33851,810);(33922,821);(33993,832);(34064,843);(34142,855);(34218,868);(34294,881);(34370,895);(34446,909);(34522,925);(34598,941);(34674,959);(34750,977);(34826,998);(34902,1016);(34978,1037);(35058,1057);(35129,1079);(35200,1101);(35271,1122);(35342,1150);(790,1284);(861,1282);(932,1278);(1003,1275);(1074,1271);(1145,1267);(1216,1263);(1287,1259);(1358,1256);(1429,1254);(1500,1251);(1581,1249);(1655,1247);(1729,1244);(1803,1242);(1877,1240);(1951,1238);(2025,1237);(2099,1236);(2173,1235);(2247,1234);(2321,1233);(2395,1232);(2473,1232);(2544,1232);(2615,1232);(2686,1232);(2757,1232);(2828,1233);(2899,1234);(2970,1235);(3041,1236);(3112,1238);(3183,1239);(3254,1241);(3332,1243);(3403,1245);(3474,1247);(3545,1250);(3616,1253);(3687,1256);(3758,1260);(3829,1264);(3900,1268);(3971,1272);(4042,1276);(4113,1283);(4191,1288);(4256,1293);(4321,1298);(4386,1304);(4451,1310);(4516,1316);(4581,1323);(4646,1331);(4711,288);(4776,1332);(4841,1346);(4906,1358);(4986,1367);(5058,1375);(5130,1384);(5202,1394);(5274,1402);(5346,1412);(5418,1422);(5490,1431);(5562,1441);(5634,1451);(5706,1463);(5778,1474);(5852,1486);(5922,1497);(5992,1509);(6062,1521);(6132,1535);(6202,1549);(6272,606);(6342,588);(6412,583);(6482,582);(6552,582);(6622,582);(6705,583);(6776,584);(6847,586);(6918,588);(6989,589);(7060,590);(7131,591);(7202,591);(7273,591);(7344,594);(7415,603);(7486,662);(7560,1503);(7636,1511);(7712,1065);(7788,1063);(7864,1059);(7940,1053);(8016,1051);(8092,1047);(8168,1045);(8244,1047);(8320,1063);(8396,1082);(8473,1102);(8541,1376);(8609,1371);(8677,1363);(8745,1354);(8813,1345);(8881,1338);(8949,1331);(9017,1324);(9085,1317);(9153,1311);(9221,1305);(9275,1300);(9346,1295);(9417,1290);(9488,1288);(9559,1284);(9630,1281);(9701,1279);(9772,1277);(9843,1273);(9914,1266);(9985,1260);(10056,1255);(10134,1250);(10210,1248);(10286,1244);(10362,1240);(10438,1236);(10514,1232);(10590,1228);(10666,1225);(10742,1223);(10818,1221);(10894,1221);(10970,1221);(11047,1221);(11118,1221);(11189,1222);(11260,1222);(11331,1221);(11402,1219);(11473,1218);(11544,1217);(11615,1216);(11686,1216);(11757,1216);(11828,1216);(11906,1217);(11977,1217);(12048,1219);(12119,1222);(12190,1226);(12261,1230);(12332,1233);(12403,1237);(12474,1240);(12545,1242);(12616,1245);(12687,1247);(12762,1251);(12833,1254);(12904,1258);(12975,1261);(13046,1265);(13117,1269);(13188,1273);(13259,1277);(13330,1281);(13401,1285);(13472,1292);(13543,1294);(13620,1301);(13692,1307);(13764,1314);(13836,1321);(13908,1328);(13980,1337);(14052,1344);(14124,1351);(14196,1359);(14268,1370);(14340,1382);(14412,1392);(14494,1401);(14569,1412);(14644,1423);(14719,1436);(14794,1447);(14869,1459);(14944,1473);(15019,1486);(15094,1501);(15169,1515);(15244,1529);(15319,1545);(15400,1561);(15471,1579);(15542,1600);(15613,1618);(15684,1634);(15755,1653);(15826,1674);(15897,1698);(15968,1720);(16039,1745);(16110,1776);(16181,1760);(16259,1741);(16330,1717);(16401,1693);(16472,1670);(16543,1650);(16614,1634);(16685,1618);(16756,1601);(16827,1582);(16898,1564);(16969,1548);(17040,1532);(17115,1517);(17186,1505);(17257,1492);(18773,1307);(18845,1302);(18917,1297);(18989,1291);(19061,1286);(19133,1280);(19205,1274);(19277,1269);(19349,1267);(19421,1260);(19493,1258);(19565,1254);(19638,1250);(19714,1246);(19790,1242);(19866,1239);(19942,1236);(20018,1233);(20094,1230);(20170,1228);(20246,1226);(20322,1224);(20398,1222);(20474,1221);(20548,1220);(20620,1219);(20692,1218);(20764,1218);(20836,1218);(20908,1218);(20980,1218);(21052,1218);(21124,1218);(21196,1219);(21268,1220);(21340,1220);(21418,1221);(21490,1222);(21562,1223);(21634,1224);(21706,1225);(21778,1226);(21850,1228);(21922,1229);(21994,1231);(22066,1233);(22138,1235);(22210,1237);(22291,1240);(22359,1243);(22427,1246);(22495,1250);(22563,1254);(22631,1258);(22699,1262);(22767,1266);(22835,1270);(22903,1273);(22971,1275);(23039,1276);(23119,1276);(23190,1247);(23261,1220);(23332,1188);(23403,1164);(23474,1140);(23545,1120);(23616,1098);(23687,1079);(23758,1061);(23829,1042);(23900,1026);(23977,1011);(24042,995);(24107,979);(24172,964);(24237,950);(24302,936);(24367,922);(24432,909);(24497,893);(24562,874);(24627,860);(24692,848);(24773,839);(24844,830);(24915,820);(24986,809);(25057,800);(25128,792);(25199,784);(25270,776);(25341,768);(25412,761);(25483,754);(25554,747);(25635,740);(25711,733);(25787,727);(25863,721);(25939,715);(26015,709);(26091,704);(26167,699);(26243,697);(26319,690);(26395,688);(26471,684);(26544,677);(26610,675);(26676,671);(26742,667);(26808,663);(26874,659);(26940,655);(27006,651);(27072,647);(27138,644);(27204,641);(27270,638);(27350,634);(27421,631);(27492,628);(27563,625);(27634,622);(27705,619);(27776,617);(27847,615);(27918,613);(27989,611);(28060,610);(28131,608);(28205,606);(28276,604);(28347,603);(28418,601);(28489,599);(28560,597);(28631,596);(28702,594);(28773,593);(28844,592);(28915,591);(28986,590);(29065,589);(29136,588);(29207,588);(29278,587);(29349,586);(29420,584);(29491,583);(29562,581);(29633,580);(29704,580);(29775,580);(29846,580);(29920,581);(29991,582);(30062,584);(30133,585);(30204,586);(30275,587);(30346,588);(30417,589);(30488,591);(30559,593);(30630,595);(30701,597);(30780,599);(30851,601);(30922,603);(30993,606);(31064,608);(31135,610);(31206,613);(31277,616);(31348,618);(31419,621);(31490,624);(31561,627);(31638,630);(31714,634);(31790,637);(31866,641);(31942,645);(32018,649);(32094,653);(32170,660);(32246,665);(32322,667);(32398,674);(32474,679);(32544,684);(32615,690);(32686,695);(32757,700);(32828,705);(32899,707);(32970,711);(33041,715);(33112,722);(33183,728);(33254,735);(33325,743);(33407,751);(33478,759);(33549,768);(33620,777);(33691,786);(33762,795);(33833,805);(33904,816);(33975,827);(34046,837);(34117,850);(34188,862);(34265,875);(34335,888);(34405,902);(34475,917);(34545,933);(34615,949);(34685,966);(34755,984);(34825,1001);(34895,1021);(34965,1042);(35035,1064)";
-    pub const TEST_CENTRE_GAUCHE_ORIENTE_GAUCHE: &str = "(35137,1301);(35208,1304);(35279,1311);(25925,639);(25996,641);(26067,644);(26138,647);(26209,650);(26280,653);(26351,656);(26422,659);(26493,663);(26564,666);(26635,670);(26706,674);(26782,681);(26853,682);(26924,686);(26995,690);(27066,697);(27137,702);(27208,707);(27279,712);(27350,717);(27421,722);(27492,728);(27563,733);(27636,738);(27707,744);(27778,750);(27849,757);(27920,763);(27991,770);(28062,777);(28133,785);(28204,793);(28275,800);(28346,808);(28417,818);(28493,827);(28569,838);(28645,849);(28721,859);(28797,870);(28873,882);(28949,895);(29025,910);(29101,925);(29177,940);(29253,955);(29329,972);(29400,990);(29471,1009);(29542,1027);(29613,1045);(29684,1063);(29755,1082);(29826,1105);(29897,1129);(29968,1153);(30039,1177);(30110,1202);(30181,1227);(30257,1256);(30328,1286);(30399,1318);(30470,1355);(30541,1415);(30612,1408);(30683,1399);(30754,1393);(30825,1385);(30896,1373);(30967,1363);(31038,1354);(31118,1346);(31189,1339);(31260,1331);(31331,1323);(31402,1318);(31473,1313);(31544,1307);(31615,1301);(31686,1296);(31757,1294);(31828,1287);(31899,1282);(31975,1280);(32048,1276);(32121,1272);(32194,1269);(32267,1265);(32340,1261);(32413,1258);(32486,1255);(32559,1253);(32632,1250);(32705,1248);(32778,1246);(32850,1244);(32921,1242);(32992,1241);(33063,1239);(33134,1239);(33205,1238);(33276,1238);(33347,1238);(33418,1239);(33489,1239);(33560,1240);(33631,1241);(33707,1242);(33778,1244);(33849,1246);(33920,1249);(33991,1253);(34062,1257);(34133,1261);(34204,1265);(34275,1268);(34346,1271);(34417,1273);(34488,1275);(34564,1277);(34634,1279);(34704,1281);(34774,1284);(34844,1287);(34914,1290);(34984,1293);(35054,1296);(35124,1299);(35194,1302);(35264,1306);(35334,1310);(758,1466);(829,1477);(900,1488);(971,1498);(1042,1510);(1119,1522);(1190,1535);(1261,1550);(1332,1567);(1403,1584);(1474,1600);(1545,1615);(1616,1626);(1687,1632);(1758,1633);(1829,1625);(1900,1607);(1972,1589);(2043,1571);(2114,1555);(2185,1539);(2256,1523);(2327,1509);(2398,1496);(2469,1483);(2540,1471);(2611,1459);(2682,1447);(2753,1437);(2833,1427);(2905,1418);(2977,1409);(3049,1400);(3121,1390);(3193,1378);(3265,1364);(3337,1350);(3409,1339);(3481,1332);(3553,1326);(3625,1320);(3704,1313);(3775,1307);(3846,1301);(3917,1295);(3988,1290);(4059,1285);(4130,1280);(4201,1278);(4272,1274);(4343,1270);(4414,1266);(4485,1262);(4562,1258);(4633,1255);(4704,1252);(4775,1250);(4846,1247);(4917,1244);(4988,1241);(5059,1238);(5130,1235);(5201,1233);(5272,1230);(5343,1227);(5419,1225);(5490,1223);(5561,1221);(5632,1219);(5703,1217);(5774,1215);(5845,1214);(5916,1213);(5987,1212);(6058,1211);(6129,1209);(6200,1207);(6261,1205);(6332,1204);(6403,1204);(6474,1204);(6545,1206);(6616,1208);(6687,1210);(6758,1212);(6829,1214);(6900,1216);(6971,1218);(7042,1221);(7119,1223);(7185,1226);(7251,1230);(7317,1233);(7383,1237);(7449,1241);(7515,1248);(7581,1250);(7647,1257);(7713,1259);(7779,1263);(7845,1266);(7922,1270);(7993,1273);(8064,1277);(8135,1284);(8206,1291);(8277,1298);(8348,1305);(8419,1313);(8490,1320);(8561,1329);(8632,1337);(8703,1346);(8780,1356);(8855,1367);(8930,1378);(9005,1387);(9080,1397);(9155,1408);(9230,1419);(9305,1432);(9380,1443);(9455,1456);(9530,1469);(9605,1483);(9686,1498);(9757,1513);(9828,1528);(9899,1543);(10041,216);(10183,1614);(10254,1633);(10325,1650);(10396,1668);(10467,1692);(10541,1716);(10619,1727);(10697,1720);(10775,1699);(10853,1680);(10931,1662);(11009,1646);(11087,1629);(11165,1612);(11243,1594);(11321,1579);(11399,1563);(11472,1548);(11543,1533);(11614,1518);(11685,1504);(11756,1492);(11827,1480);(11898,1469);(11969,1459);(12040,1449);(12111,1438);(12182,1428);(12253,1419);(12332,1409);(12403,1401);(12474,1001);(12545,996);(12616,994);(12687,988);(12758,983);(12829,977);(12900,975);(12971,971);(13042,970);(13113,972);(13190,993);(13263,1016);(13336,1043);(13409,1073);(13482,1295);(13555,1289);(13628,1284);(13701,1282);(13774,1278);(13847,1274);(13920,1270);(13993,1267);(14068,1263);(14138,1260);(14208,1258);(14278,1255);(14348,1252);(14418,1250);(14488,1248);(14558,1246);(14628,594);(14698,568);(14768,561);(14838,556);(14919,555);(14990,552);(15061,551);(15132,552);(15203,554);(15274,557);(15345,560);(15416,563);(15487,564);(15558,565);(15629,565);(15700,565);(15779,565);(15850,562);(15921,1242);(15992,1247);(16063,1249);(16134,1252);(16205,1254);(16276,1256);(16347,1259);(16418,1261);(16489,1264);(16560,1268);(16637,1272);(16706,1276);(16775,1280);(16844,1287);(16913,1289);(16982,1296);(17051,1301);(17120,1306);(17189,1312);(18780,981);(18851,959);(18922,948);(18993,936);(19064,922);(19135,909);(19208,897);(19277,885);(19346,874);(19415,863);(19484,852);(19553,842);(19622,833);(19691,823);(19760,814);(19829,805);(19898,797);(19967,789);(20047,781);(20118,774);(20189,767);(20260,761);(20331,754);(20402,747);(20473,741);(20544,735);(20615,733);(20686,729);(20757,725);(20828,722);(20905,718);(20976,711);(21047,706);(21118,700);(21189,694);(21260,689);(21331,684);(21402,682);(21473,678);(21544,674);(21615,670);(21686,663);(21758,661);(21829,657);(21900,653);(21971,650);(22042,646);(22113,642);(22184,638);(22255,634);(22326,631);(22397,628);(22468,624);(22539,621);(22619,619);(22692,616);(22765,614);(22838,612);(22911,611);(22984,609);(23057,609);(23130,609);(23203,610);(23276,611);(23349,612);(23422,613);(23494,612);(23563,611);(23632,610);(23701,608);(23770,607);(23839,606);(23908,605);(23977,604);(24046,603);(24115,602);(24184,602);(24253,602);(24332,602);(24403,601);(24474,601);(24545,601);(24616,601);(24687,601);(24758,601);(24829,602);(24900,602);(24971,603);(25042,604);(25113,605);(25190,606);(25261,608);(25332,610);(25403,613);(25474,617);(25545,624);(25616,626);(25687,630);(25758,633);(25829,636);(25900,638);(25971,640);(26047,643);(26118,645);(26189,648);(26260,651);(26331,654);(26402,657);(26473,660);(26544,663);(26615,667);(26686,671);(26757,675);(26828,679);(26901,683);(26967,687);(27033,694);(27099,699);(27165,704);(27231,706);(27297,713);(27363,718);(27429,724);(27495,729);(27561,735);(27627,740);(27704,746);(27775,751);(27846,758);(27917,765);(27988,772);(28059,780);(28130,788);(28201,796);(28272,804);(28343,812);(28414,822);(28485,833);(28562,844);(28638,854);(28714,863);(28790,874);(28866,887);(28942,900);(29018,913);(29094,929);(29170,944);(29246,960);(29322,977);(29398,995);(29476,1012);(29548,1032);(29620,1051);(29692,1070);(29764,1090);(29836,1113);(29908,1136);(29980,1161);(30052,1187);(30124,1210);(30196,1240);(30268,1263);(30347,1295);(30418,1328);(30489,1376);(30560,1416);(30631,1406);(30702,1398);(30773,1389);(30844,1379);(30915,1370);(30986,1361);(31057,1350);(31128,1342);(31205,1336);(31276,1329);(31347,1322);(31418,1316);(31489,1310);(31560,1304);(31631,1299);(31702,1294);(31773,1289);(31844,1284);(31915,1282);(31986,1278);(32065,1274);(32136,1270);(32207,1267);(32278,1263);(32349,1261);(32420,1258);(32491,1255);(32562,1252);(32633,1249);(32704,1247);(32775,1245);(32846,1243);(32923,1242);(32994,1240);(33065,1238);(33136,1238);(33207,1237);(33278,1237);(33349,1237);(33420,1238);(33491,1238);(33562,1239);(33633,1241);(33704,1242);(33781,1244);(33852,1246);(33923,1249);(33994,1253);(34065,1257);(34136,1261);(34207,1264);(34278,1267);(34349,1270);(34420,1273);(34491,1275);(34562,1277);(34639,1279);(34708,1281);(34777,1284);(34846,1286);(34915,1289);(34984,1293);(35053,1296);(35122,1299);(35191,1303);(35260,1307)";
-    pub const TEST_BAS_GAUCHE_ORIENTE_GAUCHE: &str = "(35207,2263);(35278,2233);(25619,570);(25690,577);(25761,582);(25832,587);(25903,593);(25974,598);(26045,603);(26116,608);(26187,614);(26258,620);(26329,626);(26400,632);(26475,638);(26546,645);(26617,652);(26688,658);(26759,666);(26830,674);(26901,683);(26972,692);(27043,701);(27114,709);(27185,716);(27256,723);(27332,731);(27403,739);(27474,748);(27545,757);(27616,766);(27687,775);(27758,784);(27829,794);(27900,805);(27971,816);(28042,829);(28113,842);(28190,854);(28263,869);(28336,889);(28409,914);(28482,932);(28555,948);(28628,967);(28701,984);(28774,1002);(28847,1020);(28920,1039);(28993,1060);(29061,1080);(29132,1130);(29203,1164);(29274,1192);(29345,1221);(29416,1252);(29487,1286);(29558,1326);(29629,1362);(29700,1398);(29771,1434);(29842,1475);(29918,1523);(29994,1582);(30070,1650);(30146,1730);(30222,1805);(30298,1897);(30374,2009);(30450,2150);(30526,2144);(30602,2138);(30678,2132);(30754,2125);(30829,2116);(30900,2109);(30971,2104);(31042,2099);(31113,2094);(31184,2089);(31255,2084);(31326,2082);(31397,2078);(31468,2074);(31539,2070);(31610,2067);(31683,2064);(31755,2060);(31827,2056);(31899,2053);(31971,2051);(32043,2051);(32115,2051);(32187,2051);(32259,2051);(32331,2053);(32403,2057);(32475,2064);(32547,2070);(32618,2078);(32689,2084);(32760,2086);(32831,2090);(32902,2093);(32973,2094);(33044,2096);(33115,2099);(33186,2103);(33257,2107);(33328,2115);(33401,2120);(33472,2126);(33543,2128);(33614,2136);(33685,2143);(33756,2150);(33827,2156);(33898,2162);(33969,2171);(34040,2178);(34111,2186);(34182,2197);(34262,2207);(34328,2216);(34394,2225);(34460,2233);(34526,2242);(34592,2252);(34658,2262);(34724,2275);(34790,2289);(34856,2304);(34922,2316);(34988,2322);(35068,2323);(35138,2317);(35208,2266);(35278,2230);(776,1694);(847,1679);(918,1663);(989,1648);(1060,1633);(1131,1618);(1202,1604);(1273,1589);(1344,1574);(1415,1558);(1486,1543);(1557,1527);(1638,1513);(1709,1500);(1780,342);(1851,320);(1922,1464);(1993,1457);(2064,1450);(2135,1442);(2206,1434);(2277,1426);(2348,1419);(2419,1410);(2494,1399);(2570,1191);(2646,1189);(2722,715);(2798,696);(2874,694);(2950,694);(3026,695);(3102,697);(3178,699);(3254,702);(3330,705);(3404,707);(3475,708);(3546,710);(3617,713);(3688,722);(3759,764);(3830,1298);(3901,1309);(3972,1310);(4043,1311);(4114,1310);(4185,1309);(4262,1308);(4326,1308);(4390,1308);(4454,1308);(4518,1308);(4582,1308);(4646,1309);(4710,1309);(4774,1310);(4838,1311);(4902,1312);(4966,1313);(5047,1313);(5118,1314);(5189,1315);(5260,1317);(5331,1319);(5402,1321);(5473,1324);(5544,1327);(5615,1330);(5686,1333);(5757,1335);(5828,1337);(5905,1339);(5976,1341);(6047,1345);(6118,1349);(6189,1356);(6260,1362);(6331,1364);(6402,1371);(6473,1378);(6544,1335);(6615,1284);(6686,1245);(6758,1207);(6829,1166);(6900,1127);(6971,1094);(7042,1065);(7113,1037);(7184,1008);(7255,980);(7326,952);(7397,928);(7468,907);(7539,886);(7620,865);(7692,846);(7764,827);(7836,810);(7908,792);(7980,775);(8052,761);(8124,747);(8196,733);(8268,720);(8340,708);(8412,697);(8494,686);(8565,675);(8636,665);(8707,655);(8778,645);(8849,635);(8920,626);(8991,618);(9062,610);(9133,602);(9204,594);(9275,586);(9350,578);(9421,571);(9492,564);(9563,557);(9634,551);(9705,545);(9776,539);(9847,533);(9918,528);(9989,523);(10060,518);(10131,516);(10208,512);(10279,508);(10350,504);(10421,497);(10492,495);(10563,491);(10634,487);(10705,483);(10776,480);(10847,476);(10918,473);(10989,470);(11065,467);(11136,464);(11207,461);(11278,458);(11349,456);(11420,453);(11491,450);(11562,447);(11633,445);(11704,443);(11775,441);(11846,439);(11927,437);(11998,435);(12069,434);(12140,432);(12211,430);(12282,428);(12353,427);(12424,426);(12495,425);(12566,424);(12637,422);(12708,421);(12779,420);(12850,419);(12921,418);(12992,417);(13063,416);(13134,415);(13205,414);(13276,413);(13347,413);(13418,413);(13489,412);(13560,412);(13641,412);(13718,412);(13795,412);(13872,412);(13949,412);(14026,412);(14103,412);(14180,413);(14257,413);(14334,413);(14411,414);(14488,414);(14562,414);(14633,414);(14704,415);(14775,416);(14846,417);(14917,418);(14988,419);(15059,420);(15130,421);(15201,422);(15272,424);(15343,425);(15418,427);(15489,428);(15560,430);(15631,432);(15702,433);(15773,435);(15844,437);(15915,439);(15986,442);(16057,444);(16128,446);(16199,449);(16273,451);(16345,453);(16417,456);(16489,459);(16561,461);(16633,464);(16705,467);(16777,470);(16849,473);(16921,476);(16993,479);(17065,481);(17136,482);(17207,483);(18829,616);(18900,615);(18971,614);(19042,611);(19113,608);(19184,604);(19255,597);(19326,591);(19397,586);(19468,584);(19539,577);(19610,575);(19691,571);(19763,567);(19835,563);(19907,559);(19979,556);(20051,552);(20123,549);(20195,545);(20267,542);(20339,538);(20411,535);(20483,532);(20562,530);(20633,527);(20704,524);(20775,522);(20846,520);(20917,518);(20988,516);(21059,514);(21130,512);(21201,511);(21272,509);(21343,508);(21422,507);(21493,506);(21564,504);(21635,503);(21706,502);(21777,501);(21848,500);(21919,499);(21990,498);(22061,498);(22132,498);(22203,497);(22280,497);(22351,497);(22422,496);(22493,496);(22564,496);(22635,495);(22706,495);(22777,495);(22848,495);(22919,495);(22990,496);(23061,496);(23133,497);(23204,498);(23275,498);(23346,499);(23417,499);(23488,500);(23559,501);(23630,502);(23701,503);(23772,504);(23843,505);(23914,506);(23991,507);(24056,508);(24121,510);(24186,511);(24251,513);(24316,514);(24381,516);(24446,518);(24511,520);(24576,522);(24641,524);(24706,526);(24779,529);(24850,531);(24921,534);(24992,537);(25063,540);(25134,544);(25205,548);(25276,552);(25347,556);(25418,560);(25489,564);(25560,568);(25638,575);(25714,580);(25790,585);(25866,590);(25942,595);(26018,601);(26094,606);(26170,612);(26246,617);(26322,623);(26398,629);(26474,635);(26548,641);(26614,648);(26680,655);(26746,662);(26812,670);(26878,679);(26944,688);(27010,697);(27076,705);(27142,712);(27208,720);(27274,727);(27350,735);(27421,744);(27492,753);(27563,762);(27634,770);(27705,779);(27776,789);(27847,799);(27918,810);(27989,821);(28060,833);(28131,845);(28208,859);(28284,876);(28360,899);(28436,923);(28512,938);(28588,956);(28664,975);(28740,993);(28816,1011);(28892,1030);(28968,1049);(29044,1072);(29118,1100);(29189,1143);(29260,1178);(29331,1205);(29402,1237);(29473,1267);(29544,1305);(29615,1346);(29686,1375);(29757,1413);(29828,1440);(29899,1496);(29973,1547);(30045,1615);(30117,1686);(30189,1771);(30261,1843);(30333,1934);(30405,2095);(30477,2153);(30549,2144);(30621,2135);(30693,2129);(30765,2123);(30847,2115);(30918,2109);(30989,2102);(31060,2096);(31131,2090);(31202,2085);(31273,2083);(31344,2079);(31415,2075);(31486,2073);(31557,2070);(31628,2067);(31706,2064);(31777,2060);(31848,2056);(31919,2052);(31990,2050);(32061,2050);(32132,2050);(32203,2051);(32274,2053);(32345,2056);(32416,2059);(32487,2067);(32565,2075);(32636,2081);(32707,2086);(32778,2088);(32849,2091);(32920,2094);(32991,2096);(33062,2098);(33133,2102);(33204,2106);(33275,2113);(33346,2118);(33424,2120);(33495,2127);(33566,2132);(33637,2139);(33708,2146);(33779,2152);(33850,2158);(33921,2165);(33992,2172);(34063,2181);(34134,2193);(34205,2204);(34278,2213);(34347,2222);(34416,2229);(34485,2236);(34554,2245);(34623,2255);(34692,2266);(34761,2280);(34830,2296);(34899,2310);(34968,2320);(35037,2326)";
-    pub const TEST_BAS_GAUCHE_ORIENTE_DROITE: &str = "(34112,949);(34184,916);(34256,142);(34328,157);(34400,860);(34472,839);(34544,823);(34616,807);(34688,792);(34760,777);(34832,762);(34904,748);(22708,2062);(22777,2062);(22846,2061);(22915,2061);(22984,2063);(23053,2065);(23122,2072);(23191,2079);(23260,2088);(23329,2096);(23398,2105);(23467,2112);(23544,2117);(23610,2119);(23676,2123);(23742,2130);(23808,2132);(23874,2139);(23940,2145);(24006,2151);(24072,2156);(24138,2156);(24204,2148);(24270,2141);(24347,2142);(24418,2165);(24489,2188);(24560,2202);(24631,2213);(24702,2221);(24773,2227);(24844,2232);(24915,2237);(24986,2237);(25057,2236);(25128,2241);(25204,2250);(25279,2259);(25354,2305);(25429,2343);(25504,2344);(25579,2354);(25654,2363);(25729,2375);(25804,2388);(25879,2296);(25954,2194);(26029,2167);(26107,2135);(26178,2104);(26249,2073);(26320,2046);(26391,2016);(26462,1988);(26533,1921);(26604,1873);(26675,1866);(26746,1847);(26817,1827);(26888,1810);(26969,1793);(27034,1776);(27099,1757);(27164,1738);(27229,1719);(27294,1702);(27359,1685);(27424,1668);(27489,1650);(27554,1634);(27619,1618);(27684,1602);(27761,1587);(27831,1572);(27901,1557);(27971,1544);(28041,1530);(28111,1515);(28181,1500);(28251,1095);(28321,1094);(28391,1083);(28461,1071);(28531,1065);(28615,1064);(28692,1065);(28769,1073);(28846,1090);(28923,1106);(29000,1123);(29077,1144);(29154,1375);(29231,1376);(29308,1376);(29385,1370);(29462,1364);(29547,1357);(29611,1349);(29675,1342);(29739,1335);(29803,1329);(29867,1323);(29931,1321);(29995,1317);(30059,1314);(30123,1311);(30187,1308);(30251,1305);(30332,1299);(30403,1294);(30474,687);(30545,677);(30616,671);(30687,670);(30758,669);(30829,669);(30900,671);(30971,673);(31042,675);(31113,676);(31190,677);(31267,678);(31344,678);(31421,681);(31498,695);(31575,729);(31652,1288);(31729,1288);(31806,1288);(31883,1288);(31960,1290);(32037,1292);(32115,1295);(32186,1298);(32257,1302);(32328,1305);(32399,1308);(32470,1310);(32541,1312);(32612,1316);(32683,1320);(32754,1324);(32825,1328);(32896,1332);(32976,1336);(33047,1340);(33118,1344);(33189,1348);(33260,1355);(33331,1318);(33402,1264);(33473,1233);(33544,1194);(33615,1148);(33686,1111);(33757,1081);(33830,1052);(33901,1025);(33972,1000);(34043,975);(34114,941);(34185,909);(34256,145);(34327,144);(34398,853);(34469,836);(34540,820);(34611,804);(34688,789);(34760,774);(34832,760);(34904,745);(34976,732);(35048,719);(35120,708);(35192,697);(35264,685);(35336,671);(787,509);(858,510);(929,512);(1000,510);(1071,507);(1142,503);(1219,499);(1293,495);(1367,491);(1441,487);(1515,484);(1589,481);(1663,477);(1737,474);(1811,471);(1885,468);(1959,466);(2033,463);(2119,461);(2190,458);(2261,456);(2332,454);(2403,452);(2474,449);(2545,447);(2616,445);(2687,443);(2758,441);(2829,439);(2900,437);(2977,436);(3043,434);(3109,432);(3175,431);(3241,430);(3307,428);(3373,427);(3439,427);(3505,426);(3571,425);(3637,424);(3703,424);(3780,423);(3851,422);(3922,421);(3993,420);(4064,420);(4135,420);(4206,419);(4277,419);(4348,419);(4419,419);(4490,419);(4561,419);(4634,419);(4705,419);(4776,419);(4847,419);(4918,419);(4989,420);(5060,420);(5131,421);(5202,422);(5273,423);(5344,424);(5415,424);(5494,425);(5565,426);(5636,427);(5707,428);(5778,429);(5849,430);(5920,432);(5991,433);(6062,435);(6133,437);(6204,438);(6275,440);(6350,442);(6421,444);(6492,446);(6563,448);(6634,450);(6705,452);(6776,454);(6847,457);(6918,459);(6989,462);(7060,465);(7131,468);(7209,471);(7279,474);(7349,477);(7419,480);(7489,483);(7559,486);(7629,490);(7699,493);(7769,497);(7839,501);(7909,505);(7979,509);(8058,513);(8129,517);(8200,524);(8271,529);(8342,534);(8413,539);(8484,545);(8555,551);(8626,557);(8697,563);(8768,570);(8839,577);(8916,584);(8988,592);(9060,600);(9132,607);(9204,615);(9276,623);(9348,632);(9420,638);(9492,639);(9564,641);(9636,641);(9708,639);(9776,632);(9847,627);(9918,622);(9989,617);(10060,612);(10131,607);(10202,602);(10273,600);(10344,596);(10415,592);(10486,588);(10557,581);(10638,579);(10714,576);(10790,572);(10866,569);(10942,565);(11018,562);(11094,559);(11170,556);(11246,553);(11322,551);(11398,548);(11474,545);(11548,542);(11620,540);(11692,538);(11764,536);(11836,534);(11908,532);(11980,530);(12052,528);(12124,527);(12196,525);(12268,524);(12340,523);(12422,522);(12493,521);(12564,520);(12635,519);(12706,518);(12777,518);(12848,518);(12919,517);(12990,517);(13061,517);(13132,516);(13203,516);(13280,516);(13351,516);(13422,516);(13493,516);(13564,516);(13635,517);(13706,517);(13777,517);(13848,517);(13919,518);(13990,519);(14061,519);(14133,520);(14204,521);(14275,522);(14346,523);(14417,524);(14488,525);(14559,527);(14630,528);(14701,530);(14772,531);(14843,533);(14914,535);(14991,537);(15067,540);(15143,542);(15219,544);(15295,546);(15371,548);(15447,551);(15523,553);(15599,556);(15675,559);(15751,562);(15827,566);(15901,569);(15972,572);(16043,576);(16114,580);(16185,587);(16256,589);(16327,596);(16398,601);(16469,606);(16540,611);(16611,616);(16682,618);(16761,625);(16831,630);(16901,636);(16971,642);(17041,648);(17111,653);(17181,655);(17251,662);(18834,859);(18905,873);(18976,900);(19047,926);(19118,944);(19189,961);(19260,979);(19334,996);(19404,1014);(19474,1033);(19544,1051);(19614,1070);(19684,1092);(19754,1138);(19824,1174);(19894,1202);(19964,1231);(20034,1261);(20104,1296);(20183,1333);(20254,1369);(20325,1402);(20396,1448);(20467,1489);(20538,1533);(20609,1573);(20680,1681);(20751,1758);(20822,1844);(20893,1918);(20964,2010);(21041,2114);(21114,2144);(21187,2138);(21260,2133);(21333,2128);(21406,2126);(21479,2119);(21552,2117);(21625,2113);(21698,2109);(21771,2105);(21844,2102);(21926,2098);(21997,2094);(22068,2091);(22139,2087);(22210,2083);(22281,2076);(22352,2074);(22423,2070);(22494,2066);(22565,2063);(22636,2061);(22707,2061);(22722,2061);(22792,2061);(22862,2061);(22932,2062);(23002,2064);(23072,2071);(23142,2078);(23212,2088);(23282,2098);(23352,2108);(23422,2115);(23492,2117);(23562,2120);(23633,2123);(23704,2127);(23775,2130);(23846,2137);(23917,2143);(23988,2150);(24059,2156);(24130,2157);(24201,2152);(24272,2144);(24343,2142);(24415,2160);(24486,2190);(24557,2200);(24628,2210);(24699,2219);(24770,2226);(24841,2233);(24912,2238);(24983,2238);(25054,2238);(25125,2239);(25196,2244);(25273,2250);(25344,2286);(25415,2337);(25486,2347);(25557,2357);(25628,2366);(25699,2376);(25770,2388);(25841,2342);(25912,2178);(25983,2169);(26054,2148);(26133,2117);(26204,2083);(26275,2051);(26346,2020);(26417,1995);(26488,1932);(26559,1891);(26630,1871);(26701,1851);(26772,1831);(26843,1815);(26914,1796);(26998,1777);(27067,1758);(27136,1740);(27205,1722);(27274,1705);(27343,1688);(27412,1669);(27481,1653);(27550,1639);(27619,1623);(27688,1606);(27757,1591);(27826,1576);(27897,1561);(27968,1547);(28039,1533);(28110,1518);(28181,1504);(28252,1091);(28323,1092);(28394,1086);(28465,1073);(28536,1066);(28607,1065);(28684,1065);(28757,1072);(28830,1086);(28903,1103);(28976,1119);(29049,1137);(29122,1164);(29195,1383);(29268,1377);(29341,1371);(29414,1365);(29487,1358);(29569,1350);(29639,1343);(29709,1336);(29779,1330);(29849,1325);(29919,1320);(29989,1318);(30059,1314);(30129,1311);(30199,1309);(30269,1306);(30339,1304);(30418,1301);(30489,684);(30560,679);(30631,674);(30702,672);(30773,670);(30844,670);(30915,671);(30986,673);(31057,676);(31128,678);(31199,679);(31277,679);(31353,680);(31429,681);(31505,685);(31581,729);(31657,1275);(31733,1284);(31809,1286);(31885,1288);(31961,1289);(32037,1291);(32113,1294)";
-    pub const TEST_BAS_CENTRE_ORIENTE_CENTRE: &str = "(18840,911);(7832,427);(7903,426);(7974,425);(8045,424);(8116,424);(8187,423);(8258,423);(8329,423);(8400,422);(8471,422);(8542,422);(8613,422);(8694,423);(8765,423);(8836,423);(8907,423);(8978,423);(9049,424);(9120,424);(9191,424);(9262,424);(9333,425);(9404,425);(9475,426);(9550,427);(9621,428);(9692,429);(9763,430);(9834,431);(9905,433);(9976,435);(10047,436);(10118,438);(10189,440);(10260,441);(10331,443);(10408,445);(10480,447);(10552,449);(10624,451);(10696,453);(10768,455);(10840,457);(10912,459);(10984,461);(11056,463);(11128,466);(11200,469);(11276,472);(11347,474);(11418,477);(11489,481);(11560,484);(11631,487);(11702,491);(11773,494);(11844,498);(11915,502);(11986,506);(12057,510);(12134,514);(12210,518);(12286,525);(12362,530);(12438,535);(12514,540);(12590,546);(12666,552);(12742,559);(12818,566);(12894,572);(12970,579);(13044,586);(13115,593);(13186,600);(13257,607);(13328,615);(13399,623);(13470,631);(13541,640);(13612,650);(13683,658);(13754,668);(13825,679);(13904,690);(13975,701);(14046,712);(14117,722);(14188,734);(14259,748);(14330,763);(14401,777);(14472,792);(14543,809);(14614,825);(14685,842);(14762,860);(14835,879);(14908,899);(14981,920);(15054,943);(15127,965);(15200,971);(15273,971);(15346,970);(15419,967);(15492,961);(15565,959);(15636,955);(15707,951);(15778,947);(15849,943);(15920,939);(15991,935);(16062,932);(16133,929);(16204,926);(16275,923);(16346,920);(16417,917);(16491,915);(16568,913);(16645,911);(16722,909);(16799,907);(16876,906);(16953,905);(17030,904);(17107,903);(17184,900);(17261,892);(18840,910);(18911,912);(18982,923);(19053,930);(19130,935);(19200,937);(19270,941);(19340,948);(19410,953);(19480,958);(19550,963);(19620,968);(19690,973);(19760,979);(19830,985);(19900,991);(19972,998);(20043,1005);(20114,1013);(20185,1021);(20256,1028);(20327,1035);(20398,1042);(20469,1048);(20540,1054);(20611,1059);(20682,1065);(20753,1072);(20833,1079);(20899,1086);(20965,1094);(21031,1101);(21097,1109);(21163,1117);(21229,1125);(21295,1134);(21361,1145);(21427,1161);(21493,1180);(21559,1197);(21636,1210);(21707,1222);(21778,1234);(21849,1246);(21920,1258);(21991,1270);(22062,1282);(22133,1296);(22204,1314);(22275,1332);(22346,1368);(22417,1389);(22491,1409);(22567,1426);(22643,1447);(22719,1468);(22795,1489);(22871,1512);(22947,1537);(23023,1569);(23099,1599);(23175,1625);(23251,1651);(23327,1686);(23404,1716);(23475,1758);(23546,1791);(23617,1827);(23688,1863);(23759,1898);(23830,1950);(23901,1998);(23972,2047);(24043,2095);(24114,2147);(24185,2210);(24254,2273);(24325,2623);(24396,2602);(24467,2585);(24538,2232);(24609,2224);(24680,2214);(24751,2204);(24822,2191);(24893,2177);(24964,2167);(25035,2166);(25115,2164);(25179,2153);(25243,2139);(25307,2127);(25371,2116);(25435,2105);(25499,2096);(25563,2090);(25627,2088);(25691,2084);(25755,2081);(25819,2079);(25900,2076);(25971,2072);(26042,2068);(26113,2066);(26184,2065);(26255,2067);(26326,2071);(26397,2075);(26468,2077);(26539,2078);(26610,2078);(26681,2078);(26758,2079);(26829,2081);(26900,2082);(26971,2084);(27042,2085);(27113,2086);(27184,2085);(27255,2083);(27326,2366);(27397,2365);(27468,2028);(27539,2072);(27615,2085);(27686,2092);(27757,2094);(27828,2096);(27899,2097);(27970,2098);(28041,2097);(28112,2096);(28183,2093);(28254,2091);(28325,2090);(28396,2098);(28476,2114);(28550,2114);(28624,2216);(28698,2165);(28772,2172);(28846,2181);(28920,2189);(28994,2190);(29068,2192);(29142,2152);(29216,1975);(29290,1925);(29361,1882);(29432,1831);(29503,1795);(29574,1764);(29645,1729);(29716,1670);(29787,1646);(29858,1623);(29929,1593);(30000,1565);(30071,872);(30142,870);(30219,867);(30290,866);(30361,867);(30432,868);(30503,867);(30574,866);(30645,865);(30716,865);(30787,865);(30858,865);(30929,1292);(31000,1271);(31076,1257);(31146,1243);(31216,1228);(31286,1215);(31356,1196);(31426,1179);(31496,1164);(31566,1149);(31636,1138);(31706,1129);(31776,1118);(31846,1109);(31922,1099);(31993,1091);(32064,1083);(32135,1075);(32206,1067);(32277,1058);(32348,1051);(32419,1042);(32490,1032);(32561,759);(32632,749);(32703,737);(32773,726);(32844,720);(32915,718);(32986,715);(33057,714);(33128,715);(33199,723);(33270,737);(33341,751);(33412,762);(33483,773);(33554,787);(33634,936);(33705,934);(33776,930);(33847,927);(33918,923);(33989,919);(34060,916);(34131,914);(34202,912);(34273,911);(34344,909);(34415,908);(34492,907);(34561,905);(34630,904);(34699,902);(34768,901);(34837,900);(34906,898);(34975,897);(35044,897);(35113,896);(35182,896);(35251,891);(35333,881);(756,911);(827,922);(898,929);(969,931);(1044,934);(1117,937);(1190,941);(1263,945);(1336,949);(1409,953);(1482,957);(1555,964);(1628,966);(1701,973);(1774,975);(1847,982);(1918,987);(1989,988);(2060,991);(2131,992);(2202,974);(2273,951);(2344,928);(2415,905);(2486,886);(2557,867);(2628,848);(2699,830);(2776,813);(2846,796);(2916,782);(2986,767);(3056,753);(3126,738);(3196,725);(3266,713);(3336,702);(3406,691);(3476,681);(3546,671);(3622,663);(3693,655);(3764,646);(3835,636);(3906,627);(3977,619);(4048,611);(4119,604);(4190,596);(4261,588);(4332,581);(4403,574);(4480,568);(4546,561);(4612,555);(4678,549);(4744,544);(4810,538);(4876,533);(4942,528);(5008,523);(5074,521);(5140,514);(5206,512);(5283,508);(5354,504);(5425,500);(5496,496);(5567,492);(5638,488);(5709,485);(5780,482);(5851,479);(5922,476);(5993,473);(6064,470);(6138,467);(6213,464);(6288,461);(6363,459);(6438,457);(6513,455);(6588,453);(6663,451);(6738,449);(6813,447);(6888,445);(6963,443);(7044,441);(7115,439);(7186,437);(7257,435);(7328,434);(7399,432);(7470,431);(7541,430);(7612,429);(7683,428);(7754,427);(7825,427);(7897,426);(7968,425);(8039,424);(8110,424);(8181,423);(8252,423);(8323,423);(8394,423);(8465,423);(8536,423);(8607,423);(8678,423);(8755,423);(8822,423);(8889,423);(8956,423);(9023,423);(9090,423);(9157,424);(9224,425);(9291,425);(9358,426);(9425,427);(9492,428);(9565,429);(9636,429);(9707,430);(9778,431);(9849,432);(9920,434);(9991,435);(10062,437);(10133,439);(10204,441);(10275,443);(10346,445);(10423,447);(10498,448);(10573,450);(10648,452);(10723,454);(10798,456);(10873,459);(10948,461);(11023,463);(11098,466);(11173,469);(11248,472);(11329,475);(11400,478);(11471,481);(11542,484);(11613,487);(11684,490);(11755,494);(11826,498);(11897,501);(11968,505);(12039,509);(12110,513);(12257,517);(12328,524);(12399,529);(12470,534);(12541,540);(12612,546);(12683,551);(12754,557);(12825,563);(12896,569);(12967,576);(13038,583);(13116,591);(13182,599);(13248,606);(13314,614);(13380,621);(13446,629);(13512,638);(13578,646);(13644,656);(13710,667);(13776,678);(13842,687);(13922,696);(13993,708);(14064,720);(14135,733);(14206,746);(14277,759);(14348,773);(14419,787);(14490,803);(14561,818);(14632,834);(14703,853);(14780,872);(14856,888);(14932,909);(15008,932);(15084,953);(15160,966);(15236,968);(15312,969);(15388,967);(15464,961);(15540,959);(15616,955);(15690,951);(15761,947);(15832,943);(15903,939);(15974,935);(16045,932);(16116,929);(16187,926);(16258,924);(16329,921);(16400,919);(16471,917);(16545,915);(16616,913);(16687,911);(16758,910);(16829,909);(16900,907);(16971,906);(17042,905);(17113,904);(17184,898);(17255,888)";
-    pub const TEST_BAS_DROITE_ORIENTE_GAUCHE: &str = "(8419,662);(8495,657);(8571,650);(8647,642);(8723,633);(8799,625);(8875,617);(8951,610);(9027,603);(9103,596);(9179,588);(9255,581);(9329,575);(9400,569);(9471,563);(9542,557);(9613,552);(9684,547);(9755,542);(9826,537);(9897,532);(9968,527);(10039,522);(10110,520);(10187,516);(10258,512);(10329,509);(10400,505);(10471,502);(10542,498);(10613,494);(10684,491);(10755,488);(10826,485);(10897,483);(10968,480);(11047,478);(11118,475);(11189,473);(11260,471);(11331,469);(11402,467);(11473,465);(11544,463);(11615,461);(11686,459);(11757,457);(11828,455);(11905,453);(11976,451);(12047,450);(12118,449);(12189,447);(12260,446);(12331,445);(12402,444);(12473,443);(12544,442);(12615,441);(12686,440);(12761,439);(12832,438);(12903,438);(12974,438);(13045,437);(13116,437);(13187,437);(13258,436);(13329,436);(13400,436);(13471,436);(13542,436);(13620,436);(13691,436);(13762,436);(13833,437);(13904,438);(13975,438);(14046,439);(14117,439);(14188,440);(14259,440);(14330,441);(14401,442);(14476,443);(14548,444);(14620,446);(14692,447);(14764,448);(14836,449);(14908,451);(14980,453);(15052,455);(15124,457);(15196,459);(15268,460);(15350,462);(15421,464);(15492,466);(15563,468);(15634,470);(15705,472);(15776,474);(15847,477);(15918,480);(15989,483);(16060,486);(16131,488);(16205,491);(16276,494);(16347,498);(16418,501);(16489,504);(16560,508);(16631,512);(16702,516);(16773,520);(16844,524);(16915,528);(16986,532);(17061,536);(17132,539);(17203,540);(18776,718);(18848,733);(18920,752);(18992,771);(19064,785);(19136,802);(19208,819);(19280,835);(19352,851);(19424,871);(19496,888);(19568,909);(19641,930);(19716,952);(19791,977);(19866,1002);(19941,1028);(20016,1056);(20091,1084);(20166,1114);(20241,1148);(20316,1185);(20391,1224);(20466,1260);(20540,1301);(20607,1350);(20674,1350);(20741,1349);(20808,1343);(20875,1341);(20942,1333);(21009,1331);(21076,1327);(21143,1323);(21210,1320);(21277,1318);(21350,1316);(21421,1314);(21492,1312);(21563,1310);(21634,1308);(21705,1307);(21776,1306);(21847,1305);(21918,1305);(21989,1305);(22060,1305);(22131,1304);(22208,1304);(22277,1305);(22346,1305);(22415,1306);(22484,1307);(22553,1308);(22622,1309);(22691,1310);(22760,1311);(22829,1313);(22898,1315);(22967,1317);(23047,1319);(23118,1321);(23189,1323);(23260,1325);(23331,1329);(23402,1332);(23473,1336);(23544,1340);(23615,1344);(23686,1348);(23757,1356);(23828,1361);(23905,1368);(23971,1375);(24037,1384);(24103,1393);(24169,1400);(24235,1406);(24301,1411);(24367,1417);(24433,1423);(24499,1429);(24565,1435);(24631,1441);(24708,1447);(24779,1454);(24850,1462);(24921,1469);(24992,1477);(25063,1486);(25134,1500);(25205,1515);(25276,1527);(25347,1537);(25418,1546);(25489,1555);(25563,1566);(25639,1578);(25715,1594);(25791,1605);(25867,1620);(25943,1640);(26019,1656);(26095,1672);(26171,1688);(26247,1705);(26323,1721);(26399,1737);(26476,1755);(26547,1777);(26618,1795);(26689,1812);(26760,1829);(26831,1851);(26902,1872);(26973,1895);(27044,1920);(27115,1946);(27186,1976);(27257,2008);(27329,2044);(27400,2074);(27471,2114);(27542,2153);(27613,2192);(27684,2234);(27755,2270);(27826,2312);(27897,2351);(27968,2402);(28039,2455);(28110,2447);(28187,2434);(28258,2415);(28329,2396);(28400,2377);(28471,2360);(28542,2341);(28613,2323);(28684,2308);(28755,2291);(28826,2274);(28897,2257);(28968,2242);(29043,2228);(29114,2215);(29185,2202);(29256,2189);(29327,2179);(29398,2169);(29469,2160);(29540,2153);(29611,1077);(29682,1077);(29753,1077);(29824,1076);(29898,1073);(29969,1071);(30040,1069);(30111,1074);(30182,1088);(30253,1114);(30324,1146);(30395,2055);(30466,2049);(30537,2043);(30608,2038);(30679,2032);(30761,2027);(30832,2022);(30903,2017);(30974,2012);(31045,2010);(31116,2006);(31187,2003);(31258,2001);(31329,2000);(31400,2000);(31471,1999);(31542,1999);(31620,738);(31692,716);(31764,709);(31836,707);(31908,703);(31980,700);(32052,701);(32124,705);(32196,713);(32268,719);(32340,725);(32412,726);(32494,729);(32564,730);(32634,2000);(32704,1978);(32774,1858);(32844,1777);(32914,1713);(32984,1649);(33054,1601);(33124,1498);(33194,1464);(33264,1422);(33345,1374);(33416,1332);(33487,1303);(33558,1274);(33629,1239);(33700,1211);(33771,1176);(33842,1149);(33913,1122);(33984,1091);(34055,1065);(34126,1041);(34207,1017);(34278,993);(34349,968);(34420,937);(34491,914);(34562,896);(34633,880);(34704,866);(34775,852);(34846,838);(34917,819);(34988,237);(35061,238);(35132,780);(35203,769);(35274,758);(35345,738);(754,589);(826,589);(898,588);(970,586);(1042,583);(1114,579);(1186,575);(1258,571);(1330,567);(1402,564);(1474,560);(1546,557);(1615,554);(1687,551);(1759,548);(1831,545);(1903,542);(1975,540);(2047,537);(2119,534);(2191,531);(2263,529);(2335,526);(2407,524);(2479,522);(2551,520);(2623,518);(2695,516);(2767,515);(2839,514);(2911,513);(2983,511);(3055,510);(3127,508);(3199,507);(3271,506);(3347,505);(3418,504);(3489,504);(3560,504);(3631,504);(3702,503);(3773,503);(3844,503);(3915,502);(3986,502);(4057,502);(4128,502);(4204,502);(4274,502);(4344,502);(4414,502);(4484,502);(4554,502);(4624,503);(4694,503);(4764,503);(4834,504);(4904,506);(4974,507);(5050,508);(5121,509);(5192,510);(5263,512);(5334,513);(5405,515);(5476,516);(5547,518);(5618,520);(5689,522);(5760,524);(5831,526);(5904,528);(5970,530);(6036,532);(6102,534);(6168,537);(6234,540);(6300,543);(6366,546);(6432,549);(6498,553);(6564,556);(6630,559);(6707,563);(6778,566);(6849,570);(6920,573);(6991,577);(7062,581);(7133,585);(7204,588);(7275,595);(7346,597);(7417,604);(7488,606);(7565,613);(7642,618);(7719,623);(7796,628);(7873,634);(7950,639);(8027,645);(8104,651);(8181,657);(8258,659);(8335,662);(8412,662);(8490,660);(8561,652);(8632,644);(8703,635);(8774,627);(8845,619);(8916,612);(8987,605);(9058,597);(9129,590);(9200,583);(9271,576);(9347,570);(9418,564);(9489,558);(9560,553);(9631,547);(9702,542);(9773,537);(9844,532);(9915,527);(9986,525);(10057,521);(10128,517);(10208,513);(10283,509);(10358,505);(10433,502);(10508,499);(10583,496);(10658,493);(10733,490);(10808,487);(10883,484);(10958,481);(11033,478);(11115,475);(11186,473);(11257,471);(11328,469);(11399,467);(11470,465);(11541,463);(11612,461);(11683,459);(11754,457);(11825,455);(11896,453);(11968,452);(12040,450);(12112,449);(12184,447);(12256,446);(12328,445);(12400,443);(12472,442);(12544,441);(12616,440);(12688,439);(12760,439);(12829,438);(12900,438);(12971,438);(13042,437);(13113,437);(13184,437);(13255,437);(13326,437);(13397,437);(13468,437);(13539,437);(13610,437);(13686,437);(13758,437);(13830,437);(13902,437);(13974,438);(14046,438);(14118,439);(14190,439);(14262,440);(14334,441);(14406,442);(14478,443);(14550,444);(14622,445);(14694,446);(14766,448);(14838,449);(14910,450);(14982,452);(15054,454);(15126,456);(15198,457);(15270,459);(15342,461);(15422,463);(15493,465);(15564,467);(15635,469);(15706,471);(15777,474);(15848,477);(15919,479);(15990,482);(16061,485);(16132,488);(16203,491);(16276,494);(16347,497);(16418,500);(16489,503);(16560,506);(16631,510);(16702,514);(16773,518);(16844,525);(16915,527);(16986,531);(17057,535);(17132,538);(17203,540)";
-    pub const TEST_BAS_DROITE_ORIENTE_DROITE: &str = "(7549,580);(7621,587);(7693,594);(7765,601);(7837,608);(7909,615);(7981,623);(8053,631);(8125,640);(8197,649);(8269,657);(8341,666);(8419,675);(8495,685);(8571,695);(8647,706);(8723,717);(8799,729);(8875,742);(8951,755);(9027,769);(9103,784);(9179,798);(9255,813);(9329,829);(9400,846);(9471,865);(9542,885);(9613,905);(9684,925);(9755,948);(9826,972);(9897,999);(9968,1023);(10039,1051);(10110,1079);(10187,1109);(10261,1143);(10335,1180);(10409,1216);(10483,1255);(10557,1299);(10631,1350);(10705,1358);(10779,1357);(10853,1352);(10927,1350);(11001,1344);(11079,1342);(11150,1338);(11221,1335);(11292,1332);(11363,1329);(11434,1327);(11505,1324);(11576,1322);(11647,1320);(11718,1319);(11789,1317);(11860,1316);(11938,1315);(12007,1315);(12076,1314);(12145,1314);(12214,1314);(12283,1314);(12352,1314);(12421,1315);(12490,1316);(12559,1316);(12628,1317);(12697,1317);(12776,1317);(12847,1319);(12918,1321);(12989,1323);(13060,1326);(13131,1329);(13202,1331);(13273,1333);(13344,1335);(13415,1337);(13486,1340);(13557,1344);(13634,1352);(13711,1358);(13788,1364);(13865,1369);(13942,1375);(14019,1382);(14096,1390);(14173,1397);(14250,1404);(14327,1411);(14404,1417);(14481,1419);(14562,1426);(14632,1432);(14702,1437);(14772,1443);(14842,1449);(14912,1456);(14982,1462);(15052,1469);(15122,1475);(15192,1484);(15262,1495);(15332,1508);(15404,1521);(15475,1533);(15546,1544);(15617,1554);(15688,1563);(15759,1576);(15830,1589);(15901,1602);(15972,1618);(16043,1635);(16114,1653);(16185,1671);(16262,1688);(16334,1703);(16406,1719);(16478,1737);(16550,1758);(16622,1779);(16694,1800);(16766,1817);(16838,1835);(16910,1855);(16982,1876);(17054,1900);(17136,1921);(17207,1948);(18844,2293);(18916,2278);(18988,2264);(19060,2250);(19132,2236);(19204,2222);(19276,2208);(19348,2194);(19420,2182);(19492,2171);(19564,2162);(19636,2154);(19761,2146);(19832,2140);(19903,2138);(19974,2134);(20045,2132);(20116,2131);(20187,2129);(20258,2127);(20329,2121);(20400,2115);(20471,2109);(20542,2103);(20619,2098);(20691,2091);(20763,2084);(20835,2077);(20907,2070);(20979,981);(21051,981);(21123,980);(21195,979);(21267,977);(21339,975);(21411,975);(21493,978);(21563,988);(21633,1007);(21703,1026);(21773,1039);(21843,2015);(21913,2014);(21983,2014);(22053,2012);(22123,2003);(22193,2002);(22263,2009);(22348,2023);(22419,2337);(22490,2269);(22561,2024);(22632,696);(22703,655);(22774,649);(22845,644);(22916,642);(22987,641);(23058,641);(23129,643);(23208,647);(23279,651);(23350,654);(23421,657);(23492,659);(23563,663);(23634,671);(23705,1252);(23776,1218);(23847,1183);(23918,1151);(23989,1122);(24063,1096);(24128,1070);(24193,1046);(24258,1021);(24323,996);(24388,974);(24453,942);(24518,918);(24583,898);(24648,882);(24713,867);(24778,852);(24851,838);(24922,825);(24993,811);(25064,796);(25135,784);(25206,772);(25277,762);(25348,751);(25419,741);(25490,732);(25561,724);(25632,715);(25710,707);(25779,699);(25848,691);(25917,683);(25986,676);(26055,668);(26124,661);(26193,654);(26262,646);(26331,637);(26400,629);(26469,621);(26548,615);(26619,609);(26690,604);(26761,599);(26832,594);(26903,589);(26974,587);(27045,580);(27116,578);(27187,574);(27258,570);(27329,566);(27404,562);(27475,558);(27546,555);(27617,551);(27688,548);(27759,545);(27830,542);(27901,539);(27972,536);(28043,534);(28114,531);(28185,529);(28262,526);(28333,524);(28404,522);(28475,520);(28546,518);(28617,516);(28688,514);(28759,512);(28830,510);(28901,509);(28972,507);(29043,505);(29119,504);(29190,503);(29261,502);(29332,501);(29403,500);(29474,499);(29545,498);(29616,498);(29687,497);(29758,497);(29829,497);(29900,497);(29977,496);(30050,496);(30123,496);(30196,496);(30269,496);(30342,496);(30415,496);(30488,496);(30561,497);(30634,497);(30707,497);(30780,498);(30847,498);(30918,499);(30989,500);(31060,500);(31131,499);(31202,498);(31273,496);(31344,493);(31415,194);(31486,174);(31557,164);(31628,163);(31768,164);(31839,498);(31910,508);(31981,514);(32052,516);(32123,520);(32194,523);(32265,526);(32336,529);(32407,532);(32478,535);(32549,538);(32630,541);(32701,545);(32772,549);(32843,553);(32914,556);(32985,560);(33056,563);(33127,567);(33198,570);(33269,573);(33340,577);(33411,581);(33492,585);(33563,589);(33634,593);(33705,600);(33776,601);(33847,605);(33918,609);(33989,613);(34060,620);(34131,625);(34202,631);(34273,637);(34351,645);(34415,654);(34479,659);(34543,659);(34607,658);(34671,652);(34735,646);(34799,638);(34863,629);(34927,621);(34991,614);(35055,607);(35133,600);(35204,593);(35275,586);(687,135);(757,478);(829,479);(900,481);(971,481);(1042,480);(1113,477);(1184,475);(1255,472);(1326,470);(1397,468);(1468,466);(1539,464);(1610,462);(1691,460);(1762,458);(1833,456);(1904,455);(1975,453);(2046,452);(2117,450);(2188,449);(2259,447);(2330,446);(2401,445);(2472,444);(2548,442);(2619,441);(2690,440);(2761,440);(2832,439);(2903,439);(2974,439);(3045,438);(3116,438);(3187,438);(3258,437);(3329,437);(3404,437);(3475,437);(3546,437);(3617,437);(3688,437);(3759,438);(3830,438);(3901,438);(3972,438);(4043,439);(4114,439);(4185,440);(4266,440);(4337,441);(4408,442);(4479,443);(4550,444);(4621,445);(4692,446);(4763,448);(4834,449);(4905,450);(4976,452);(5047,453);(5118,455);(5189,457);(5260,459);(5331,461);(5402,463);(5473,465);(5544,467);(5615,469);(5686,471);(5757,473);(5828,475);(5899,478);(5977,480);(6043,483);(6109,486);(6175,489);(6241,492);(6307,495);(6373,498);(6439,501);(6505,505);(6571,508);(6637,512);(6703,516);(6776,520);(6847,524);(6918,528);(6989,535);(7060,540);(7131,545);(7202,551);(7273,557);(7344,563);(7415,569);(7486,575);(7557,582);(7634,589);(7710,597);(7786,604);(7862,611);(7938,619);(8014,627);(8090,634);(8166,642);(8242,651);(8318,661);(8394,672);(8470,682);(8548,691);(8619,700);(8690,709);(8761,720);(8832,732);(8903,745);(8974,759);(9045,774);(9116,789);(9187,805);(9258,821);(9329,837);(9404,854);(9475,872);(9546,891);(9617,911);(9688,933);(9759,956);(9830,982);(9901,1007);(9972,1032);(10043,1060);(10114,1089);(10185,1118);(10259,1155);(10336,1194);(10413,1229);(10490,1266);(10567,1314);(10644,1359);(10721,1359);(10798,1358);(10875,1353);(10952,1351);(11029,1344);(11106,1342);(11186,1338);(11257,1334);(11328,1331);(11399,1329);(11470,1326);(11541,1324);(11612,1322);(11683,1321);(11754,1320);(11825,1318);(11896,1317);(11967,1315);(12045,1315);(12116,1315);(12187,1315);(12258,1315);(12329,1315);(12400,1315);(12471,1315);(12542,1315);(12613,1316);(12684,1316);(12755,1317);(12826,1318);(12905,1320);(12976,1322);(13047,1324);(13118,1326);(13189,1329);(13260,1331);(13331,1333);(13402,1335);(13473,1337);(13544,1340);(13615,1343);(13686,1350);(13761,1356);(13832,1362);(13903,1368);(13974,1375);(14045,1382);(14116,1390);(14187,1396);(14258,1403);(14329,1410);(14400,1416);(14471,1421);(14542,1427);(14616,1433);(14687,1438);(14758,1444);(14829,1450);(14900,1456);(14971,1462);(15042,1469);(15113,1476);(15184,1485);(15255,1496);(15326,1508);(15397,1520);(15479,1531);(15550,1542);(15621,1554);(15692,1565);(15763,1578);(15834,1593);(15905,1604);(15976,1616);(16047,1632);(16118,1651);(16189,1668);(16260,1684);(16337,1699);(16408,1716);(16479,1737);(16550,1758);(16621,1779);(16692,1800);(16763,1817);(16834,1834);(16905,1854);(16976,1874);(17047,1895);(17118,1921)";
-
-    pub const TESTS_DETECTION: [&&str; 13] = [
-        &TEST_CENTRE_DROIT_ORIENTE_DROITE,
-        &TEST_CENTRE_GAUCHE_ORIENTE_GAUCHE,
-        &TEST_HAUT_GAUCHE_ORIENTE_GAUCHE,
-        &TEST_HAUT_GAUCHE_ORIENTE_DROITE,
-        &TEST_HAUT_MILIEU_ORIENTE_GAUCHE,
-        &TEST_HAUT_MILIEU_ORIENTE_DROITE,
-        &TEST_HAUT_DROITE_ORIENTE_GAUCHE,
-        &TEST_HAUT_DROITE_ORIENTE_DROITE,
-        &TEST_BAS_GAUCHE_ORIENTE_GAUCHE,
-        &TEST_BAS_GAUCHE_ORIENTE_DROITE,
-        &TEST_BAS_CENTRE_ORIENTE_CENTRE,
-        &TEST_BAS_DROITE_ORIENTE_GAUCHE,
-        &TEST_BAS_DROITE_ORIENTE_DROITE,
-    ];
-}
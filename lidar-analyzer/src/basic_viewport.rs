@@ -1,20 +1,22 @@
 use eframe::egui::Pos2;
-use eframe::{egui, EventLoopBuilderHook};
+use eframe::{EventLoopBuilderHook, egui};
 use winit::platform::wayland::EventLoopBuilderExtWayland;
 
-use crate::analyze::PolarLine;
+use crate::analyze::{HoughConfig, PolarLine, WallLine, accumulator_cell};
+use crate::dataset::Capture;
+use crate::hough_explorer::HoughExplorer;
 use crate::parse::{LidarPoint, PolarPoint};
-use crate::units::*;
+use crate::prelude::*;
 
 const DEFAULT_ZOOM: f32 = 180.0;
 
+/// Vue des points du lidar, zoom avec la molette, déplacement en faisant glisser la souris,
+/// "H" pour revenir à la vue de départ
 struct PolarPointsApp {
     zoom: f32,
     points: Vec<LidarPoint>,
     vlines: Vec<ViewportLine>,
-    offset: egui::Vec2,                 // Décalage
-    dragging: bool,                     // Indique si la souris est en train d'être maintenue
-    last_mouse_pos: Option<egui::Pos2>, // Dernière position de la souris
+    offset: egui::Vec2, // Décalage
 }
 
 impl Default for PolarPointsApp {
@@ -28,14 +30,7 @@ impl Default for PolarPointsApp {
                 intensity: Intensity::NULL,
             })
             .collect();
-        Self {
-            zoom: DEFAULT_ZOOM,
-            points,
-            vlines: Vec::new(),
-            offset: egui::vec2(0.0, 0.0),
-            dragging: false,
-            last_mouse_pos: None,
-        }
+        Self::new(points, Vec::new())
     }
 }
 
@@ -46,89 +41,72 @@ impl PolarPointsApp {
             points,
             vlines: lines,
             offset: egui::vec2(0.0, 0.0),
-            dragging: false,
-            last_mouse_pos: None,
         }
     }
 }
 
 impl eframe::App for PolarPointsApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Vérifier si la touche "H" est pressée
-        if ctx.input(|i| i.key_pressed(egui::Key::H)) {
-            self.zoom = DEFAULT_ZOOM; // Réinitialiser le zoom
-            self.offset = egui::vec2(0.0, 0.0); // Réinitialiser le décalage
-        }
-
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.label("Basic Viewport");
             ui.label(format!("zoom : {}", self.zoom));
             ui.label(format!("décallage : {}", self.offset));
+            self.show_points(ui, &[]);
+        });
+    }
+}
+
+impl PolarPointsApp {
+    /// Dessine les points et les droites dans toute la place restante de `ui`,
+    /// les points d'indice `highlighted` sont mis en évidence
+    fn show_points(&mut self, ui: &mut egui::Ui, highlighted: &[usize]) {
+        let (response, painter) = ui.allocate_painter(ui.available_size(), egui::Sense::drag());
 
+        // Vérifier si la touche "H" est pressée
+        if ui.input(|i| i.key_pressed(egui::Key::H)) {
+            self.zoom = DEFAULT_ZOOM; // Réinitialiser le zoom
+            self.offset = egui::vec2(0.0, 0.0); // Réinitialiser le décalage
+        }
+        if response.hovered() {
             // Zoom avec la molette de la souris
             let scroll_delta = ui.input(|i| i.smooth_scroll_delta);
             self.zoom *= 1.0 + scroll_delta.y * 0.01; // Ajustez le facteur de zoom ici
+        }
+        self.offset += response.drag_delta();
 
-            // Gérer le décalage avec la souris
-            let pointer = ui.input(|i| i.pointer.clone());
-            if pointer.any_released() {
-                self.dragging = false;
-                self.last_mouse_pos = None;
-            }
-
-            if pointer.any_pressed() {
-                self.dragging = true;
-                self.last_mouse_pos = pointer.interact_pos();
-            }
-
-            if self.dragging {
-                if let Some(current_mouse_pos) = pointer.interact_pos() {
-                    if let Some(last_pos) = self.last_mouse_pos {
-                        let delta = current_mouse_pos - last_pos;
-                        self.offset += delta;
-                    }
-                    self.last_mouse_pos = Some(current_mouse_pos);
-                }
-            }
-
-            // Dessiner les points
-            let painter = ui.painter();
-            let center = ui.available_rect_before_wrap().center() + self.offset;
-
-            painter.circle(center, 2.0, egui::Color32::GRAY, egui::Stroke::NONE);
-
-            for point in &self.points {
-                let x = center.x + (point.point.angle.cos() * point.point.distance.0) as f32 * self.zoom;
-                let y = center.y + (point.point.angle.sin() * point.point.distance.0) as f32 * self.zoom;
-                painter.circle(
-                    egui::pos2(x, y),
-                    5.0,
-                    egui::Color32::GREEN.gamma_multiply(0.5),
-                    egui::Stroke::NONE,
-                );
-            }
-            let center_pos_w = (0.6494805440218329, 1.282994287434672);
-            painter.circle(egui::pos2(center.x + self.zoom * center_pos_w.0, center.y + self.zoom * center_pos_w.1), 2.0, egui::Color32::WHITE, egui::Stroke::NONE);
-
-            // for vline in self.vlines.iter() {
-            //     self.draw_line(ui, ctx, center, vline);
-            // }
-            
-        });
+        let center = response.rect.center() + self.offset;
+        painter.circle(center, 2.0, egui::Color32::GRAY, egui::Stroke::NONE);
+
+        for (n_point, point) in self.points.iter().enumerate() {
+            let (color, radius) = if highlighted.contains(&n_point) {
+                (egui::Color32::YELLOW, 4.0)
+            } else {
+                (egui::Color32::GREEN.gamma_multiply(0.5), 3.0)
+            };
+            painter.circle(
+                self.to_screen(center, &point.point),
+                radius,
+                color,
+                egui::Stroke::NONE,
+            );
+        }
+
+        for vline in self.vlines.iter() {
+            self.draw_line(&painter, center, vline);
+        }
     }
-}
 
-impl PolarPointsApp {
     #[inline]
-    fn draw_line(
-        &self,
-        ui: &mut egui::Ui,
-        ctx: &egui::Context,
-        center: Pos2,
-        vline: &ViewportLine,
-    ) {
+    fn to_screen(&self, center: Pos2, point: &PolarPoint) -> Pos2 {
+        let x = center.x + (point.angle.cos() * point.distance.0) as f32 * self.zoom;
+        let y = center.y + (point.angle.sin() * point.distance.0) as f32 * self.zoom;
+        egui::pos2(x, y)
+    }
+
+    #[inline]
+    fn draw_line(&self, painter: &egui::Painter, center: Pos2, vline: &ViewportLine) {
         let perpendicular_angle = vline.line.angle + Rad::QUARTER_TURN;
-        let (width, height) = ctx.screen_rect().size().into();
+        let (width, height) = painter.clip_rect().size().into();
         let length: f32 = 2.0 * (width.powi(2) + height.powi(2)).sqrt(); // Assez bonne approximation
         let x_end: f32 =
             center.x + ((vline.line.distance.0 * vline.line.angle.cos()) as f32) * self.zoom;
@@ -138,26 +116,28 @@ impl PolarPointsApp {
         let y1: f32 = y_end + length * perpendicular_angle.sin() as f32;
         let x2: f32 = x_end - length * perpendicular_angle.cos() as f32;
         let y2: f32 = y_end - length * perpendicular_angle.sin() as f32;
-        ui.painter()
-            .line_segment([egui::pos2(x1, y1), egui::pos2(x2, y2)], vline.stroke);
+        painter.line_segment([egui::pos2(x1, y1), egui::pos2(x2, y2)], vline.stroke);
     }
 }
 
-pub fn show_viewport(
-    points: Vec<LidarPoint>,
-    lines: Vec<ViewportLine>,
-) -> Result<(), eframe::Error> {
+fn native_options() -> eframe::NativeOptions {
     let event_loop_builder: Option<EventLoopBuilderHook> = Some(Box::new(|event_loop_builder| {
         event_loop_builder.with_any_thread(true);
     }));
-    let options = eframe::NativeOptions {
+    eframe::NativeOptions {
         event_loop_builder,
         viewport: egui::ViewportBuilder::default().with_inner_size([800.0, 800.0]),
         ..Default::default()
-    };
+    }
+}
+
+pub fn show_viewport(
+    points: Vec<LidarPoint>,
+    lines: Vec<ViewportLine>,
+) -> Result<(), eframe::Error> {
     eframe::run_native(
         "Points en coordonnées polaires",
-        options,
+        native_options(),
         Box::new(|_cc| Ok(Box::new(PolarPointsApp::new(points, lines)))),
     )
 }
@@ -167,3 +147,217 @@ pub struct ViewportLine {
     pub line: PolarLine,
     pub stroke: egui::Stroke,
 }
+
+// Explorateur de l'accumulateur de Hough (voir `hough_explorer`) :
+// les seuils à gauche, l'accumulateur au milieu et les points à droite.
+// Un clic sur l'accumulateur sélectionne le pic le plus proche, sa droite et ses points sont surlignés.
+
+/// hauteur de l'accumulateur à l'écran pour une case
+const HEATMAP_CELL_SIZE: f32 = 1.2;
+
+struct HoughExplorerApp {
+    explorer: HoughExplorer,
+    view: PolarPointsApp,
+    heatmap: Option<egui::TextureHandle>,
+}
+
+impl HoughExplorerApp {
+    fn new(capture: Capture) -> Self {
+        let view = PolarPointsApp::new(capture.points.clone(), Vec::new());
+        let mut app = Self {
+            explorer: HoughExplorer::new(capture, HoughConfig::default()),
+            view,
+            heatmap: None,
+        };
+        app.update_lines();
+        app
+    }
+
+    /// Murs trouvés et droite du pic sélectionné
+    fn update_lines(&mut self) {
+        let walls = self.explorer.analysis().walls.map(|walls| walls.as_array());
+        self.view.vlines = walls
+            .iter()
+            .flatten()
+            .map(|wall| ViewportLine {
+                line: wall.line(),
+                stroke: egui::Stroke::new(1.5, wall_color(wall)),
+            })
+            .chain(self.explorer.selected().map(|peak| ViewportLine {
+                line: peak.line,
+                stroke: egui::Stroke::new(2.5, egui::Color32::YELLOW),
+            }))
+            .collect();
+    }
+
+    /// Renvoit la configuration modifiée par les curseurs
+    fn config_panel(&self, ui: &mut egui::Ui) -> HoughConfig {
+        let mut config = *self.explorer.config();
+        ui.heading("Seuils");
+        ui.add(
+            egui::Slider::new(&mut config.min_points_per_line, 1..=300).text("points par droite"),
+        );
+        ui.add(
+            egui::Slider::new(&mut config.nms_distance_radius, 0..=20)
+                .text("non-maxima (cases de distance)"),
+        );
+        ui.add(
+            egui::Slider::new(&mut config.nms_angle_radius, 0..=20)
+                .text("non-maxima (cases d'angle)"),
+        );
+        ui.add(egui::Slider::new(&mut config.refine_radius, 0..=10).text("affinage (cases)"));
+        ui.add(
+            egui::Slider::new(&mut config.merge_distance_tolerance.0, 0.0..=0.5).text("fusion (m)"),
+        );
+        degrees_slider(ui, &mut config.merge_angle_tolerance, "fusion (°)");
+        degrees_slider(ui, &mut config.pair_angle_tolerance, "paires (°)");
+        ui.add(
+            egui::Slider::new(&mut config.pair_distance_tolerance.0, 0.0..=0.5).text("paires (m)"),
+        );
+        if ui.button("valeurs du robot").clicked() {
+            config = HoughConfig::default();
+        }
+
+        ui.separator();
+        ui.label(format!("capture : {}", self.explorer.capture().name));
+        ui.label(format!("{} pics", self.explorer.peaks().len()));
+        ui.label(format!(
+            "{} paires largeur, {} paires longueur",
+            self.explorer.analysis().width_candidates.len(),
+            self.explorer.analysis().length_candidates.len()
+        ));
+        ui.label(match self.explorer.analysis().walls {
+            Some(_) => "murs trouvés",
+            None => "murs non trouvés",
+        });
+        if let Some(peak) = self.explorer.selected() {
+            ui.separator();
+            ui.label(format!(
+                "pic sélectionné : poids {}, distance {:.3} m, angle {:.1}°",
+                peak.weight,
+                peak.line.distance.0,
+                peak.line.angle.deg().val()
+            ));
+            ui.label(format!(
+                "{} points sur la droite",
+                self.explorer.supporting_points().len()
+            ));
+        }
+        config
+    }
+
+    fn heatmap_panel(&mut self, ui: &mut egui::Ui) {
+        let ([width, height], pixels) = self.explorer.heatmap();
+        // l'accumulateur ne change qu'avec les seuils, voir `update`
+        let texture = self.heatmap.get_or_insert_with(|| {
+            let rgb: Vec<u8> = pixels
+                .iter()
+                .flat_map(|weight| heat_color(*weight))
+                .collect();
+            ui.ctx().load_texture(
+                "hough_accumulator",
+                egui::ColorImage::from_rgb([width, height], &rgb),
+                egui::TextureOptions::NEAREST,
+            )
+        });
+
+        ui.label("distance ↓ angle →");
+        let size = egui::vec2(width as f32, height as f32) * HEATMAP_CELL_SIZE;
+        let explorer = &self.explorer;
+        let clicked_cell = egui::ScrollArea::vertical()
+            .show(ui, |ui| {
+                let response = ui.add(
+                    egui::Image::new(&*texture)
+                        .fit_to_exact_size(size)
+                        .sense(egui::Sense::click()),
+                );
+                let to_screen = |(distance_case, angle_case): (usize, usize)| {
+                    response.rect.min
+                        + egui::vec2(angle_case as f32 + 0.5, distance_case as f32 + 0.5)
+                            * HEATMAP_CELL_SIZE
+                };
+                let painter = ui.painter_at(response.rect);
+                for peak in explorer.peaks() {
+                    if let Some(cell) = accumulator_cell(peak.line) {
+                        let stroke = egui::Stroke::new(1.0, egui::Color32::LIGHT_BLUE);
+                        painter.circle_stroke(to_screen(cell), 4.0, stroke);
+                    }
+                }
+                if let Some(cell) = explorer
+                    .selected()
+                    .and_then(|peak| accumulator_cell(peak.line))
+                {
+                    let stroke = egui::Stroke::new(2.0, egui::Color32::YELLOW);
+                    painter.circle_stroke(to_screen(cell), 6.0, stroke);
+                }
+
+                let position = response
+                    .interact_pointer_pos()
+                    .filter(|_| response.clicked())?;
+                let cell = (position - response.rect.min) / HEATMAP_CELL_SIZE;
+                Some((cell.y.max(0.0) as usize, cell.x.max(0.0) as usize))
+            })
+            .inner;
+        if let Some(cell) = clicked_cell {
+            self.explorer.select_near(cell);
+            self.update_lines();
+        }
+    }
+}
+
+impl eframe::App for HoughExplorerApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let config = egui::SidePanel::left("hough_config")
+            .show(ctx, |ui| self.config_panel(ui))
+            .inner;
+        if self.explorer.set_config(config) {
+            self.heatmap = None;
+            self.update_lines();
+        }
+        egui::SidePanel::left("hough_accumulator")
+            .resizable(false)
+            .show(ctx, |ui| self.heatmap_panel(ui));
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let supporting = self.explorer.supporting_points();
+            self.view.show_points(ui, &supporting);
+        });
+    }
+}
+
+/// Curseur en degrés pour un angle stocké en radians
+fn degrees_slider(ui: &mut egui::Ui, radians: &mut f64, text: &str) {
+    let mut degrees = radians.to_degrees();
+    if ui
+        .add(egui::Slider::new(&mut degrees, 0.0..=45.0).text(text))
+        .changed()
+    {
+        *radians = degrees.to_radians();
+    }
+}
+
+/// Noir, rouge, jaune puis blanc quand le poids augmente
+fn heat_color(weight: u8) -> [u8; 3] {
+    let weight = u32::from(weight) * 3;
+    let channel = |start: u32| weight.saturating_sub(start).min(255) as u8;
+    [channel(0), channel(255), channel(510)]
+}
+
+fn wall_color(wall: &WallLine) -> egui::Color32 {
+    let (r, g, b) = match wall {
+        WallLine::FoundAsParallelLine(_) => colors::GREEN,
+        WallLine::FoundAsPerpendicular(_) => colors::ORANGE,
+        WallLine::GuessedLine(_) => colors::MAGENTA,
+    };
+    egui::Color32::from_rgb(r, g, b)
+}
+
+/// Ouvre l'explorateur de l'accumulateur de Hough sur `capture`
+pub fn show_hough_explorer(capture: Capture) -> Result<(), eframe::Error> {
+    let mut options = native_options();
+    options.viewport = options.viewport.with_inner_size([1400.0, 900.0]);
+    eframe::run_native(
+        &format!("Accumulateur de Hough : {}", capture.name),
+        options,
+        Box::new(|_cc| Ok(Box::new(HoughExplorerApp::new(capture)))),
+    )
+}
@@ -0,0 +1,24 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use lidar_analyzer::basic_viewport::show_hough_explorer;
+use lidar_analyzer::dataset::{CAPTURE_EXTENSION, Capture, default_captures_dir};
+use robot_logging::{LogConfig, set_up_logging};
+
+/// Ouvre une capture dans l'explorateur de l'accumulateur de Hough.
+///
+/// `hough-explorer <capture>` où `<capture>` est un fichier `.capture`
+/// ou le nom d'une capture du dossier `captures/` du crate.
+fn main() -> Result<(), Box<dyn Error>> {
+    set_up_logging(&LogConfig::new("hough-explorer"))?;
+    let name = std::env::args()
+        .nth(1)
+        .ok_or("usage : hough-explorer <fichier .capture ou nom de capture>")?;
+    let mut path = PathBuf::from(&name);
+    if !path.exists() {
+        path = default_captures_dir().join(format!("{}.{}", name, CAPTURE_EXTENSION));
+    }
+    let capture = Capture::load(&path)?;
+    show_hough_explorer(capture)?;
+    Ok(())
+}
@@ -0,0 +1,276 @@
+//! Charge les tours de lidar enregistrés sur le terrain (captures) depuis des fichiers.
+//!
+//! Une capture est un fichier `<nom>.capture` placé dans le dossier `captures/` du crate.
+//! Chaque ligne est de la forme `clé: valeur`, les lignes vides et celles commençant par `#`
+//! sont ignorées. Clés reconnues :
+//!  - `points` (obligatoire) : les points au format du robot `(angle,distance);(angle,distance);...`
+//!    avec l'angle en centièmes de degrés et la distance en mm
+//!  - `field_length_cm`, `field_width_cm` : dimensions du terrain sur lequel la capture a été faite
+//!  - `expected_x_cm`, `expected_y_cm`, `expected_orientation_deg` : pose du robot mesurée sur le terrain
+//!    (voir `analyze::RobotPose`), les trois ou aucune
+//!  - `approx_x_cm`, `approx_y_cm`, `approx_orientation_deg` : pose approximative déduite du nom de
+//!    la capture (zone du terrain et direction), en attendant une pose mesurée, les trois ou aucune
+//!  - `notes` : texte libre, peut être répété
+
+use std::error::Error;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use crate::analyze::RobotPose;
+use crate::parse::{LidarPoint, PolarPoint};
+use crate::prelude::*;
+
+/// extension des fichiers de capture
+pub const CAPTURE_EXTENSION: &str = "capture";
+
+/// Représente l'ensemble des erreurs pouvant survenir lors du chargement d'une capture
+#[derive(Debug)]
+pub enum DatasetError {
+    /// Erreurs de lecture du fichier ou du dossier
+    IoError(std::io::Error),
+    /// La ligne donnée n'est pas de la forme `clé: valeur`
+    MalformedLine(usize),
+    /// La clé donnée n'est pas reconnue
+    UnknownKey(String),
+    /// La valeur de la clé donnée n'est pas un nombre valide
+    InvalidValue(String),
+    /// Le point donné n'est pas de la forme `(angle,distance)`
+    InvalidPoint(String),
+    /// La clé donnée est obligatoire mais absente
+    MissingKey(&'static str),
+    /// La pose attendue n'est que partiellement renseignée
+    IncompletePose,
+}
+
+impl Display for DatasetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Error for DatasetError {}
+
+impl From<std::io::Error> for DatasetError {
+    fn from(error: std::io::Error) -> Self {
+        DatasetError::IoError(error)
+    }
+}
+
+/// Un tour de lidar enregistré et ses métadonnées
+#[derive(Debug, Clone)]
+pub struct Capture {
+    /// nom du fichier sans l'extension
+    pub name: String,
+    pub points: Vec<LidarPoint>,
+    /// dimensions (longueur, largeur) du terrain lors de l'enregistrement, si connues
+    pub field_size: Option<(Meters, Meters)>,
+    /// pose du robot lors de l'enregistrement, si elle a été mesurée
+    pub expected_pose: Option<RobotPose>,
+    /// pose grossière (zone du terrain, direction), à comparer avec de larges tolérances
+    pub approx_pose: Option<RobotPose>,
+    pub notes: Vec<String>,
+}
+
+impl Capture {
+    /// Charge une capture depuis un fichier
+    pub fn load(path: &Path) -> Result<Self, DatasetError> {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self::parse(name, &std::fs::read_to_string(path)?)
+    }
+
+    /// Lit une capture depuis le contenu d'un fichier
+    pub fn parse(name: String, content: &str) -> Result<Self, DatasetError> {
+        let mut points = None;
+        let mut field_length = None;
+        let mut field_width = None;
+        let mut expected_x = None;
+        let mut expected_y = None;
+        let mut expected_orientation = None;
+        let mut approx_x = None;
+        let mut approx_y = None;
+        let mut approx_orientation = None;
+        let mut notes = Vec::new();
+
+        for (n_line, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(DatasetError::MalformedLine(n_line + 1));
+            };
+            let value = value.trim();
+            match key.trim() {
+                "points" => points = Some(parse_points(value)?),
+                "field_length_cm" => field_length = Some(Meters::cm(parse_value(key, value)?)),
+                "field_width_cm" => field_width = Some(Meters::cm(parse_value(key, value)?)),
+                "expected_x_cm" => expected_x = Some(Meters::cm(parse_value(key, value)?)),
+                "expected_y_cm" => expected_y = Some(Meters::cm(parse_value(key, value)?)),
+                "expected_orientation_deg" => {
+                    expected_orientation = Some(Deg::new(parse_value(key, value)?).rad())
+                }
+                "approx_x_cm" => approx_x = Some(Meters::cm(parse_value(key, value)?)),
+                "approx_y_cm" => approx_y = Some(Meters::cm(parse_value(key, value)?)),
+                "approx_orientation_deg" => {
+                    approx_orientation = Some(Deg::new(parse_value(key, value)?).rad())
+                }
+                "notes" => notes.push(value.to_owned()),
+                other => return Err(DatasetError::UnknownKey(other.to_owned())),
+            }
+        }
+
+        Ok(Self {
+            name,
+            points: points.ok_or(DatasetError::MissingKey("points"))?,
+            field_size: field_length.zip(field_width),
+            expected_pose: pose_of(expected_x, expected_y, expected_orientation)?,
+            approx_pose: pose_of(approx_x, approx_y, approx_orientation)?,
+            notes,
+        })
+    }
+}
+
+/// Une pose n'a de sens que si ses trois valeurs sont données
+#[inline]
+fn pose_of(
+    x: Option<Meters>,
+    y: Option<Meters>,
+    orientation: Option<Rad>,
+) -> Result<Option<RobotPose>, DatasetError> {
+    match (x, y, orientation) {
+        (Some(x), Some(y), Some(orientation)) => Ok(Some(RobotPose { x, y, orientation })),
+        (None, None, None) => Ok(None),
+        _ => Err(DatasetError::IncompletePose),
+    }
+}
+
+#[inline]
+fn parse_value(key: &str, value: &str) -> Result<f64, DatasetError> {
+    value
+        .parse()
+        .map_err(|_| DatasetError::InvalidValue(key.trim().to_owned()))
+}
+
+/// Lit des points au format du robot : `(angle,distance);(angle,distance);...`
+/// avec l'angle en centièmes de degrés et la distance en mm
+pub fn parse_points(from: &str) -> Result<Vec<LidarPoint>, DatasetError> {
+    let mut points = Vec::with_capacity(from.len() / 10);
+    for point in from.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+        let invalid = || DatasetError::InvalidPoint(point.to_owned());
+        let (angle, distance) = point
+            .strip_prefix('(')
+            .and_then(|p| p.strip_suffix(')'))
+            .and_then(|p| p.split_once(','))
+            .ok_or_else(invalid)?;
+        let angle = angle.trim().parse::<u16>().map_err(|_| invalid())?;
+        let distance = distance.trim().parse::<u16>().map_err(|_| invalid())?;
+        points.push(LidarPoint {
+            point: PolarPoint {
+                distance: Meters::mm(f64::from(distance)),
+                angle: Deg::new(f64::from(angle) / 100.0).rad(),
+            },
+            intensity: Intensity::NULL,
+        });
+    }
+    Ok(points)
+}
+
+/// Dossier où sont rangées les captures du dépôt
+pub fn default_captures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("captures")
+}
+
+/// Charge toutes les captures d'un dossier, triées par nom
+pub fn load_captures(dir: &Path) -> Result<Vec<Capture>, DatasetError> {
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == CAPTURE_EXTENSION) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    paths.iter().map(|path| Capture::load(path)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_capture() {
+        let capture = Capture::parse(
+            "exemple".to_owned(),
+            "# commentaire\n\
+             field_length_cm: 243\n\
+             field_width_cm: 182\n\
+             expected_x_cm: -12.5\n\
+             expected_y_cm: 30\n\
+             expected_orientation_deg: 90\n\
+             approx_x_cm: 0\n\
+             approx_y_cm: 45.5\n\
+             approx_orientation_deg: -90\n\
+             notes: première note\n\
+             notes: seconde note\n\
+             points: (9000,1000);(18000, 500);\n",
+        )
+        .unwrap();
+        assert_eq!(capture.points.len(), 2);
+        assert_eq!(capture.points[0].point.distance, Meters(1.0));
+        assert!((capture.points[1].point.angle - Rad::HALF_TURN).mag() < Rad::new(1e-10));
+        assert_eq!(
+            capture.field_size,
+            Some((Meters::cm(243.0), Meters::cm(182.0)))
+        );
+        let pose = capture.expected_pose.unwrap();
+        assert_eq!(pose.x, Meters::cm(-12.5));
+        assert_eq!(pose.y, Meters::cm(30.0));
+        let approx = capture.approx_pose.unwrap();
+        assert_eq!(approx.y, Meters::cm(45.5));
+        assert!((approx.orientation + Rad::QUARTER_TURN).mag() < Rad::new(1e-10));
+        assert_eq!(capture.notes, ["première note", "seconde note"]);
+    }
+
+    #[test]
+    fn test_parse_capture_errors() {
+        assert!(matches!(
+            Capture::parse(String::new(), "field_length_cm: 243"),
+            Err(DatasetError::MissingKey("points"))
+        ));
+        assert!(matches!(
+            Capture::parse(String::new(), "points: (100,200);(300)"),
+            Err(DatasetError::InvalidPoint(_))
+        ));
+        assert!(matches!(
+            Capture::parse(String::new(), "points: (100,200)\nexpected_x_cm: 10"),
+            Err(DatasetError::IncompletePose)
+        ));
+        assert!(matches!(
+            Capture::parse(
+                String::new(),
+                "points: (100,200)\napprox_orientation_deg: 10"
+            ),
+            Err(DatasetError::IncompletePose)
+        ));
+        assert!(matches!(
+            Capture::parse(String::new(), "points: (100,200)\nvitesse: 10"),
+            Err(DatasetError::UnknownKey(_))
+        ));
+        assert!(matches!(
+            Capture::parse(String::new(), "points: (100,200)\npas de clé"),
+            Err(DatasetError::MalformedLine(2))
+        ));
+    }
+
+    #[test]
+    fn test_load_repository_captures() {
+        let captures = load_captures(&default_captures_dir()).unwrap();
+        assert!(!captures.is_empty());
+        for capture in captures {
+            assert!(!capture.points.is_empty(), "{} est vide", capture.name);
+        }
+    }
+}
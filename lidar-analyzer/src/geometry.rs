@@ -0,0 +1,800 @@
+//! Types géométriques partagés par l'analyse du lidar.
+//!
+//! Toutes les coordonnées sont en mètres et tous les angles sont comptés depuis l'axe x vers l'axe y.
+//!
+//! Une seule convention pour les droites : la forme normale de Hesse
+//! `x cos(angle) + y sin(angle) = distance` avec `distance >= 0` et `angle` dans [0, 2pi[.
+//! Les deux conventions de la transformation de Hough (distance négative ou angle > 180°)
+//! sont converties vers celle-ci par `Line::new`.
+//!
+//! Ces types ne sont pas construits sur `robot_common::frames`, et c'est voulu :
+//!  - ils servent dans deux repères propres à l'analyse, celui du lidar (x vers l'angle 0°, le kicker),
+//!    où sont calculées les droites de Hough, et celui du terrain de `RobotPose` (x le long des murs
+//!    de longueur). Aucun des deux n'est `Local` ou `Global`, ils sont tournés d'un quart de tour ;
+//!  - `Transform` passe justement de l'un à l'autre (`RobotPose::transform`), ce qu'un `Point2<F>`
+//!    ne sait pas exprimer ;
+//!  - les droites (`Line`, `PolarLine`) n'ont pas d'équivalent dans `robot_common`.
+//!
+//! Les unités (`Meters`, `Rad`) sont celles de `robot_common`. Les résultats ne quittent l'analyse
+//! que par `RobotPose::to_pose2` et `LocalPoint::from(&PolarPoint)` : tout ce qui est envoyé aux
+//! autres programmes (voir `ipc`) doit passer par ces conversions.
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::analyze::PolarLine;
+use crate::parse::PolarPoint;
+use crate::prelude::*;
+
+/// en dessous de cet écart d'angle *en radians*, deux droites sont considérées comme parallèles
+pub const PARALLEL_EXACT_TOLERANCE: f64 = 10e-5;
+
+/// Ramène un angle dans [0, 2pi[
+#[inline]
+pub fn wrap_angle(angle: Rad) -> Rad {
+    let wrapped = angle.val().rem_euclid(Rad::FULL_TURN.val());
+    // rem_euclid peut renvoyer exactement 2pi à cause des arrondis
+    if wrapped >= Rad::FULL_TURN.val() {
+        Rad::ZERO
+    } else {
+        Rad::new(wrapped)
+    }
+}
+
+/// Ramène un angle dans ]-pi, pi]
+#[inline]
+pub fn wrap_angle_signed(angle: Rad) -> Rad {
+    let wrapped = wrap_angle(angle);
+    if wrapped > Rad::HALF_TURN {
+        wrapped - Rad::FULL_TURN
+    } else {
+        wrapped
+    }
+}
+
+/// Un point du plan
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: Meters,
+    pub y: Meters,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(Meters(0.0), Meters(0.0));
+
+    #[inline]
+    pub const fn new(x: Meters, y: Meters) -> Self {
+        Self { x, y }
+    }
+
+    #[inline]
+    pub fn from_polar(distance: Meters, angle: Rad) -> Self {
+        Self::new(distance * angle.cos(), distance * angle.sin())
+    }
+
+    #[inline]
+    pub fn distance_to(&self, other: Point) -> Meters {
+        (*self - other).norm()
+    }
+
+    /// Vecteur allant de l'origine à ce point
+    #[inline]
+    pub fn to_vector(self) -> Vector {
+        self - Point::ORIGIN
+    }
+
+    pub fn to_polar_point(self) -> PolarPoint {
+        let vector = self.to_vector();
+        PolarPoint {
+            distance: vector.norm(),
+            angle: if vector == Vector::ZERO {
+                // atan2(0, 0) n'a pas de sens, on choisit l'angle nul
+                Rad::ZERO
+            } else {
+                wrap_angle(vector.angle())
+            },
+        }
+    }
+
+    /// Pour l'affichage (rerun, egui)
+    #[inline]
+    pub fn to_f32(self) -> (f32, f32) {
+        (self.x.0 as f32, self.y.0 as f32)
+    }
+
+    /// Moyenne d'un ensemble de points, `None` si l'ensemble est vide
+    pub fn centroid(points: &[Point]) -> Option<Point> {
+        if points.is_empty() {
+            return None;
+        }
+        let sum = points
+            .iter()
+            .fold(Vector::ZERO, |sum, point| sum + point.to_vector());
+        Some(Point::ORIGIN + sum / points.len() as f64)
+    }
+}
+
+impl From<&PolarPoint> for Point {
+    #[inline]
+    fn from(point: &PolarPoint) -> Self {
+        Self::from_polar(point.distance, point.angle)
+    }
+}
+
+impl From<PolarPoint> for Point {
+    #[inline]
+    fn from(point: PolarPoint) -> Self {
+        Self::from(&point)
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vector) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Vector) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+/// Un déplacement dans le plan
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vector {
+    pub x: Meters,
+    pub y: Meters,
+}
+
+impl Vector {
+    pub const ZERO: Vector = Vector::new(Meters(0.0), Meters(0.0));
+
+    #[inline]
+    pub const fn new(x: Meters, y: Meters) -> Self {
+        Self { x, y }
+    }
+
+    #[inline]
+    pub fn from_polar(norm: Meters, angle: Rad) -> Self {
+        Point::from_polar(norm, angle).to_vector()
+    }
+
+    #[inline]
+    pub fn norm(&self) -> Meters {
+        Meters(self.x.0.hypot(self.y.0))
+    }
+
+    /// Angle du vecteur depuis l'axe x, dans ]-pi, pi]
+    #[inline]
+    pub fn angle(&self) -> Rad {
+        Rad::atan2(self.y.0, self.x.0)
+    }
+
+    /// Produit scalaire, en m²
+    #[inline]
+    pub fn dot(&self, other: Vector) -> f64 {
+        self.x.0 * other.x.0 + self.y.0 * other.y.0
+    }
+
+    /// Produit vectoriel (composante z), en m²
+    #[inline]
+    pub fn cross(&self, other: Vector) -> f64 {
+        self.x.0 * other.y.0 - self.y.0 * other.x.0
+    }
+
+    pub fn rotate(&self, angle: Rad) -> Vector {
+        let (sin, cos) = (angle.sin(), angle.cos());
+        Vector::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+}
+
+impl Add for Vector {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<f64> for Vector {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Div<f64> for Vector {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self::Output {
+        Self::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+/// Une droite sous forme normale de Hesse : `x cos(angle) + y sin(angle) = distance`.
+/// Les champs sont privés pour garantir la convention `distance >= 0`, `angle` dans [0, 2pi[.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Line {
+    distance: Meters,
+    angle: Rad,
+}
+
+impl Line {
+    /// Accepte n'importe quelle distance (y compris négative) et n'importe quel angle
+    pub fn new(distance: Meters, angle: Rad) -> Self {
+        let (distance, angle) = if distance < Meters(0.0) {
+            (-distance, angle + Rad::HALF_TURN)
+        } else {
+            (distance, angle)
+        };
+        let mut angle = wrap_angle(angle);
+        if distance == Meters(0.0) && angle >= Rad::HALF_TURN {
+            // une droite passant par l'origine a deux normales possibles, on garde celle dans [0, pi[
+            angle -= Rad::HALF_TURN;
+        }
+        Self { distance, angle }
+    }
+
+    /// Droite passant par deux points, `None` si les points sont confondus
+    pub fn through_points(a: Point, b: Point) -> Option<Self> {
+        let direction = b - a;
+        if direction == Vector::ZERO {
+            return None;
+        }
+        let normal_angle = direction.angle() + Rad::QUARTER_TURN;
+        let normal = Vector::from_polar(Meters(1.0), normal_angle);
+        Some(Self::new(Meters(a.to_vector().dot(normal)), normal_angle))
+    }
+
+    /// Distance (positive) de l'origine à la droite
+    #[inline]
+    pub fn distance(&self) -> Meters {
+        self.distance
+    }
+
+    /// Angle de la normale à la droite, dans [0, 2pi[
+    #[inline]
+    pub fn angle(&self) -> Rad {
+        self.angle
+    }
+
+    /// Angle de la direction de la droite (normale tournée de 90°)
+    #[inline]
+    pub fn direction(&self) -> Rad {
+        wrap_angle(self.angle + Rad::QUARTER_TURN)
+    }
+
+    /// Projeté orthogonal de l'origine sur la droite
+    #[inline]
+    pub fn foot(&self) -> Point {
+        Point::from_polar(self.distance, self.angle)
+    }
+
+    /// Distance signée d'un point à la droite :
+    /// positive du côté opposé à l'origine, négative du côté de l'origine
+    #[inline]
+    pub fn signed_distance_to(&self, point: Point) -> Meters {
+        point.x * self.angle.cos() + point.y * self.angle.sin() - self.distance
+    }
+
+    #[inline]
+    pub fn distance_to(&self, point: Point) -> Meters {
+        self.signed_distance_to(point).abs()
+    }
+
+    /// Projeté orthogonal d'un point sur la droite
+    pub fn project(&self, point: Point) -> Point {
+        point - Vector::from_polar(self.signed_distance_to(point), self.angle)
+    }
+
+    /// Angle aigu entre les deux droites, dans [0, pi/2]
+    pub fn angle_with(&self, other: &Self) -> Rad {
+        let alpha = (self.angle - other.angle)
+            .val()
+            .rem_euclid(Rad::HALF_TURN.val());
+        Rad::new(alpha.min(Rad::HALF_TURN.val() - alpha))
+    }
+
+    #[inline]
+    pub fn is_parallel_with(&self, other: &Self, tolerance: Rad) -> bool {
+        self.angle_with(other) <= tolerance
+    }
+
+    #[inline]
+    pub fn is_perpendicular_with(&self, other: &Self, tolerance: Rad) -> bool {
+        (self.angle_with(other) - Rad::QUARTER_TURN).mag() <= tolerance
+    }
+
+    /// Point d'intersection des deux droites (règle de Cramer),
+    /// `None` si elles sont parallèles
+    pub fn intersect(&self, other: &Self) -> Option<Point> {
+        if self.is_parallel_with(other, Rad::new(PARALLEL_EXACT_TOLERANCE)) {
+            return None;
+        }
+        let determinant = (other.angle - self.angle).sin();
+        Some(Point::new(
+            (self.distance * other.angle.sin() - other.distance * self.angle.sin()) / determinant,
+            (other.distance * self.angle.cos() - self.distance * other.angle.cos()) / determinant,
+        ))
+    }
+
+    /// Droite parallèle décalée de `offset` le long de la normale,
+    /// un décalage positif l'éloigne de l'origine
+    #[inline]
+    pub fn parallel_at(&self, offset: Meters) -> Self {
+        Self::new(self.distance + offset, self.angle)
+    }
+
+    /// Image de la droite par un déplacement
+    pub fn transform(&self, transform: &Transform) -> Self {
+        let foot = transform.apply(self.foot());
+        let angle = self.angle + transform.rotation;
+        Self::new(foot.x * angle.cos() + foot.y * angle.sin(), angle)
+    }
+}
+
+impl From<PolarLine> for Line {
+    #[inline]
+    fn from(line: PolarLine) -> Self {
+        Self::new(line.distance, line.angle)
+    }
+}
+
+impl From<Line> for PolarLine {
+    #[inline]
+    fn from(line: Line) -> Self {
+        PolarLine {
+            distance: line.distance,
+            angle: line.angle,
+        }
+    }
+}
+
+/// Moyenne pondérée de droites proches les unes des autres.
+///
+/// Une même droite s'écrit avec un angle à 2pi près, ou avec la normale opposée si elle passe
+/// près de l'origine : chaque droite ajoutée est donc d'abord exprimée par rapport à la référence.
+#[derive(Debug, Clone, Copy)]
+pub struct LineMean {
+    reference: Line,
+    distance_sum: f64,
+    angle_sum: f64,
+    weight_sum: f64,
+}
+
+impl LineMean {
+    #[inline]
+    pub fn new(reference: Line) -> Self {
+        Self {
+            reference,
+            distance_sum: 0.0,
+            angle_sum: 0.0,
+            weight_sum: 0.0,
+        }
+    }
+
+    pub fn add(&mut self, line: Line, weight: f64) {
+        let mut offset = wrap_angle_signed(line.angle - self.reference.angle);
+        let mut distance = line.distance;
+        if offset.mag() > Rad::QUARTER_TURN {
+            offset = wrap_angle_signed(offset + Rad::HALF_TURN);
+            distance = -distance;
+        }
+        self.distance_sum += distance.0 * weight;
+        self.angle_sum += offset.val() * weight;
+        self.weight_sum += weight;
+    }
+
+    /// La référence si aucune droite n'a été ajoutée
+    pub fn mean(&self) -> Line {
+        if self.weight_sum == 0.0 {
+            return self.reference;
+        }
+        Line::new(
+            Meters(self.distance_sum / self.weight_sum),
+            self.reference.angle + Rad::new(self.angle_sum / self.weight_sum),
+        )
+    }
+}
+
+/// Un segment de droite
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Segment {
+    #[inline]
+    pub const fn new(start: Point, end: Point) -> Self {
+        Self { start, end }
+    }
+
+    #[inline]
+    pub fn length(&self) -> Meters {
+        self.start.distance_to(self.end)
+    }
+
+    #[inline]
+    pub fn midpoint(&self) -> Point {
+        self.start + (self.end - self.start) / 2.0
+    }
+
+    /// Droite portant le segment, `None` si le segment est réduit à un point
+    #[inline]
+    pub fn line(&self) -> Option<Line> {
+        Line::through_points(self.start, self.end)
+    }
+
+    /// Distance d'un point au point le plus proche du segment
+    pub fn distance_to(&self, point: Point) -> Meters {
+        let direction = self.end - self.start;
+        let squared_length = direction.dot(direction);
+        if squared_length == 0.0 {
+            return self.start.distance_to(point);
+        }
+        let t = ((point - self.start).dot(direction) / squared_length).clamp(0.0, 1.0);
+        (self.start + direction * t).distance_to(point)
+    }
+}
+
+/// Déplacement rigide du plan : une rotation autour de l'origine suivie d'une translation.
+///
+/// Un déplacement représente aussi la pose d'un repère dans un autre : `apply` passe alors
+/// des coordonnées du repère local à celles du repère parent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub translation: Vector,
+    pub rotation: Rad,
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        translation: Vector::ZERO,
+        rotation: Rad::ZERO,
+    };
+
+    #[inline]
+    pub const fn new(translation: Vector, rotation: Rad) -> Self {
+        Self {
+            translation,
+            rotation,
+        }
+    }
+
+    #[inline]
+    pub fn apply(&self, point: Point) -> Point {
+        Point::ORIGIN + self.apply_vector(point.to_vector()) + self.translation
+    }
+
+    /// Les vecteurs ne sont que tournés
+    #[inline]
+    pub fn apply_vector(&self, vector: Vector) -> Vector {
+        vector.rotate(self.rotation)
+    }
+
+    /// Déplacement réciproque : `t.inverse().apply(t.apply(p)) == p`
+    pub fn inverse(&self) -> Self {
+        Self::new(
+            -self.translation.rotate(-self.rotation),
+            wrap_angle_signed(-self.rotation),
+        )
+    }
+
+    /// Composition : `a.then(&b)` applique `a` puis `b`
+    pub fn then(&self, next: &Self) -> Self {
+        Self::new(
+            next.apply_vector(self.translation) + next.translation,
+            wrap_angle_signed(self.rotation + next.rotation),
+        )
+    }
+}
+
+/// `a * b` applique `b` puis `a`, comme pour la composition de poses `parent_local = parent_enfant * enfant_local`
+impl Mul for Transform {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        rhs.then(&self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const APPROX_LIMIT: f64 = 1e-9;
+
+    fn angles() -> impl Iterator<Item = Rad> {
+        (-36..=36).map(|i| Deg::new(f64::from(i) * 10.0 + 0.5).rad())
+    }
+
+    fn assert_meters(a: Meters, b: Meters) {
+        assert!((a.0 - b.0).abs() < APPROX_LIMIT, "{:?} ~!= {:?}", a, b);
+    }
+
+    fn assert_rad(a: Rad, b: Rad) {
+        let diff = wrap_angle_signed(a - b);
+        assert!(diff.mag() < Rad::new(APPROX_LIMIT), "{} ~!= {}", a, b);
+    }
+
+    fn assert_point(a: Point, b: Point) {
+        assert!(
+            a.distance_to(b) < Meters(APPROX_LIMIT),
+            "{:?} ~!= {:?}",
+            a,
+            b
+        );
+    }
+
+    fn assert_line(a: Line, b: Line) {
+        assert_meters(a.distance(), b.distance());
+        assert_rad(a.angle(), b.angle());
+    }
+
+    #[test]
+    fn test_wrap_angle() {
+        for angle in angles() {
+            let wrapped = wrap_angle(angle);
+            assert!(Rad::ZERO <= wrapped && wrapped < Rad::FULL_TURN);
+            assert_rad(wrapped, angle);
+            let signed = wrap_angle_signed(angle);
+            assert!(-Rad::HALF_TURN < signed && signed <= Rad::HALF_TURN);
+            assert_rad(signed, angle);
+        }
+        assert_eq!(wrap_angle(Rad::new(-1e-17)), Rad::ZERO);
+    }
+
+    #[test]
+    fn test_point_polar_round_trip() {
+        for angle in angles() {
+            let point = Point::from_polar(Meters(1.5), angle);
+            let polar = point.to_polar_point();
+            assert_meters(polar.distance, Meters(1.5));
+            assert_rad(polar.angle, angle);
+            assert_point(Point::from(polar), point);
+        }
+        assert_eq!(Point::ORIGIN.to_polar_point().angle, Rad::ZERO);
+    }
+
+    #[test]
+    fn test_vector_operations() {
+        let a = Vector::new(Meters(1.0), Meters(2.0));
+        let b = Vector::new(Meters(-3.0), Meters(0.5));
+        assert_eq!(a + b - b, a);
+        assert_eq!(-a + a, Vector::ZERO);
+        assert_eq!(a * 2.0 / 2.0, a);
+        assert_eq!(a.dot(b), -2.0);
+        assert_eq!(a.cross(b), 6.5);
+        for angle in angles() {
+            let rotated = a.rotate(angle);
+            assert_meters(rotated.norm(), a.norm());
+            assert_rad(rotated.angle() - a.angle(), angle);
+        }
+        let centroid = Point::centroid(&[
+            Point::new(Meters(0.0), Meters(0.0)),
+            Point::new(Meters(2.0), Meters(0.0)),
+            Point::new(Meters(1.0), Meters(3.0)),
+        ]);
+        assert_point(centroid.unwrap(), Point::new(Meters(1.0), Meters(1.0)));
+        assert!(Point::centroid(&[]).is_none());
+    }
+
+    #[test]
+    fn test_line_normalisation() {
+        for angle in angles() {
+            let line = Line::new(Meters(-2.0), angle);
+            assert_meters(line.distance(), Meters(2.0));
+            assert!(Rad::ZERO <= line.angle() && line.angle() < Rad::FULL_TURN);
+            assert_rad(line.angle(), angle + Rad::HALF_TURN);
+            // les deux écritures représentent la même droite
+            assert_line(line, Line::new(Meters(2.0), angle + Rad::HALF_TURN));
+
+            let through_origin = Line::new(Meters(0.0), angle);
+            assert!(through_origin.angle() < Rad::HALF_TURN);
+        }
+    }
+
+    #[test]
+    fn test_line_from_both_polar_conventions() {
+        let negative_distance = PolarLine {
+            distance: Meters(-0.5),
+            angle: Rad::new(0.3),
+        };
+        let big_angle = PolarLine {
+            distance: Meters(0.5),
+            angle: Rad::new(0.3) + Rad::HALF_TURN,
+        };
+        assert_line(Line::from(negative_distance), Line::from(big_angle));
+        let back = PolarLine::from(Line::from(negative_distance));
+        assert_meters(back.distance, Meters(0.5));
+    }
+
+    #[test]
+    fn test_line_through_points() {
+        for angle in angles() {
+            let a = Point::from_polar(Meters(1.0), angle);
+            let b = a + Vector::from_polar(Meters(0.7), angle + Rad::new(1.0));
+            let line = Line::through_points(a, b).unwrap();
+            assert_meters(line.distance_to(a), Meters(0.0));
+            assert_meters(line.distance_to(b), Meters(0.0));
+            assert!(line.distance() >= Meters(0.0));
+        }
+        let a = Point::new(Meters(1.0), Meters(1.0));
+        assert!(Line::through_points(a, a).is_none());
+    }
+
+    #[test]
+    fn test_line_signed_distance_and_projection() {
+        let line = Line::new(Meters(2.0), Rad::QUARTER_TURN); // y = 2
+        let outside = Point::new(Meters(5.0), Meters(3.0));
+        let inside = Point::new(Meters(-1.0), Meters(0.5));
+        assert_meters(line.signed_distance_to(outside), Meters(1.0));
+        assert_meters(line.signed_distance_to(inside), Meters(-1.5));
+        assert_meters(line.signed_distance_to(Point::ORIGIN), -line.distance());
+        assert_point(line.project(outside), Point::new(Meters(5.0), Meters(2.0)));
+        assert_point(line.foot(), Point::new(Meters(0.0), Meters(2.0)));
+        assert_rad(line.direction(), Rad::HALF_TURN);
+    }
+
+    #[test]
+    fn test_line_angles() {
+        for angle in angles() {
+            let line = Line::new(Meters(1.0), angle);
+            let parallel = Line::new(Meters(-3.0), angle);
+            let perpendicular = Line::new(Meters(0.5), angle + Rad::QUARTER_TURN);
+            assert_rad(line.angle_with(&parallel), Rad::ZERO);
+            assert_rad(line.angle_with(&perpendicular), Rad::QUARTER_TURN);
+            assert!(line.is_parallel_with(&parallel, Rad::new(0.01)));
+            assert!(line.is_perpendicular_with(&perpendicular, Rad::new(0.01)));
+            assert!(!line.is_parallel_with(&perpendicular, Rad::new(0.01)));
+            let tilted = Line::new(Meters(1.0), angle + Rad::new(0.3));
+            assert_rad(line.angle_with(&tilted), Rad::new(0.3));
+            assert_rad(tilted.angle_with(&line), Rad::new(0.3));
+        }
+    }
+
+    #[test]
+    fn test_line_intersection() {
+        for angle in angles() {
+            let point = Point::from_polar(Meters(1.3), angle * 0.7);
+            let line1 = Line::through_points(point, point + Vector::from_polar(Meters(1.0), angle))
+                .unwrap();
+            let line2 = Line::through_points(
+                point,
+                point + Vector::from_polar(Meters(1.0), angle + Rad::new(1.2)),
+            )
+            .unwrap();
+            assert_point(line1.intersect(&line2).unwrap(), point);
+            assert_point(line2.intersect(&line1).unwrap(), point);
+            assert!(line1.intersect(&line1.parallel_at(Meters(0.4))).is_none());
+        }
+    }
+
+    #[test]
+    fn test_line_parallel_at() {
+        let line = Line::new(Meters(1.0), Rad::new(0.4));
+        assert_line(
+            line.parallel_at(Meters(0.5)),
+            Line::new(Meters(1.5), Rad::new(0.4)),
+        );
+        // la droite passe de l'autre côté de l'origine
+        let other_side = line.parallel_at(Meters(-1.82));
+        assert_meters(other_side.distance(), Meters(0.82));
+        assert_rad(other_side.angle(), Rad::new(0.4) + Rad::HALF_TURN);
+        assert_meters(line.distance_to(other_side.foot()), Meters(1.82));
+    }
+
+    #[test]
+    fn test_line_mean() {
+        let reference = Line::new(Meters(1.0), Rad::new(0.01));
+        let mut mean = LineMean::new(reference);
+        assert_line(mean.mean(), reference);
+        mean.add(Line::new(Meters(1.2), Rad::new(-0.03)), 1.0);
+        mean.add(Line::new(Meters(1.0), Rad::new(0.01)), 3.0);
+        // de part et d'autre de l'angle nul : pas de moyenne entre 0 et 2pi
+        assert_line(mean.mean(), Line::new(Meters(1.05), Rad::ZERO));
+
+        // droites passant près de l'origine, normales opposées
+        let mut mean = LineMean::new(Line::new(Meters(0.02), Rad::new(0.5)));
+        mean.add(Line::new(Meters(0.02), Rad::new(0.5)), 1.0);
+        mean.add(Line::new(Meters(0.02), Rad::new(0.5) + Rad::HALF_TURN), 1.0);
+        assert_line(mean.mean(), Line::new(Meters(0.0), Rad::new(0.5)));
+    }
+
+    #[test]
+    fn test_segment() {
+        let segment = Segment::new(
+            Point::new(Meters(0.0), Meters(0.0)),
+            Point::new(Meters(2.0), Meters(0.0)),
+        );
+        assert_meters(segment.length(), Meters(2.0));
+        assert_point(segment.midpoint(), Point::new(Meters(1.0), Meters(0.0)));
+        assert_meters(
+            segment.distance_to(Point::new(Meters(1.0), Meters(-0.5))),
+            Meters(0.5),
+        );
+        assert_meters(
+            segment.distance_to(Point::new(Meters(5.0), Meters(4.0))),
+            Meters(5.0),
+        );
+        assert_line(
+            segment.line().unwrap(),
+            Line::new(Meters(0.0), Rad::QUARTER_TURN),
+        );
+        let empty = Segment::new(segment.start, segment.start);
+        assert!(empty.line().is_none());
+        assert_meters(empty.distance_to(segment.end), Meters(2.0));
+    }
+
+    #[test]
+    fn test_transform_inverse_and_composition() {
+        let point = Point::new(Meters(0.3), Meters(-1.1));
+        for angle in angles() {
+            let a = Transform::new(Vector::new(Meters(1.0), Meters(-0.5)), angle);
+            let b = Transform::new(Vector::new(Meters(-0.2), Meters(2.0)), angle * 0.3);
+
+            assert_point(a.inverse().apply(a.apply(point)), point);
+            assert_point(a.apply(a.inverse().apply(point)), point);
+
+            let composed = a.then(&b);
+            assert_point(composed.apply(point), b.apply(a.apply(point)));
+            assert_point((b * a).apply(point), composed.apply(point));
+
+            let identity = a * a.inverse();
+            assert_point(identity.apply(point), point);
+            assert_rad(identity.rotation, Rad::ZERO);
+
+            // les distances sont conservées
+            let other = Point::new(Meters(-2.0), Meters(0.7));
+            assert_meters(
+                a.apply(point).distance_to(a.apply(other)),
+                point.distance_to(other),
+            );
+        }
+        assert_point(Transform::IDENTITY.apply(point), point);
+    }
+
+    #[test]
+    fn test_line_transform() {
+        for angle in angles() {
+            let transform = Transform::new(Vector::new(Meters(0.4), Meters(1.5)), angle);
+            let a = Point::new(Meters(1.0), Meters(0.2));
+            let b = Point::new(Meters(-0.5), Meters(2.0));
+            let line = Line::through_points(a, b).unwrap();
+            let moved = line.transform(&transform);
+            assert_meters(moved.distance_to(transform.apply(a)), Meters(0.0));
+            assert_meters(moved.distance_to(transform.apply(b)), Meters(0.0));
+            assert_line(moved.transform(&transform.inverse()), line);
+        }
+    }
+}
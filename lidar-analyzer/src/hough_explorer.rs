@@ -0,0 +1,204 @@
+//! Explorateur de l'accumulateur de Hough : relance l'analyse d'une capture quand les seuils
+//! (`HoughConfig`) changent et retrouve les points qui soutiennent le pic choisi.
+//!
+//! Ce module ne contient que l'état de l'explorateur, pour pouvoir le tester sans écran.
+//! La fenêtre est dans `basic_viewport` (feature `viewer`) et se lance avec
+//! `cargo run --features viewer --bin hough-explorer -- <capture>`.
+
+use crate::analyze::*;
+use crate::dataset::Capture;
+use crate::geometry::{Line, Point};
+
+/// un clic sélectionne le pic le plus proche s'il est à moins de ce nombre de cases
+const SELECT_RADIUS: f64 = 15.0;
+
+#[derive(Debug)]
+pub struct HoughExplorer {
+    capture: Capture,
+    analysis: WallsAnalysis,
+    selected: Option<usize>,
+}
+
+impl HoughExplorer {
+    pub fn new(capture: Capture, config: HoughConfig) -> Self {
+        let analysis = WallsAnalysis::run_with(&capture.points, &config);
+        Self {
+            capture,
+            analysis,
+            selected: None,
+        }
+    }
+
+    #[inline]
+    pub fn capture(&self) -> &Capture {
+        &self.capture
+    }
+
+    #[inline]
+    pub fn config(&self) -> &HoughConfig {
+        &self.analysis.config
+    }
+
+    #[inline]
+    pub fn analysis(&self) -> &WallsAnalysis {
+        &self.analysis
+    }
+
+    /// Pics de l'accumulateur, triés par poids décroissant
+    #[inline]
+    pub fn peaks(&self) -> &[HoughLine] {
+        &self.analysis.peaks
+    }
+
+    #[inline]
+    pub fn selected(&self) -> Option<&HoughLine> {
+        self.peaks().get(self.selected?)
+    }
+
+    /// Relance l'analyse si `config` est différent de la configuration actuelle.
+    /// La sélection passe au nouveau pic le plus proche de l'ancien, s'il y en a un.
+    /// Renvoit `true` si l'analyse a été relancée.
+    pub fn set_config(&mut self, config: HoughConfig) -> bool {
+        if config == self.analysis.config {
+            return false;
+        }
+        let selected_cell = self.selected().and_then(|peak| accumulator_cell(peak.line));
+        self.analysis = WallsAnalysis::run_with(&self.capture.points, &config);
+        self.selected = selected_cell.and_then(|cell| self.nearest_peak(cell));
+        true
+    }
+
+    /// Sélectionne le pic le plus proche de la case `(distance, angle)` de l'accumulateur,
+    /// aucun s'il n'y a pas de pic à moins de `SELECT_RADIUS` cases
+    pub fn select_near(&mut self, cell: (usize, usize)) -> Option<&HoughLine> {
+        self.selected = self.nearest_peak(cell);
+        self.selected()
+    }
+
+    #[inline]
+    pub fn clear_selection(&mut self) {
+        self.selected = None;
+    }
+
+    fn nearest_peak(&self, (distance_case, angle_case): (usize, usize)) -> Option<usize> {
+        self.peaks()
+            .iter()
+            .enumerate()
+            .filter_map(|(n_peak, peak)| {
+                let (peak_distance, peak_angle) = accumulator_cell(peak.line)?;
+                let gap = (peak_distance as f64 - distance_case as f64)
+                    .hypot(peak_angle as f64 - angle_case as f64);
+                (gap <= SELECT_RADIUS).then_some((n_peak, gap))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(n_peak, _)| n_peak)
+    }
+
+    /// Indices des points de la capture qui soutiennent la droite du pic sélectionné,
+    /// avec la même tolérance que `LineSupport::measure`
+    pub fn supporting_points(&self) -> Vec<usize> {
+        let Some(peak) = self.selected() else {
+            return Vec::new();
+        };
+        let line = Line::from(peak.line);
+        self.capture
+            .points
+            .iter()
+            .enumerate()
+            .filter(|(_, point)| point.point.distance <= LIDAR_DISTANCE_MAX)
+            .filter(|(_, point)| {
+                line.distance_to(Point::from(&point.point)) <= SUPPORT_DISTANCE_TOLERANCE
+            })
+            .map(|(n_point, _)| n_point)
+            .collect()
+    }
+
+    /// Accumulateur en niveaux de gris (voir `accumulator_pixels`) et sa taille `[angles, distances]`
+    pub fn heatmap(&self) -> ([usize; 2], Vec<u8>) {
+        let accumulator = &self.analysis.accumulator;
+        (
+            [accumulator[0].len(), accumulator.len()],
+            accumulator_pixels(accumulator),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataset::{default_captures_dir, load_captures};
+
+    fn explorer() -> HoughExplorer {
+        let capture = load_captures(&default_captures_dir())
+            .unwrap()
+            .into_iter()
+            .next()
+            .expect("aucune capture");
+        HoughExplorer::new(capture, HoughConfig::default())
+    }
+
+    #[test]
+    fn test_select_peak_and_supporting_points() {
+        let mut explorer = explorer();
+        assert!(!explorer.peaks().is_empty());
+        assert!(explorer.supporting_points().is_empty());
+
+        let strongest = explorer.peaks()[0];
+        let (distance_case, angle_case) = accumulator_cell(strongest.line).unwrap();
+        let selected = explorer
+            .select_near((distance_case + 2, angle_case))
+            .unwrap();
+        assert_eq!(selected.weight, strongest.weight);
+        let supporting = explorer.supporting_points();
+        // le pic compte les points de sa case, ceux autour de la droite sont au moins aussi nombreux
+        assert!(
+            supporting.len() >= usize::from(strongest.weight) / 2,
+            "{}",
+            supporting.len()
+        );
+
+        explorer.clear_selection();
+        assert!(explorer.selected().is_none());
+        assert!(
+            explorer
+                .select_near((distance_case + 100, angle_case))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_set_config_updates_analysis() {
+        let mut explorer = explorer();
+        assert!(explorer.analysis().walls.is_some());
+        let first = explorer.peaks()[0];
+        explorer.select_near(accumulator_cell(first.line).unwrap());
+
+        assert!(!explorer.set_config(HoughConfig::default()));
+        let strict = HoughConfig {
+            min_points_per_line: first.weight,
+            ..HoughConfig::default()
+        };
+        assert!(explorer.set_config(strict));
+        assert_eq!(explorer.config(), &strict);
+        assert!(
+            explorer
+                .peaks()
+                .iter()
+                .all(|peak| peak.weight >= first.weight)
+        );
+        // la sélection suit le pic le plus fort, toujours présent
+        assert_eq!(explorer.selected().unwrap().weight, first.weight);
+
+        let impossible = HoughConfig {
+            min_points_per_line: u16::MAX,
+            ..HoughConfig::default()
+        };
+        explorer.set_config(impossible);
+        assert!(explorer.peaks().is_empty() && explorer.selected().is_none());
+        assert!(explorer.analysis().walls.is_none());
+
+        let ([width, height], pixels) = explorer.heatmap();
+        assert_eq!(pixels.len(), width * height);
+        assert_eq!(pixels.iter().max(), Some(&255));
+    }
+}
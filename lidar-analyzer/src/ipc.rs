@@ -0,0 +1,317 @@
+//! Publication des résultats du lidar aux autres programmes du robot (stratégie, moteurs).
+//!
+//! Le programme `lidar-analyzer` écoute sur une socket Unix, par défaut `/tmp/lidar-analyzer.sock`
+//! (modifiable avec la variable d'environnement `LIDAR_ANALYZER_SOCKET`).
+//! Chaque client connecté reçoit un message par tour de lidar, il n'y a rien à envoyer.
+//! Un client trop lent pour recevoir un message est déconnecté.
+//!
+//! Un message est un `LidarMessage` sérialisé en JSON (serde), sur une seule ligne terminée par `\n` :
+//!
+//! ```text
+//! {"scan":42,"time":{"secs":8,"nanos":400000000},"pose":{"position":{"x":0.4,"y":0.1234},"heading":0.5},"nearest_wall":{"distance":0.41,"angle":3.0},"robots":[{"position":{"distance":0.8,"angle":1.0472},"width":0.18}],"error":null}
+//! ```
+//!
+//! | champ | description |
+//! | --- | --- |
+//! | `scan` | numéro du tour de lidar depuis le démarrage |
+//! | `time` | temps écoulé entre le démarrage et la fin du tour |
+//! | `pose` | `Pose2<Global>` du robot (voir `robot_common::frames`), `null` si elle n'a pas été trouvée |
+//! | `nearest_wall` | point le plus proche du mur le plus proche, en coordonnées polaires dans le repère du lidar, `null` si les murs n'ont pas été trouvés |
+//! | `robots` | autres robots vus (centre de leur face visible) en coordonnées polaires dans le repère du lidar, avec leur largeur apparente, du plus proche au plus lointain |
+//! | `error` | `null`, ou le `FieldError` expliquant pourquoi la pose n'a pas été trouvée |
+//!
+//! Les distances sont en mètres et les angles en radians, le repère du lidar a pour origine le lidar
+//! et son axe x est l'axe 0° du lidar.
+//! Les programmes en Rust peuvent relire les champs avec les types de `robot_common`.
+
+use std::io::{self, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::analyze::{FieldError, RobotPose, WallsAnalysis};
+use crate::geometry::Line;
+use crate::parse::{LidarPoint, PolarPoint};
+use crate::prelude::*;
+use crate::robots::{RobotDetection, detect_robots};
+use robot_common::frames::{Global, Pose2};
+
+/// socket utilisée si la variable d'environnement `LIDAR_ANALYZER_SOCKET` n'est pas définie
+pub const DEFAULT_SOCKET_PATH: &str = "/tmp/lidar-analyzer.sock";
+
+/// temps maximal pour envoyer un message à un client avant de le déconnecter
+const CLIENT_WRITE_TIMEOUT: Duration = Duration::from_millis(5);
+
+/// Chemin de la socket : `LIDAR_ANALYZER_SOCKET` ou `DEFAULT_SOCKET_PATH`
+pub fn socket_path() -> PathBuf {
+    std::env::var_os("LIDAR_ANALYZER_SOCKET")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_SOCKET_PATH))
+}
+
+/// Un autre robot vu par le lidar, tel qu'il est envoyé aux autres programmes
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SeenRobot {
+    /// centre de sa face visible, dans le repère du lidar
+    pub position: PolarPoint,
+    /// largeur apparente
+    pub width: Meters,
+}
+
+impl From<&RobotDetection> for SeenRobot {
+    fn from(robot: &RobotDetection) -> Self {
+        Self {
+            position: robot.position.to_polar_point(),
+            width: robot.width,
+        }
+    }
+}
+
+/// Résultat de l'analyse d'un tour de lidar, envoyé aux autres programmes
+#[derive(Debug, serde::Serialize)]
+pub struct LidarMessage {
+    pub scan: u64,
+    /// temps écoulé depuis le démarrage
+    pub time: Duration,
+    pub pose: Option<Pose2<Global>>,
+    pub nearest_wall: Option<PolarPoint>,
+    pub robots: Vec<SeenRobot>,
+    pub error: Option<FieldError>,
+}
+
+impl LidarMessage {
+    /// Localise le robot et les autres robots dans un tour complet de lidar
+    #[inline]
+    pub fn from_scan(scan: u64, time: Duration, points: &[LidarPoint]) -> Self {
+        Self::from_analysis(scan, time, points, &WallsAnalysis::run(points))
+    }
+
+    /// Comme `from_scan`, quand les murs ont déjà été cherchés
+    pub fn from_analysis(
+        scan: u64,
+        time: Duration,
+        points: &[LidarPoint],
+        analysis: &WallsAnalysis,
+    ) -> Self {
+        let mut message = Self {
+            scan,
+            time,
+            pose: None,
+            nearest_wall: None,
+            robots: Vec::new(),
+            error: None,
+        };
+        match &analysis.walls {
+            Some(walls) => {
+                message.nearest_wall =
+                    Some(Line::from(walls.nearest().line()).foot().to_polar_point());
+                message.robots = detect_robots(points, walls)
+                    .iter()
+                    .map(SeenRobot::from)
+                    .collect();
+                match RobotPose::from_walls(walls) {
+                    Ok(pose) => message.pose = Some(pose.to_pose2()),
+                    Err(err) => message.error = Some(err),
+                }
+            }
+            None => message.error = Some(FieldError::WallsNotFound),
+        }
+        message
+    }
+}
+
+/// Socket Unix sur laquelle sont envoyés les messages à tous les clients connectés
+#[derive(Debug)]
+pub struct Publisher {
+    path: PathBuf,
+    listener: UnixListener,
+    clients: Vec<UnixStream>,
+}
+
+impl Publisher {
+    /// Crée la socket, en remplaçant celle laissée par une exécution précédente
+    pub fn bind(path: &Path) -> io::Result<Self> {
+        match std::fs::remove_file(path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+        let listener = UnixListener::bind(path)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            path: path.to_owned(),
+            listener,
+            clients: Vec::new(),
+        })
+    }
+
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn accept_clients(&mut self) {
+        loop {
+            match self.listener.accept() {
+                Ok((client, _)) => {
+                    let configured = client
+                        .set_nonblocking(false)
+                        .and_then(|_| client.set_write_timeout(Some(CLIENT_WRITE_TIMEOUT)));
+                    match configured {
+                        Ok(()) => {
+                            info!("nouveau client connecté à {}", self.path.display());
+                            self.clients.push(client);
+                        }
+                        Err(err) => warn!("impossible de configurer le nouveau client : {}", err),
+                    }
+                }
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => {
+                    warn!("impossible d'accepter un client : {}", err);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Accepte les nouveaux clients puis leur envoie le message.
+    /// Renvoit le nombre de clients qui l'ont reçu.
+    pub fn publish(&mut self, message: &LidarMessage) -> usize {
+        self.accept_clients();
+        let mut line = match serde_json::to_string(message) {
+            Ok(line) => line,
+            Err(err) => {
+                warn!("impossible de sérialiser le message : {}", err);
+                return self.clients.len();
+            }
+        };
+        line.push('\n');
+        self.clients
+            .retain_mut(|client| match client.write_all(line.as_bytes()) {
+                Ok(()) => true,
+                Err(err) => {
+                    info!("client déconnecté : {}", err);
+                    false
+                }
+            });
+        self.clients.len()
+    }
+}
+
+impl Drop for Publisher {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Point;
+    use crate::simulation::{square_robot, synthetic_scan};
+    use std::f64::consts::FRAC_PI_3;
+    use std::io::{BufRead, BufReader};
+
+    fn example_message() -> LidarMessage {
+        LidarMessage {
+            scan: 42,
+            time: Duration::from_millis(8400),
+            pose: Some(
+                RobotPose {
+                    x: Meters(0.1234),
+                    y: Meters(-0.4),
+                    orientation: Rad::new(0.5),
+                }
+                .to_pose2(),
+            ),
+            nearest_wall: Some(PolarPoint {
+                distance: Meters(0.41),
+                angle: Rad::new(3.0),
+            }),
+            robots: vec![SeenRobot::from(&RobotDetection {
+                position: Point::new(Meters(0.4), Meters(0.69282)),
+                width: Meters(0.18),
+                points: 12,
+            })],
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_message_format() {
+        let json: serde_json::Value = serde_json::to_value(example_message()).unwrap();
+        assert_eq!(json["scan"], 42);
+        assert_eq!(json["time"]["secs"], 8);
+        assert_eq!(json["time"]["nanos"], 400_000_000);
+        assert_eq!(json["pose"]["position"]["x"], 0.4);
+        assert_eq!(json["pose"]["position"]["y"], 0.1234);
+        assert_eq!(json["pose"]["heading"], 0.5);
+        assert_eq!(json["nearest_wall"]["distance"], 0.41);
+        assert_eq!(json["nearest_wall"]["angle"], 3.0);
+        let robot = &json["robots"][0];
+        assert!((robot["position"]["distance"].as_f64().unwrap() - 0.8).abs() < 1e-4);
+        assert!((robot["position"]["angle"].as_f64().unwrap() - FRAC_PI_3).abs() < 1e-4);
+        assert_eq!(robot["width"], 0.18);
+        assert!(json["error"].is_null());
+
+        let failed = LidarMessage {
+            scan: 1,
+            time: Duration::ZERO,
+            pose: None,
+            nearest_wall: None,
+            robots: Vec::new(),
+            error: Some(FieldError::WallsNotFound),
+        };
+        assert_eq!(
+            serde_json::to_string(&failed).unwrap(),
+            "{\"scan\":1,\"time\":{\"secs\":0,\"nanos\":0},\"pose\":null,\"nearest_wall\":null,\
+             \"robots\":[],\"error\":\"WallsNotFound\"}"
+        );
+        let json = serde_json::to_value(FieldError::WrongSideLength {
+            expected: Meters(1.82),
+            found: Meters(1.5),
+        })
+        .unwrap();
+        assert_eq!(json["WrongSideLength"]["found"], 1.5);
+    }
+
+    #[test]
+    fn test_message_from_scan() {
+        let robot = Point::new(Meters(-0.3), Meters(-0.2));
+        let other = Point::new(Meters(0.5), Meters(0.3));
+        let points = synthetic_scan(robot, &square_robot(other, Meters::cm(18.0)));
+        let message = LidarMessage::from_scan(3, Duration::from_millis(300), &points);
+        assert!(message.error.is_none(), "{:?}", message.error);
+        assert!(message.pose.is_some());
+        // mur de longueur le plus proche : y = -0.91 dans le repère du terrain
+        let wall = message.nearest_wall.unwrap();
+        assert!(
+            (wall.distance - Meters(0.71)).abs() < Meters::cm(2.0),
+            "{:?}",
+            wall
+        );
+        assert_eq!(message.robots.len(), 1);
+    }
+
+    #[test]
+    fn test_publish_to_clients() {
+        let path =
+            std::env::temp_dir().join(format!("lidar-analyzer-test-{}.sock", std::process::id()));
+        let mut publisher = Publisher::bind(&path).unwrap();
+        let client = UnixStream::connect(&path).unwrap();
+        assert_eq!(publisher.publish(&example_message()), 1);
+        let mut line = String::new();
+        BufReader::new(&client).read_line(&mut line).unwrap();
+        assert_eq!(
+            line,
+            serde_json::to_string(&example_message()).unwrap() + "\n"
+        );
+
+        drop(client);
+        // le premier envoi après la déconnexion peut encore réussir
+        publisher.publish(&example_message());
+        assert_eq!(publisher.publish(&example_message()), 0);
+
+        drop(publisher);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,22 @@
+//! Lecture des données du lidar, détection des murs du terrain et localisation du robot
+
+pub mod analyze;
+#[cfg(feature = "viewer")]
+pub mod basic_viewport;
+pub mod dataset;
+pub mod geometry;
+pub mod hough_explorer;
+#[cfg(feature = "serde")]
+pub mod ipc;
+pub mod log_manager;
+pub mod metrics;
+pub mod parse;
+pub mod prelude;
+pub mod recording;
+pub mod robots;
+pub mod scan_log;
+/// Unités communes à tous les programmes du robot
+pub use robot_common::units;
+
+#[cfg(test)]
+mod simulation;
@@ -1,56 +1,347 @@
-use std::{error::Error, fs::OpenOptions};
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
 
+use robot_logging::{LogConfig, set_up_logging_with_layer};
 use tracing::field::Visit;
-use tracing::subscriber;
-use tracing_panic::panic_hook;
-use tracing_subscriber::layer::SubscriberExt;
-use tracing_subscriber::{self, Registry, fmt};
-use tracing_subscriber::{EnvFilter, Layer};
+use tracing::span::{Attributes, Id, Record};
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
 
+use crate::metrics::Metrics;
 use crate::prelude::*;
 
+/// Logs du terminal et des fichiers (voir `robot_logging`), envoyés aussi à rerun
 #[inline]
 pub fn set_up_logging(rec: rerun::RecordingStream) -> Result<(), Box<dyn Error>> {
-    std::fs::create_dir_all("log")?;
-    let log_file = OpenOptions::new()
-        .append(true)
-        .create(true)
-        .open("./log/rust-on-rasp.log")?;
-    let subscriber_param = Registry::default()
-        .with(fmt::layer().with_ansi(true))
-        .with(fmt::layer().with_ansi(false).with_writer(log_file))
-        .with(RerunLayer::new(rec))
-        .with(EnvFilter::new("warn,lidar_analyzer=trace")); // remember "-" must be replaced by "_" in the filter
-    subscriber::set_global_default(subscriber_param)?;
-
-    std::panic::set_hook(Box::new(panic_hook));
-    info!("NEW START");
-    Ok(())
+    set_up_logging_with_layer(
+        &LogConfig::new("lidar-analyzer"),
+        RerunLayer::new(rec, rerun_health()),
+    )
+}
+
+/// nombre de messages gardés en attente quand rerun ne les accepte plus,
+/// les plus anciens sont abandonnés au-delà
+const MAX_PENDING_LOGS: usize = 256;
+/// temps entre deux essais de renvoi des messages en attente quand rerun refuse les messages
+const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// État de l'envoi des logs à rerun, partagé avec la reconnexion en arrière-plan (voir `recording`)
+/// et exporté dans les mesures avec `RerunHealth::record_in`.
+#[derive(Debug, Default)]
+pub struct RerunHealth {
+    failing: AtomicBool,
+    dropped: AtomicU64,
+    buffered: AtomicUsize,
+    reconnections: AtomicU64,
+}
+
+impl RerunHealth {
+    pub const fn new() -> Self {
+        Self {
+            failing: AtomicBool::new(false),
+            dropped: AtomicU64::new(0),
+            buffered: AtomicUsize::new(0),
+            reconnections: AtomicU64::new(0),
+        }
+    }
+
+    /// `true` si le dernier envoi à rerun a échoué
+    #[inline]
+    pub fn is_failing(&self) -> bool {
+        self.failing.load(Ordering::Relaxed)
+    }
+
+    /// nombre de messages abandonnés depuis le lancement
+    #[inline]
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// nombre de messages en attente d'être renvoyés
+    #[inline]
+    pub fn buffered(&self) -> usize {
+        self.buffered.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub fn reconnections(&self) -> u64 {
+        self.reconnections.load(Ordering::Relaxed)
+    }
+
+    /// À appeler quand le flux a été reconnecté : les messages en attente sont renvoyés au prochain message
+    pub fn reconnected(&self) {
+        self.reconnections.fetch_add(1, Ordering::Relaxed);
+        self.failing.store(false, Ordering::Relaxed);
+    }
+
+    /// Copie l'état dans les jauges `rerun_failing`, `rerun_buffered_logs`, `rerun_dropped_logs`
+    /// et `rerun_reconnections` de `metrics`
+    pub fn record_in(&self, metrics: &mut Metrics) {
+        metrics.set("rerun_failing", if self.is_failing() { 1.0 } else { 0.0 });
+        metrics.set("rerun_buffered_logs", self.buffered() as f64);
+        metrics.set("rerun_dropped_logs", self.dropped() as f64);
+        metrics.set("rerun_reconnections", self.reconnections() as f64);
+    }
+}
+
+static RERUN_HEALTH: RerunHealth = RerunHealth::new();
+
+/// État de l'envoi des logs du programme à rerun
+#[inline]
+pub fn rerun_health() -> &'static RerunHealth {
+    &RERUN_HEALTH
 }
 
 // Couche de compatibilité tracing/rerun
+//
+// Chaque message est envoyé sous `code/<crate>/<module>/<span racine>/.../<span courant>`,
+// avec les champs des spans dans lesquels il a été émis.
+// À la fermeture d'un span, le temps passé dedans est envoyé sous `<chemin du span>/busy_ms`.
+//
+// Les logs ne doivent jamais arrêter le robot : si rerun refuse un message (ou panique),
+// il est gardé en attente et renvoyé plus tard, et les messages en trop sont abandonnés et comptés.
+
+/// Message prêt à être envoyé à rerun, gardé tel quel tant que l'envoi échoue
+#[derive(Debug, Clone, PartialEq)]
+enum PendingLog {
+    Text {
+        path: String,
+        text: String,
+        level: &'static str,
+        color: Option<(u8, u8, u8)>,
+    },
+    Scalar {
+        path: String,
+        value: f64,
+    },
+}
+
+/// Destination des messages, remplacée dans les tests pour simuler les pannes
+trait LogSink: Send + Sync + 'static {
+    fn send(&self, log: &PendingLog) -> Result<(), Box<dyn Error>>;
+}
+
+impl LogSink for rerun::RecordingStream {
+    fn send(&self, log: &PendingLog) -> Result<(), Box<dyn Error>> {
+        match log {
+            PendingLog::Text {
+                path,
+                text,
+                level,
+                color,
+            } => {
+                let mut text_log = rerun::TextLog::new(text.as_str()).with_level(*level);
+                if let Some(color) = color {
+                    text_log = text_log.with_color(*color);
+                }
+                self.log(path.as_str(), &text_log)?;
+            }
+            PendingLog::Scalar { path, value } => {
+                self.log(path.as_str(), &rerun::Scalars::single(*value))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+thread_local! {
+    /// vrai pendant un envoi, pour ignorer les messages émis par rerun lui-même au lieu de boucler
+    static SENDING: Cell<bool> = const { Cell::new(false) };
+}
+
+#[derive(Debug, Default)]
+struct PendingLogs {
+    logs: VecDeque<PendingLog>,
+    last_failure: Option<Instant>,
+}
 
 #[derive(Debug)]
-struct RerunLayer {
-    rec: rerun::RecordingStream,
+struct RerunLayer<K = rerun::RecordingStream> {
+    sink: K,
+    pending: Mutex<PendingLogs>,
+    health: &'static RerunHealth,
 }
 
-impl RerunLayer {
-    fn new(rec: rerun::RecordingStream) -> Self {
-        Self { rec }
+impl<K: LogSink> RerunLayer<K> {
+    fn new(sink: K, health: &'static RerunHealth) -> Self {
+        Self {
+            sink,
+            pending: Mutex::new(PendingLogs::default()),
+            health,
+        }
     }
+
+    /// Envoie les messages en attente puis `log`, sans jamais paniquer ni bloquer sur rerun
+    fn send(&self, log: PendingLog) {
+        if SENDING.with(|sending| sending.replace(true)) {
+            self.health.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        // un panic pendant un envoi précédent ne doit pas bloquer les suivants
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        pending.logs.push_back(log);
+        if pending.logs.len() > MAX_PENDING_LOGS {
+            pending.logs.pop_front();
+            self.health.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        let retry = !self.health.is_failing()
+            || pending
+                .last_failure
+                .is_none_or(|failure| failure.elapsed() >= RETRY_INTERVAL);
+        if retry {
+            while let Some(log) = pending.logs.front() {
+                let sent = panic::catch_unwind(AssertUnwindSafe(|| self.sink.send(log)));
+                if matches!(sent, Ok(Ok(()))) {
+                    pending.logs.pop_front();
+                } else {
+                    pending.last_failure = Some(Instant::now());
+                    self.health.failing.store(true, Ordering::Relaxed);
+                    break;
+                }
+            }
+            if pending.logs.is_empty() {
+                self.health.failing.store(false, Ordering::Relaxed);
+            }
+        }
+        self.health
+            .buffered
+            .store(pending.logs.len(), Ordering::Relaxed);
+        drop(pending);
+        SENDING.with(|sending| sending.set(false));
+    }
+}
+
+/// Données gardées pour chaque span ouvert, dans ses extensions
+#[derive(Debug)]
+struct SpanTiming {
+    fields: String,
+    opened: Instant,
+    entered: Option<Instant>,
+    /// temps passé dans le span, sans compter les moments où il a été quitté (`.await`...)
+    busy: Duration,
+}
+
+/// Chemin rerun d'un message émis depuis `target` dans les spans `spans` (du plus englobant au plus proche)
+fn entity_path<'a>(target: &'a str, spans: impl Iterator<Item = &'a str>) -> String {
+    let mut path = String::from("code");
+    for part in target.split("::").take(2).chain(spans) {
+        path.push('/');
+        // rerun sépare les parties du chemin par `/` et interprète certains caractères
+        path.extend(part.chars().map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        }));
+    }
+    path
 }
 
-impl<S> Layer<S> for RerunLayer
+/// Contexte des spans au format `span1{champs}:span2{champs}`, comme les logs du terminal
+fn span_context<'a>(spans: impl Iterator<Item = (&'a str, String)>) -> String {
+    spans
+        .map(|(name, fields)| {
+            if fields.is_empty() {
+                name.to_owned()
+            } else {
+                format!("{}{{{}}}", name, fields)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+impl<S, K> Layer<S> for RerunLayer<K>
 where
-    S: tracing::Subscriber,
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+    K: LogSink,
 {
-    fn on_event(
-        &self,
-        event: &tracing::Event<'_>,
-        _ctx: tracing_subscriber::layer::Context<'_, S>,
-    ) {
-        let path_to: Vec<&str> = event.metadata().target().split("::").take(2).collect();
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = RerunLayerVisitor::default();
+        attrs.record(&mut visitor);
+        span.extensions_mut().insert(SpanTiming {
+            fields: visitor.log_text,
+            opened: Instant::now(),
+            entered: None,
+            busy: Duration::ZERO,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        if let Some(timing) = span.extensions_mut().get_mut::<SpanTiming>() {
+            let mut visitor = RerunLayerVisitor {
+                log_text: std::mem::take(&mut timing.fields),
+                ..Default::default()
+            };
+            values.record(&mut visitor);
+            timing.fields = visitor.log_text;
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id)
+            && let Some(timing) = span.extensions_mut().get_mut::<SpanTiming>()
+        {
+            timing.entered = Some(Instant::now());
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id)
+            && let Some(timing) = span.extensions_mut().get_mut::<SpanTiming>()
+            && let Some(entered) = timing.entered.take()
+        {
+            timing.busy += entered.elapsed();
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some((fields, busy, total)) = span
+            .extensions()
+            .get::<SpanTiming>()
+            .map(|timing| (timing.fields.clone(), timing.busy, timing.opened.elapsed()))
+        else {
+            return;
+        };
+        let path = entity_path(
+            span.metadata().target(),
+            span.scope().from_root().map(|span| span.name()),
+        );
+        self.send(PendingLog::Text {
+            path: path.clone(),
+            text: format!(
+                "fin de {}{{{}}} : actif {:?} sur {:?}",
+                span.name(),
+                fields,
+                busy,
+                total
+            ),
+            level: "TRACE",
+            color: None,
+        });
+        self.send(PendingLog::Scalar {
+            path: path + "/busy_ms",
+            value: busy.as_secs_f64() * 1000.0,
+        });
+    }
+
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
         let mut log_level = event.metadata().level().as_str();
         let mut visitor = RerunLayerVisitor::default();
         event.record(&mut visitor);
@@ -62,27 +353,35 @@ where
             log_level = "CRITICAL"
         }
 
-        if *event.metadata().level() >= tracing::Level::INFO
-            && visitor.message.starts_with("perf")
-        {
-            log_level = "PERF"
-        }
-
-        let mut text_log = rerun::TextLog::new(format!(
-            "{} | from {} | env {}.",
-            visitor.message,
-            event.metadata().name(),
-            visitor.log_text
-        ))
-        .with_level(log_level);
-
-        if log_level == "CRITICAL" {
-            text_log = text_log.with_color(colors::DARK_RED);
-        }
+        let spans: Vec<_> = ctx
+            .event_scope(event)
+            .map(|scope| scope.from_root().collect())
+            .unwrap_or_default();
+        let path = entity_path(
+            event.metadata().target(),
+            spans.iter().map(|span| span.name()),
+        );
+        let context = span_context(spans.iter().map(|span| {
+            let fields = span
+                .extensions()
+                .get::<SpanTiming>()
+                .map(|timing| timing.fields.clone())
+                .unwrap_or_default();
+            (span.name(), fields)
+        }));
 
-        self.rec
-            .log("code/".to_owned() + &path_to.join("/"), &text_log)
-            .unwrap()
+        self.send(PendingLog::Text {
+            path,
+            text: format!(
+                "{} | from {} | env {}. | spans {}",
+                visitor.message,
+                event.metadata().name(),
+                visitor.log_text,
+                context
+            ),
+            level: log_level,
+            color: (log_level == "CRITICAL").then_some(colors::DARK_RED),
+        });
     }
 }
 
@@ -104,3 +403,118 @@ impl Visit for RerunLayerVisitor {
         self.log_text += &format!("{}: {:?}", field.name(), value);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_entity_path() {
+        assert_eq!(
+            entity_path(
+                "lidar_analyzer::parse::sub",
+                ["read", "from_data"].into_iter()
+            ),
+            "code/lidar_analyzer/parse/read/from_data"
+        );
+        assert_eq!(
+            entity_path("lidar_analyzer", ["tour de lidar"].into_iter()),
+            "code/lidar_analyzer/tour_de_lidar"
+        );
+    }
+
+    #[test]
+    fn test_span_context() {
+        assert_eq!(
+            span_context([("run", "points: 12".to_owned()), ("inner", String::new())].into_iter()),
+            "run{points: 12}:inner"
+        );
+    }
+
+    #[test]
+    fn test_layer_with_spans() {
+        let subscriber = tracing_subscriber::Registry::default().with(RerunLayer::new(
+            rerun::RecordingStream::disabled(),
+            Box::leak(Box::default()),
+        ));
+        tracing::subscriber::with_default(subscriber, || {
+            let outer = tracing::info_span!("outer", scan = 1);
+            let _outer = outer.enter();
+            let inner = tracing::debug_span!("inner", late = tracing::field::Empty);
+            inner.record("late", 3);
+            inner.in_scope(|| warn!(value = 2, "dans les spans"));
+        });
+    }
+
+    /// Simule un flux rerun qui refuse les messages ou panique
+    #[derive(Debug, Default)]
+    struct FailingSink {
+        failing: AtomicBool,
+        panicking: AtomicBool,
+        sent: Mutex<Vec<PendingLog>>,
+    }
+
+    impl LogSink for &'static FailingSink {
+        fn send(&self, log: &PendingLog) -> Result<(), Box<dyn Error>> {
+            if self.panicking.load(Ordering::Relaxed) {
+                panic!("flux rerun cassé");
+            }
+            if self.failing.load(Ordering::Relaxed) {
+                return Err("flux rerun déconnecté".into());
+            }
+            self.sent.lock().unwrap().push(log.clone());
+            Ok(())
+        }
+    }
+
+    fn scalar(value: usize) -> PendingLog {
+        PendingLog::Scalar {
+            path: "test".to_owned(),
+            value: value as f64,
+        }
+    }
+
+    #[test]
+    fn test_layer_survives_failing_sink() {
+        let sink: &'static FailingSink = Box::leak(Box::default());
+        let health: &'static RerunHealth = Box::leak(Box::default());
+        let layer = RerunLayer::new(sink, health);
+
+        layer.send(scalar(0));
+        assert!(!health.is_failing());
+        assert_eq!(sink.sent.lock().unwrap().len(), 1);
+
+        sink.panicking.store(true, Ordering::Relaxed);
+        layer.send(scalar(1));
+        assert!(health.is_failing());
+        assert_eq!(health.buffered(), 1);
+
+        sink.panicking.store(false, Ordering::Relaxed);
+        sink.failing.store(true, Ordering::Relaxed);
+        for value in 2..=MAX_PENDING_LOGS + 10 {
+            layer.send(scalar(value));
+        }
+        assert!(health.is_failing());
+        assert_eq!(health.buffered(), MAX_PENDING_LOGS);
+        assert_eq!(health.dropped(), 10);
+
+        sink.failing.store(false, Ordering::Relaxed);
+        health.reconnected();
+        layer.send(scalar(MAX_PENDING_LOGS + 11));
+        assert!(!health.is_failing());
+        assert_eq!(health.buffered(), 0);
+        assert_eq!(health.dropped(), 11);
+        let sent = sink.sent.lock().unwrap();
+        // les messages en attente sont envoyés dans l'ordre, avant le nouveau
+        assert_eq!(sent.len(), 1 + MAX_PENDING_LOGS);
+        assert_eq!(sent[1], scalar(12));
+        assert_eq!(sent[MAX_PENDING_LOGS], scalar(MAX_PENDING_LOGS + 11));
+
+        let mut metrics = Metrics::new();
+        health.record_in(&mut metrics);
+        assert_eq!(metrics.gauge("rerun_failing"), Some(0.0));
+        assert_eq!(metrics.gauge("rerun_dropped_logs"), Some(11.0));
+        assert_eq!(metrics.gauge("rerun_reconnections"), Some(1.0));
+    }
+}
@@ -1,38 +1,105 @@
 use std::error::Error;
-use std::time::Duration;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
-mod analyze;
-mod analyze_tests_data;
-mod parse;
-mod prelude;
-mod units;
-mod log_manager;
+use lidar_analyzer::analyze::WallsAnalysis;
+use lidar_analyzer::ipc::{LidarMessage, Publisher, socket_path};
+use lidar_analyzer::log_manager::{rerun_health, set_up_logging};
+use lidar_analyzer::metrics;
+use lidar_analyzer::parse::{Lidar, LidarError, LidarPoint, ScanAssembler};
+use lidar_analyzer::prelude::*;
+use lidar_analyzer::recording::{Recording, RecordingMode};
+use lidar_analyzer::scan_log::{log_field_model, log_scan};
 
-#[cfg(test)]
-mod complex_viewport;
+/// Lit le lidar en continu, localise le robot à chaque tour et envoie le résultat
+/// aux autres programmes (voir `lidar_analyzer::ipc` pour le format).
+///
+/// Rien n'est envoyé à rerun par défaut, voir `lidar_analyzer::recording` pour les options.
+/// À l'arrêt (Ctrl-C ou SIGTERM), le résumé des mesures de performance est écrit dans les logs.
+/// Une erreur d'envoi à rerun est seulement signalée : elle n'arrête jamais la localisation.
+fn main() -> Result<(), Box<dyn Error>> {
+    let mode = RecordingMode::from_args_and_env(std::env::args().skip(1), RecordingMode::Disabled)?;
+    let mut recording = Recording::new("lidar_analyzer", &mode)?;
+    set_up_logging(recording.stream().clone())?;
+    info!("mode rerun : {:?}", mode);
+    let rec = recording.stream().clone();
+    if let Err(err) = log_field_model(&rec) {
+        warn!("impossible d'envoyer le terrain à rerun : {}", err);
+    }
+    let mut lidar = Lidar::new()?;
+    let mut publisher = Publisher::bind(&socket_path())?;
+    info!("résultats publiés sur {}", publisher.path().display());
 
-use crate::log_manager::set_up_logging;
-use prelude::*;
+    let running = Arc::new(AtomicBool::new(true));
+    let handler_running = running.clone();
+    ctrlc::set_handler(move || handler_running.store(false, Ordering::SeqCst))?;
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let rec = rerun::RecordingStreamBuilder::new("lidar_analyzer").spawn()?;
-    set_up_logging(rec.clone())?;
-    //let lidar = parse::Lidar::new()?;
-    // rec.log("test1", &rerun::Points2D::new([(0., 0.), (1., 1.)])).unwrap();
-    // debug!("test1");
-    // rec.log("test2", &rerun::TextLog::new("test2")).unwrap();
-    // loop {
-    //     let data = lidar.read()?;
-    //     let points = parse::LidarPoint::from_data(data)?;
-    //     let mut points_pos = Vec::with_capacity(points.len());
-    //     for point in points.iter() {
-    //         points_pos.push((point.distance, point.angle));
-    //     }
-    //     info!("got {:?}", points_pos);
-    // }
-    info!("it is working");
-    warn!("ok !");
-    std::thread::sleep(Duration::from_secs(2));
-    analyze::test_call();
+    let start = Instant::now();
+    let mut assembler = ScanAssembler::default();
+    let mut n_scan: u64 = 0;
+    while running.load(Ordering::SeqCst) {
+        let data = match lidar.read() {
+            Ok(data) => data,
+            Err(LidarError::NotEnoughData(_)) => {
+                std::thread::sleep(Duration::from_millis(1));
+                continue;
+            }
+            Err(err) => {
+                metrics::increment("lidar_read_errors");
+                warn!("erreur de lecture du lidar : {:?}", err);
+                continue;
+            }
+        };
+        let points = match LidarPoint::from_data(data) {
+            Ok(points) => points,
+            Err(err) => {
+                metrics::increment("dropped_packets");
+                warn!("paquet du lidar invalide : {}", err);
+                continue;
+            }
+        };
+        for scan in assembler.push(&points) {
+            n_scan += 1;
+            let time = start.elapsed();
+            let scan_start = Instant::now();
+            let analysis = WallsAnalysis::run(&scan);
+            let message = LidarMessage::from_analysis(n_scan, time, &scan, &analysis);
+            if let Some(err) = &message.error {
+                metrics::increment("failed_localisations");
+                debug!("tour {} : robot non localisé ({})", n_scan, err);
+            }
+            metrics::time("publish", || publisher.publish(&message));
+            metrics::record("scan", scan_start.elapsed());
+            metrics::increment("scans");
+            rerun_health().record_in(&mut metrics::global());
+            if rec.is_enabled() {
+                if let Err(err) = metrics::time("log_scan", || {
+                    log_scan(&rec, n_scan, time, &scan, &analysis)
+                }) {
+                    warn!("impossible d'envoyer le tour {} à rerun : {}", n_scan, err);
+                }
+                if let Err(err) = metrics::global().log_to_rerun(&rec) {
+                    warn!("impossible d'envoyer les mesures à rerun : {}", err);
+                }
+            }
+            match recording.rotate_if_needed() {
+                Ok(true) => {
+                    if let Err(err) = log_field_model(&rec) {
+                        warn!("impossible d'envoyer le terrain à rerun : {}", err);
+                    }
+                }
+                Ok(false) => {}
+                Err(err) => warn!("impossible de changer de fichier rerun : {}", err),
+            }
+        }
+    }
+
+    info!(
+        "arrêt après {} tours, {}",
+        n_scan,
+        metrics::global().summary()
+    );
+    rec.flush_blocking();
     Ok(())
 }
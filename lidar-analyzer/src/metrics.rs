@@ -0,0 +1,292 @@
+//! Mesures de performance et compteurs d'événements du programme.
+//!
+//! Les durées sont enregistrées par nom dans des histogrammes (une étape de l'analyse = un nom)
+//! et les événements (paquets rejetés, localisations échouées...) dans des compteurs.
+//! Les états instantanés (messages rerun en attente...) sont gardés dans des jauges.
+//! Les valeurs sont envoyées à rerun comme courbes sous `metrics/` avec `Metrics::log_to_rerun`
+//! et résumées dans les logs avec `Metrics::summary`, par exemple à l'arrêt du programme.
+//!
+//! Un registre global est utilisé pour pouvoir mesurer n'importe quelle fonction sans lui passer de paramètre :
+//! `metrics::time("build_hough_accumulator", || ...)` ou `metrics::increment("dropped_packets")`.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Write as _;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// nombre de tranches de l'histogramme, la tranche `i` contient les durées de `2^i` à `2^(i+1)` µs
+/// (la dernière va au-delà de 16 s)
+const N_BUCKETS: usize = 25;
+
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    count: u64,
+    sum: Duration,
+    min: Duration,
+    max: Duration,
+    buckets: [u64; N_BUCKETS],
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            sum: Duration::ZERO,
+            min: Duration::MAX,
+            max: Duration::ZERO,
+            buckets: [0; N_BUCKETS],
+        }
+    }
+}
+
+impl Histogram {
+    pub fn record(&mut self, duration: Duration) {
+        self.count += 1;
+        self.sum += duration;
+        self.min = self.min.min(duration);
+        self.max = self.max.max(duration);
+        let micros = duration.as_micros().max(1);
+        let bucket = (micros.ilog2() as usize).min(N_BUCKETS - 1);
+        self.buckets[bucket] += 1;
+    }
+
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean(&self) -> Option<Duration> {
+        (self.count > 0).then(|| self.sum / self.count as u32)
+    }
+
+    pub fn min(&self) -> Option<Duration> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        (self.count > 0).then_some(self.max)
+    }
+
+    /// Majorant de la durée sous laquelle se trouvent `quantile` (entre 0 et 1) des mesures,
+    /// à un facteur 2 près à cause des tranches
+    pub fn quantile(&self, quantile: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = ((quantile.clamp(0.0, 1.0) * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bucket, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                let upper = Duration::from_micros(2u64.pow(bucket as u32 + 1));
+                return Some(upper.min(self.max).max(self.min));
+            }
+        }
+        Some(self.max)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Timer {
+    histogram: Histogram,
+    /// somme et nombre des durées depuis le dernier envoi à rerun
+    since_export: (Duration, u32),
+}
+
+#[derive(Debug, Default)]
+pub struct Metrics {
+    timers: BTreeMap<&'static str, Timer>,
+    counters: BTreeMap<&'static str, u64>,
+    gauges: BTreeMap<&'static str, f64>,
+}
+
+impl Metrics {
+    pub const fn new() -> Self {
+        Self {
+            timers: BTreeMap::new(),
+            counters: BTreeMap::new(),
+            gauges: BTreeMap::new(),
+        }
+    }
+
+    pub fn record(&mut self, name: &'static str, duration: Duration) {
+        let timer = self.timers.entry(name).or_default();
+        timer.histogram.record(duration);
+        timer.since_export.0 += duration;
+        timer.since_export.1 += 1;
+    }
+
+    pub fn add(&mut self, name: &'static str, value: u64) {
+        *self.counters.entry(name).or_default() += value;
+    }
+
+    /// Remplace la valeur de la jauge `name`
+    pub fn set(&mut self, name: &'static str, value: f64) {
+        self.gauges.insert(name, value);
+    }
+
+    #[inline]
+    pub fn histogram(&self, name: &str) -> Option<&Histogram> {
+        self.timers.get(name).map(|timer| &timer.histogram)
+    }
+
+    #[inline]
+    pub fn counter(&self, name: &str) -> u64 {
+        self.counters.get(name).copied().unwrap_or(0)
+    }
+
+    #[inline]
+    pub fn gauge(&self, name: &str) -> Option<f64> {
+        self.gauges.get(name).copied()
+    }
+
+    /// Envoie la durée moyenne de chaque étape *en millisecondes* depuis le dernier envoi
+    /// (`metrics/time/<nom>`), la valeur de chaque compteur (`metrics/count/<nom>`)
+    /// et de chaque jauge (`metrics/gauge/<nom>`), sur les timelines en cours du flux.
+    pub fn log_to_rerun(&mut self, rec: &rerun::RecordingStream) -> Result<(), Box<dyn Error>> {
+        for (name, timer) in self.timers.iter_mut() {
+            let (sum, count) = std::mem::take(&mut timer.since_export);
+            if count > 0 {
+                let mean_ms = (sum / count).as_secs_f64() * 1000.0;
+                rec.log(
+                    format!("metrics/time/{}", name),
+                    &rerun::Scalars::single(mean_ms),
+                )?;
+            }
+        }
+        for (name, value) in self.counters.iter() {
+            rec.log(
+                format!("metrics/count/{}", name),
+                &rerun::Scalars::single(*value as f64),
+            )?;
+        }
+        for (name, value) in self.gauges.iter() {
+            rec.log(
+                format!("metrics/gauge/{}", name),
+                &rerun::Scalars::single(*value),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Résumé de toutes les mesures, une ligne par nom
+    pub fn summary(&self) -> String {
+        let mut summary = String::from("résumé des mesures :");
+        // write! sur une String ne peut pas échouer
+        for (name, timer) in self.timers.iter() {
+            let histogram = &timer.histogram;
+            let (Some(mean), Some(min), Some(p50), Some(p95), Some(max)) = (
+                histogram.mean(),
+                histogram.min(),
+                histogram.quantile(0.5),
+                histogram.quantile(0.95),
+                histogram.max(),
+            ) else {
+                continue;
+            };
+            let _ = write!(
+                summary,
+                "\n  {} : {} fois, moyenne {:?}, min {:?}, p50 < {:?}, p95 < {:?}, max {:?}",
+                name,
+                histogram.count(),
+                mean,
+                min,
+                p50,
+                p95,
+                max
+            );
+        }
+        for (name, value) in self.counters.iter() {
+            let _ = write!(summary, "\n  {} : {}", name, value);
+        }
+        for (name, value) in self.gauges.iter() {
+            let _ = write!(summary, "\n  {} : {}", name, value);
+        }
+        summary
+    }
+}
+
+static METRICS: Mutex<Metrics> = Mutex::new(Metrics::new());
+
+/// Registre global des mesures
+#[inline]
+pub fn global() -> MutexGuard<'static, Metrics> {
+    // une mesure interrompue par un panic ne doit pas empêcher les suivantes
+    METRICS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[inline]
+pub fn record(name: &'static str, duration: Duration) {
+    global().record(name, duration);
+}
+
+#[inline]
+pub fn increment(name: &'static str) {
+    global().add(name, 1);
+}
+
+/// Exécute `f` et enregistre sa durée sous `name`
+#[inline]
+pub fn time<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = f();
+    record(name, start.elapsed());
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram() {
+        let mut histogram = Histogram::default();
+        assert!(histogram.mean().is_none() && histogram.quantile(0.5).is_none());
+        for micros in [100, 200, 300, 400, 10_000] {
+            histogram.record(Duration::from_micros(micros));
+        }
+        assert_eq!(histogram.count(), 5);
+        assert_eq!(histogram.mean(), Some(Duration::from_micros(2200)));
+        assert_eq!(histogram.min(), Some(Duration::from_micros(100)));
+        assert_eq!(histogram.max(), Some(Duration::from_micros(10_000)));
+        // 300 µs est dans la tranche [256, 512[ µs
+        assert_eq!(histogram.quantile(0.5), Some(Duration::from_micros(512)));
+        assert_eq!(histogram.quantile(1.0), Some(Duration::from_micros(10_000)));
+        assert_eq!(histogram.quantile(0.0), Some(Duration::from_micros(128)));
+    }
+
+    #[test]
+    fn test_metrics_summary() {
+        let mut metrics = Metrics::new();
+        metrics.record("scan", Duration::from_millis(3));
+        metrics.record("scan", Duration::from_millis(5));
+        metrics.add("dropped_packets", 2);
+        metrics.add("dropped_packets", 1);
+        metrics.set("rerun_buffered_logs", 12.0);
+        metrics.set("rerun_buffered_logs", 4.0);
+        assert_eq!(metrics.gauge("rerun_buffered_logs"), Some(4.0));
+        assert_eq!(metrics.gauge("rerun_failing"), None);
+        assert_eq!(metrics.counter("dropped_packets"), 3);
+        assert_eq!(metrics.counter("failed_localisations"), 0);
+        assert_eq!(metrics.histogram("scan").unwrap().count(), 2);
+
+        let summary = metrics.summary();
+        assert!(
+            summary.contains("scan : 2 fois, moyenne 4ms"),
+            "{}",
+            summary
+        );
+        assert!(summary.contains("dropped_packets : 3"), "{}", summary);
+        assert!(summary.contains("rerun_buffered_logs : 4"), "{}", summary);
+
+        metrics
+            .log_to_rerun(&rerun::RecordingStream::disabled())
+            .unwrap();
+        assert_eq!(metrics.timers["scan"].since_export, (Duration::ZERO, 0));
+        // l'histogramme garde toutes les mesures pour le résumé
+        assert_eq!(metrics.histogram("scan").unwrap().count(), 2);
+    }
+}
@@ -1,6 +1,7 @@
 //! Lit les données du lidar depuis la connexion série et les transforme en une forme utilisable
 
 use crate::prelude::*;
+use robot_common::frames::LocalPoint;
 use rppal::uart::{self, Uart};
 use std::error::Error;
 use std::fmt::Display;
@@ -115,22 +116,17 @@ impl Lidar {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PolarPoint {
     pub distance: Meters,
+    #[cfg_attr(feature = "serde", serde(with = "robot_common::units::serde_rad"))]
     pub angle: Rad,
 }
 
-impl PolarPoint {
-    pub fn to_carthesian_point(&self) -> (f64, f64) {
-        (
-            self.distance.0 * self.angle.cos(),
-            self.distance.0 * self.angle.sin(),
-        )
-    }
-
-    pub fn to_carthesian_point_f32(&self) -> (f32, f32) {
-        let (x, y) = self.to_carthesian_point();
-        (x as f32, y as f32)
+/// Point dans le repère du robot partagé avec les stratégies (y vers le kicker)
+impl From<&PolarPoint> for LocalPoint {
+    fn from(point: &PolarPoint) -> Self {
+        LocalPoint::from_lidar(point.distance, point.angle)
     }
 }
 
@@ -141,6 +137,7 @@ impl PolarPoint {
 ///  - intensité entre 0 et 1
 ///  - angle en radians
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LidarPoint {
     pub point: PolarPoint,
     pub intensity: Intensity,
@@ -163,7 +160,7 @@ impl LidarPoint {
             // intensité 0-255 u8 -> 0-1 f64
             points.push(LidarPoint {
                 point: PolarPoint {
-                    distance: Meters::mm(f64::from(Self::get_2_bytes_lsb_msb(&data, i))),
+                    distance: Meters::from_mm(i32::from(Self::get_2_bytes_lsb_msb(&data, i))),
                     angle: Rad::ZERO,
                 },
                 intensity: Intensity::from_u8(data[i + 2]),
@@ -195,9 +192,104 @@ impl LidarPoint {
     }
 }
 
+/// Regroupe les points des paquets successifs du lidar en tours complets
+#[derive(Debug, Default)]
+pub struct ScanAssembler {
+    current: Vec<LidarPoint>,
+    last_angle: Option<Rad>,
+}
+
+impl ScanAssembler {
+    /// Ajoute les points d'un paquet.
+    /// Renvoit les tours terminés par ce paquet (en général aucun ou un seul), dans l'ordre.
+    /// Un tour se termine quand l'angle repasse par 0°, c'est-à-dire quand il diminue de plus
+    /// d'un demi-tour : un point dont l'angle recule un peu (bruit de mesure) ne coupe pas le tour.
+    pub fn push(&mut self, points: &[LidarPoint]) -> Vec<Vec<LidarPoint>> {
+        let mut finished = Vec::new();
+        for point in points {
+            if self
+                .last_angle
+                .is_some_and(|last_angle| last_angle - point.point.angle > Rad::HALF_TURN)
+            {
+                // Un tour fait à peu près la même taille que le précédent
+                let capacity = self.current.len();
+                finished.push(std::mem::replace(
+                    &mut self.current,
+                    Vec::with_capacity(capacity),
+                ));
+            }
+            self.last_angle = Some(point.point.angle);
+            self.current.push(point.clone());
+        }
+        finished
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    
+    use super::*;
 
-    // write unit tests here :-)
+    fn point_at(angle: f64) -> LidarPoint {
+        LidarPoint {
+            point: PolarPoint {
+                distance: Meters(1.0),
+                angle: Deg::new(angle).rad(),
+            },
+            intensity: Intensity::NULL,
+        }
+    }
+
+    #[test]
+    fn test_scan_assembler_splits_on_wrap() {
+        let mut assembler = ScanAssembler::default();
+        assert!(assembler.push(&[point_at(0.0), point_at(120.0)]).is_empty());
+        assert!(
+            assembler
+                .push(&[point_at(240.0), point_at(350.0)])
+                .is_empty()
+        );
+        let scans = assembler.push(&[point_at(355.0), point_at(2.0), point_at(10.0)]);
+        assert_eq!(scans.len(), 1);
+        assert_eq!(scans[0].len(), 5);
+        // un angle qui recule un peu ne termine pas le tour
+        assert!(assembler.push(&[point_at(9.0), point_at(200.0)]).is_empty());
+        let scans = assembler.push(&[point_at(1.0)]);
+        assert_eq!(scans.len(), 1);
+        assert_eq!(scans[0].len(), 4);
+    }
+
+    #[test]
+    fn test_scan_assembler_keeps_every_scan_of_a_packet() {
+        let mut assembler = ScanAssembler::default();
+        assembler.push(&[point_at(300.0)]);
+        // paquet qui repasse deux fois par 0° (ex. après une longue pause du programme)
+        let scans = assembler.push(&[
+            point_at(5.0),
+            point_at(200.0),
+            point_at(3.0),
+            point_at(100.0),
+        ]);
+        assert_eq!(scans.len(), 2);
+        assert_eq!(scans[0].len(), 1);
+        assert_eq!(scans[1].len(), 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_lidar_point_serde_round_trip() {
+        let point = LidarPoint {
+            point: PolarPoint {
+                distance: Meters(1.5),
+                angle: Rad::new(0.25),
+            },
+            intensity: Intensity::from_u8(128),
+        };
+        let json = serde_json::to_string(&point).unwrap();
+        assert!(json.starts_with(r#"{"point":{"distance":1.5,"angle":0.25},"intensity":0.50"#));
+        let read: LidarPoint = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.point.distance, point.point.distance);
+        assert_eq!(read.point.angle, point.point.angle);
+        assert_eq!(read.intensity, point.intensity);
+        assert!(serde_json::from_str::<LidarPoint>(&json.replace("0.50", "5.0")).is_err());
+    }
 }
@@ -0,0 +1,405 @@
+//! Choix de la destination des données envoyées à rerun.
+//!
+//! Sur le robot il n'y a pas d'écran pour lancer le viewer : on peut alors se connecter à un viewer
+//! sur un autre ordinateur ou enregistrer dans des fichiers `.rrd` ouverts après le match
+//! avec `rerun <fichier>.rrd`.
+//!
+//! Le mode est donné par l'option `--rerun <mode>` ou, à défaut, par la variable d'environnement
+//! `LIDAR_RERUN` :
+//!
+//! | mode | description |
+//! | --- | --- |
+//! | `spawn` | lance un viewer sur cet ordinateur |
+//! | `connect` ou `connect=<url>` | envoie au viewer d'un autre ordinateur, par défaut `DEFAULT_CONNECT_URL` |
+//! | `save` ou `save=<dossier>` | enregistre dans le dossier, par défaut `DEFAULT_SAVE_DIR` |
+//! | `off` | n'envoie rien |
+//!
+//! En mode `save`, un nouveau fichier est commencé quand le fichier en cours dépasse
+//! `--rerun-max-mb` (ou `LIDAR_RERUN_MAX_MB`, par défaut `DEFAULT_MAX_FILE_MB`) Mo,
+//! et seuls les `MAX_SAVED_FILES` fichiers les plus récents sont gardés pour ne pas remplir la carte SD.
+//!
+//! En mode `connect`, tant que l'envoi des logs échoue (voir `log_manager::rerun_health`),
+//! la connexion est refaite en arrière-plan toutes les `RECONNECT_INTERVAL`.
+
+use std::error::Error;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::log_manager::{RerunHealth, rerun_health};
+use crate::prelude::*;
+
+pub const DEFAULT_CONNECT_URL: &str = "rerun+http://127.0.0.1:9876/proxy";
+pub const DEFAULT_SAVE_DIR: &str = "./log/rerun";
+pub const DEFAULT_MAX_FILE_MB: u64 = 100;
+/// nombre de fichiers `.rrd` gardés dans le dossier d'enregistrement
+pub const MAX_SAVED_FILES: usize = 20;
+/// temps laissé au viewer distant pour recevoir les dernières données à l'arrêt
+const CONNECT_FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+/// temps entre deux essais de reconnexion au viewer distant
+pub const RECONNECT_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug)]
+pub enum RecordingError {
+    UnknownMode(String),
+    MissingValue(String),
+    InvalidMaxSize(String),
+    Rerun(Box<dyn Error>),
+    Io(std::io::Error),
+}
+
+impl Display for RecordingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Error for RecordingError {}
+
+impl From<std::io::Error> for RecordingError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<rerun::RecordingStreamError> for RecordingError {
+    fn from(value: rerun::RecordingStreamError) -> Self {
+        Self::Rerun(Box::new(value))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordingMode {
+    Spawn,
+    Connect { url: String },
+    Save { dir: PathBuf, max_file_bytes: u64 },
+    Disabled,
+}
+
+impl RecordingMode {
+    /// Lit un mode au format décrit en tête du module, avec la taille de fichier par défaut
+    pub fn parse(mode: &str) -> Result<Self, RecordingError> {
+        let (name, value) = match mode.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (mode, None),
+        };
+        match (name.trim(), value) {
+            ("spawn", None) => Ok(Self::Spawn),
+            ("connect", url) => Ok(Self::Connect {
+                url: url.unwrap_or(DEFAULT_CONNECT_URL).to_owned(),
+            }),
+            ("save", dir) => Ok(Self::Save {
+                dir: PathBuf::from(dir.unwrap_or(DEFAULT_SAVE_DIR)),
+                max_file_bytes: DEFAULT_MAX_FILE_MB * 1_000_000,
+            }),
+            ("off" | "disabled", None) => Ok(Self::Disabled),
+            _ => Err(RecordingError::UnknownMode(mode.to_owned())),
+        }
+    }
+
+    /// Mode choisi par les options de la ligne de commande (sans le nom du programme),
+    /// puis par les variables d'environnement, sinon `default`.
+    /// Les autres options sont ignorées.
+    pub fn from_args_and_env(
+        args: impl IntoIterator<Item = String>,
+        default: RecordingMode,
+    ) -> Result<Self, RecordingError> {
+        let mut mode = None;
+        let mut max_mb = None;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (option, inline_value) = match arg.split_once('=') {
+                Some((option, value)) if option.starts_with("--") => {
+                    (option.to_owned(), Some(value.to_owned()))
+                }
+                _ => (arg, None),
+            };
+            if option != "--rerun" && option != "--rerun-max-mb" {
+                continue;
+            }
+            let value = inline_value
+                .or_else(|| args.next())
+                .ok_or_else(|| RecordingError::MissingValue(option.clone()))?;
+            if option == "--rerun" {
+                mode = Some(value);
+            } else {
+                max_mb = Some(value);
+            }
+        }
+        let mode = mode.or_else(|| std::env::var("LIDAR_RERUN").ok());
+        let max_mb = max_mb.or_else(|| std::env::var("LIDAR_RERUN_MAX_MB").ok());
+        Self::from_values(mode.as_deref(), max_mb.as_deref(), default)
+    }
+
+    /// Mode choisi par les variables d'environnement, pour les tests où les options appartiennent à cargo
+    #[inline]
+    pub fn from_env(default: RecordingMode) -> Result<Self, RecordingError> {
+        Self::from_args_and_env(std::iter::empty(), default)
+    }
+
+    fn from_values(
+        mode: Option<&str>,
+        max_mb: Option<&str>,
+        default: RecordingMode,
+    ) -> Result<Self, RecordingError> {
+        let mut mode = match mode {
+            Some(mode) => Self::parse(mode)?,
+            None => default,
+        };
+        if let (Self::Save { max_file_bytes, .. }, Some(max_mb)) = (&mut mode, max_mb) {
+            let mb: u64 = max_mb
+                .trim()
+                .parse()
+                .ok()
+                .filter(|mb| *mb > 0)
+                .ok_or_else(|| RecordingError::InvalidMaxSize(max_mb.to_owned()))?;
+            *max_file_bytes = mb * 1_000_000;
+        }
+        Ok(mode)
+    }
+}
+
+/// Flux rerun du programme, qui change de fichier quand il devient trop gros en mode `save`
+#[derive(Debug)]
+pub struct Recording {
+    rec: rerun::RecordingStream,
+    rotation: Option<FileRotation>,
+}
+
+#[derive(Debug)]
+struct FileRotation {
+    dir: PathBuf,
+    max_file_bytes: u64,
+    /// préfixe commun aux fichiers de cette exécution
+    run_name: String,
+    current: PathBuf,
+    n_file: u32,
+}
+
+impl FileRotation {
+    fn file_path(&self, n_file: u32) -> PathBuf {
+        self.dir
+            .join(format!("{}-{:03}.rrd", self.run_name, n_file))
+    }
+}
+
+impl Recording {
+    pub fn new(application_id: &str, mode: &RecordingMode) -> Result<Self, RecordingError> {
+        let builder = rerun::RecordingStreamBuilder::new(application_id);
+        let mut rotation = None;
+        let rec = match mode {
+            RecordingMode::Spawn => builder.spawn()?,
+            RecordingMode::Connect { url } => {
+                let rec = builder.connect_grpc_opts(url.clone(), Some(CONNECT_FLUSH_TIMEOUT))?;
+                reconnect_in_background(rec.clone(), url.clone(), rerun_health())?;
+                rec
+            }
+            RecordingMode::Save {
+                dir,
+                max_file_bytes,
+            } => {
+                std::fs::create_dir_all(dir)?;
+                let started = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                let mut file_rotation = FileRotation {
+                    dir: dir.clone(),
+                    max_file_bytes: *max_file_bytes,
+                    run_name: format!("{}-{}", application_id, started),
+                    current: PathBuf::new(),
+                    n_file: 0,
+                };
+                file_rotation.current = file_rotation.file_path(0);
+                let rec = builder.save(&file_rotation.current)?;
+                rotation = Some(file_rotation);
+                rec
+            }
+            RecordingMode::Disabled => rerun::RecordingStream::disabled(),
+        };
+        Ok(Self { rec, rotation })
+    }
+
+    #[inline]
+    pub fn stream(&self) -> &rerun::RecordingStream {
+        &self.rec
+    }
+
+    /// Fichier en cours d'écriture en mode `save`
+    #[inline]
+    pub fn current_file(&self) -> Option<&Path> {
+        self.rotation
+            .as_ref()
+            .map(|rotation| rotation.current.as_path())
+    }
+
+    /// À appeler régulièrement (par exemple à chaque tour de lidar) :
+    /// commence un nouveau fichier si le fichier en cours est trop gros.
+    /// Renvoit `true` si un nouveau fichier a été commencé : les données statiques sont à renvoyer.
+    pub fn rotate_if_needed(&mut self) -> Result<bool, RecordingError> {
+        let Some(rotation) = &mut self.rotation else {
+            return Ok(false);
+        };
+        let size = std::fs::metadata(&rotation.current)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        if size < rotation.max_file_bytes {
+            return Ok(false);
+        }
+        rotation.n_file += 1;
+        rotation.current = rotation.file_path(rotation.n_file);
+        self.rec.flush_blocking();
+        self.rec
+            .save(&rotation.current)
+            .map_err(|err| RecordingError::Rerun(Box::new(err)))?;
+        info!(
+            "enregistrement rerun continué dans {}",
+            rotation.current.display()
+        );
+        remove_old_files(&rotation.dir, MAX_SAVED_FILES)?;
+        Ok(true)
+    }
+}
+
+/// Refait la connexion de `rec` à `url` tant que `health` indique que l'envoi échoue,
+/// sans jamais arrêter le programme
+fn reconnect_in_background(
+    rec: rerun::RecordingStream,
+    url: String,
+    health: &'static RerunHealth,
+) -> Result<(), RecordingError> {
+    std::thread::Builder::new()
+        .name("rerun-reconnect".to_owned())
+        .spawn(move || {
+            loop {
+                std::thread::sleep(RECONNECT_INTERVAL);
+                if !health.is_failing() {
+                    continue;
+                }
+                match rec.connect_grpc_opts(url.clone(), Some(CONNECT_FLUSH_TIMEOUT)) {
+                    Ok(()) => {
+                        health.reconnected();
+                        info!("reconnecté au viewer rerun {}", url);
+                    }
+                    Err(err) => debug!("reconnexion à rerun impossible : {}", err),
+                }
+            }
+        })?;
+    Ok(())
+}
+
+/// Supprime les fichiers `.rrd` les plus anciens du dossier pour n'en garder que `keep`
+fn remove_old_files(dir: &Path, keep: usize) -> Result<(), RecordingError> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().is_some_and(|extension| extension == "rrd") {
+            files.push((entry.metadata()?.modified()?, path));
+        }
+    }
+    if files.len() <= keep {
+        return Ok(());
+    }
+    // les noms départagent les fichiers modifiés dans la même seconde
+    files.sort();
+    for (_, path) in files.iter().take(files.len() - keep) {
+        debug!("suppression de l'ancien enregistrement {}", path.display());
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_mode() {
+        assert_eq!(RecordingMode::parse("spawn").unwrap(), RecordingMode::Spawn);
+        assert_eq!(
+            RecordingMode::parse("off").unwrap(),
+            RecordingMode::Disabled
+        );
+        assert_eq!(
+            RecordingMode::parse("connect").unwrap(),
+            RecordingMode::Connect {
+                url: DEFAULT_CONNECT_URL.to_owned()
+            }
+        );
+        assert_eq!(
+            RecordingMode::parse("connect=rerun+http://192.168.1.20:9876/proxy").unwrap(),
+            RecordingMode::Connect {
+                url: "rerun+http://192.168.1.20:9876/proxy".to_owned()
+            }
+        );
+        assert_eq!(
+            RecordingMode::parse("save=/tmp/match").unwrap(),
+            RecordingMode::Save {
+                dir: PathBuf::from("/tmp/match"),
+                max_file_bytes: DEFAULT_MAX_FILE_MB * 1_000_000
+            }
+        );
+        assert!(RecordingMode::parse("spawn=1").is_err());
+        assert!(RecordingMode::parse("viewer").is_err());
+    }
+
+    #[test]
+    fn test_mode_from_args() {
+        let mode = RecordingMode::from_args_and_env(
+            args(&[
+                "--verbose",
+                "--rerun",
+                "save=/tmp/match",
+                "--rerun-max-mb=5",
+            ]),
+            RecordingMode::Disabled,
+        )
+        .unwrap();
+        assert_eq!(
+            mode,
+            RecordingMode::Save {
+                dir: PathBuf::from("/tmp/match"),
+                max_file_bytes: 5_000_000
+            }
+        );
+        assert_eq!(
+            RecordingMode::from_args_and_env(args(&["--rerun=spawn"]), RecordingMode::Disabled)
+                .unwrap(),
+            RecordingMode::Spawn
+        );
+        assert!(
+            RecordingMode::from_args_and_env(args(&["--rerun"]), RecordingMode::Disabled).is_err()
+        );
+        assert_eq!(
+            RecordingMode::from_values(None, Some("5"), RecordingMode::Spawn).unwrap(),
+            RecordingMode::Spawn
+        );
+        assert!(RecordingMode::from_values(Some("save"), Some("0"), RecordingMode::Spawn).is_err());
+    }
+
+    #[test]
+    fn test_remove_old_files() {
+        let dir = std::env::temp_dir().join(format!("lidar-analyzer-rrd-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for n_file in 0..5 {
+            std::fs::write(dir.join(format!("run-{:03}.rrd", n_file)), b"rrd").unwrap();
+        }
+        std::fs::write(dir.join("notes.txt"), b"notes").unwrap();
+        remove_old_files(&dir, 3).unwrap();
+        let mut remaining: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        remaining.sort();
+        assert_eq!(
+            remaining,
+            ["notes.txt", "run-002.rrd", "run-003.rrd", "run-004.rrd"]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,131 @@
+//! Détection des autres robots sur le terrain à partir d'un tour de lidar et des murs trouvés
+
+use crate::analyze::FieldWalls;
+use crate::geometry::{Line, Point};
+use crate::parse::LidarPoint;
+use crate::prelude::*;
+
+/// les points plus proches d'un mur que cela appartiennent au mur
+const WALL_MARGIN: Meters = Meters::cm(8.0);
+/// deux points consécutifs plus éloignés que cela appartiennent à deux objets différents
+const CLUSTER_MAX_GAP: Meters = Meters::cm(8.0);
+/// un objet doit contenir au moins ce nombre de points pour être un robot
+const ROBOT_MIN_POINTS: usize = 3;
+/// un robot mesure au plus 22 cm de diamètre, on laisse de la marge pour le bruit
+const ROBOT_MAX_WIDTH: Meters = Meters::cm(30.0);
+
+/// Un robot vu par le lidar
+#[derive(Debug, Clone, Copy)]
+pub struct RobotDetection {
+    /// centre des points vus dans le repère du lidar.
+    /// Seule la face tournée vers nous est vue, le centre du robot est un peu plus loin.
+    pub position: Point,
+    /// distance entre les deux points extrêmes
+    pub width: Meters,
+    pub points: usize,
+}
+
+/// Regroupe les points à l'intérieur du terrain en objets et garde ceux qui ont la taille d'un robot.
+/// Les robots sont triés du plus proche au plus lointain.
+pub fn detect_robots(points: &[LidarPoint], walls: &FieldWalls) -> Vec<RobotDetection> {
+    // le robot (l'origine) est à l'intérieur du terrain : un point à l'intérieur
+    // est du même côté que l'origine pour chacun des murs
+    let walls = walls.as_array().map(|wall| Line::from(wall.line()));
+    let is_inside = |point: &Point| {
+        walls
+            .iter()
+            .all(|wall| wall.signed_distance_to(*point) < -WALL_MARGIN)
+    };
+    let points: Vec<Point> = points.iter().map(|point| Point::from(&point.point)).collect();
+
+    // on commence le parcours sur un mur pour ne pas couper en deux un robot situé vers 0°
+    let start = points
+        .iter()
+        .position(|point| !is_inside(point))
+        .unwrap_or(0);
+    let mut clusters: Vec<Vec<Point>> = Vec::new();
+    let mut current: Vec<Point> = Vec::new();
+    for point in points[start..].iter().chain(points[..start].iter()) {
+        let is_next = current
+            .last()
+            .is_some_and(|last| last.distance_to(*point) <= CLUSTER_MAX_GAP);
+        if !is_next && !current.is_empty() {
+            clusters.push(std::mem::take(&mut current));
+        }
+        if is_inside(point) {
+            current.push(*point);
+        } else if !current.is_empty() {
+            clusters.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        clusters.push(current);
+    }
+
+    let mut robots: Vec<RobotDetection> = clusters
+        .iter()
+        .filter(|cluster| cluster.len() >= ROBOT_MIN_POINTS)
+        .filter_map(|cluster| {
+            let width = cluster[0].distance_to(cluster[cluster.len() - 1]);
+            (width <= ROBOT_MAX_WIDTH).then(|| RobotDetection {
+                position: Point::centroid(cluster).unwrap(),
+                width,
+                points: cluster.len(),
+            })
+        })
+        .collect();
+    robots.sort_by(|a, b| {
+        let distance = |robot: &RobotDetection| robot.position.distance_to(Point::ORIGIN).0;
+        distance(a).total_cmp(&distance(b))
+    });
+    robots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::{square_robot, synthetic_field_walls, synthetic_scan};
+
+    #[test]
+    fn test_empty_field() {
+        let robot = Point::new(Meters(-0.3), Meters(-0.2));
+        let points = synthetic_scan(robot, &[]);
+        assert!(detect_robots(&points, &synthetic_field_walls(robot)).is_empty());
+    }
+
+    #[test]
+    fn test_detect_robots() {
+        let robot = Point::new(Meters(-0.3), Meters(-0.2));
+        let others = [
+            Point::new(Meters(0.5), Meters(0.3)),
+            Point::new(Meters(-0.9), Meters(0.4)),
+        ];
+        let obstacles: Vec<_> = others
+            .iter()
+            .flat_map(|other| square_robot(*other, Meters::cm(18.0)))
+            .collect();
+        let points = synthetic_scan(robot, &obstacles);
+        let robots = detect_robots(&points, &synthetic_field_walls(robot));
+        assert_eq!(robots.len(), 2, "{:#?}", robots);
+        // le plus proche en premier, positions dans le repère du lidar (aligné avec le terrain)
+        for (found, other) in robots.iter().zip([others[1], others[0]]) {
+            let expected = Point::ORIGIN + (other - robot);
+            assert!(
+                found.position.distance_to(expected) < Meters::cm(12.0),
+                "{:?} != {:?}",
+                found,
+                expected
+            );
+            assert!(found.width <= ROBOT_MAX_WIDTH);
+        }
+    }
+
+    #[test]
+    fn test_robot_across_zero_degree() {
+        let robot = Point::new(Meters(-0.3), Meters(0.0));
+        let other = Point::new(Meters(0.4), Meters(0.0));
+        let points = synthetic_scan(robot, &square_robot(other, Meters::cm(18.0)));
+        let robots = detect_robots(&points, &synthetic_field_walls(robot));
+        assert_eq!(robots.len(), 1, "{:#?}", robots);
+    }
+}
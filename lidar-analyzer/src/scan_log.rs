@@ -0,0 +1,333 @@
+//! Envoi de toutes les étapes de l'analyse d'un tour de lidar à rerun.
+//!
+//! Chaque tour est placé sur les timelines `scan_index` (numéro du tour) et `sensor_time`
+//! (temps écoulé depuis le démarrage) : en parcourant un match dans le viewer,
+//! on voit pour chaque tour les points, l'accumulateur, les paires candidates,
+//! les murs retenus et la pose qui en découle.
+//!
+//! Entités sous `lidar/` (repère du lidar) :
+//! `points`, `points/out_of_range`, `hough/accumulator` (lignes : distance, colonnes : angle),
+//! `candidates/width`, `candidates/length`, `candidates/perpendicular`, `walls`, `corners`,
+//! `field_center` et `status`.
+//!
+//! Entités sous `field/` (repère du terrain) : `walls` (statique, voir `log_field_model`),
+//! `robot`, `robot/covariance` (ellipse à deux écarts types) et les courbes `pose/*`.
+
+use std::error::Error;
+use std::time::Duration;
+
+use crate::analyze::*;
+use crate::geometry::{Line, Point, Vector};
+use crate::parse::LidarPoint;
+use crate::prelude::*;
+
+/// longueur des droites dessinées de part et d'autre de leur pied
+const DRAWN_LINE_HALF_LENGTH: Meters = Meters(3.0);
+/// nombre de côtés du polygone qui dessine l'ellipse d'incertitude
+const ELLIPSE_SIDES: usize = 32;
+
+const PARALLEL_COLOR: (u8, u8, u8) = colors::BLUE;
+const PERPENDICULAR_COLOR: (u8, u8, u8) = colors::TEAL;
+const GUESSED_COLOR: (u8, u8, u8) = colors::ORANGE;
+
+#[inline]
+fn hide_labels() -> rerun::components::ShowLabels {
+    rerun::components::ShowLabels(rerun::datatypes::Bool(false))
+}
+
+/// Segment long de `2 * DRAWN_LINE_HALF_LENGTH` porté par la droite, centré sur son pied
+fn line_strip(line: PolarLine) -> [(f32, f32); 2] {
+    let line = Line::from(line);
+    let along = Vector::from_polar(DRAWN_LINE_HALF_LENGTH, line.direction());
+    [
+        (line.foot() - along).to_f32(),
+        (line.foot() + along).to_f32(),
+    ]
+}
+
+/// Provenance d'un mur, affichée dans son étiquette
+fn wall_label(name: &str, wall: &WallLine) -> String {
+    match wall {
+        WallLine::FoundAsParallelLine(hough) => {
+            format!("{} : paire parallèle (poids {})", name, hough.weight)
+        }
+        WallLine::FoundAsPerpendicular(hough) => {
+            format!("{} : perpendiculaire (poids {})", name, hough.weight)
+        }
+        WallLine::GuessedLine(_) => format!("{} : devinée", name),
+    }
+}
+
+fn wall_color(wall: &WallLine) -> (u8, u8, u8) {
+    match wall {
+        WallLine::FoundAsParallelLine(_) => PARALLEL_COLOR,
+        WallLine::FoundAsPerpendicular(_) => PERPENDICULAR_COLOR,
+        WallLine::GuessedLine(_) => GUESSED_COLOR,
+    }
+}
+
+/// Accumulateur en niveaux de gris, la case la plus forte étant blanche
+fn accumulator_image(accumulator: &HoughAccumulator) -> rerun::Image {
+    rerun::Image::from_l8(
+        accumulator_pixels(accumulator),
+        [accumulator[0].len() as u32, accumulator.len() as u32],
+    )
+}
+
+fn log_candidates(
+    rec: &rerun::RecordingStream,
+    path: &str,
+    candidates: &[ParallelCandidate],
+    color: (u8, u8, u8),
+) -> Result<(), Box<dyn Error>> {
+    rec.log(
+        path,
+        &rerun::LineStrips2D::new(
+            candidates
+                .iter()
+                .flat_map(|pair| pair.lines())
+                .map(|hough| line_strip(hough.line)),
+        )
+        .with_colors(candidates.iter().flat_map(|_| [color; 2]))
+        .with_labels(candidates.iter().flat_map(|pair| {
+            let label = format!(
+                "score {:.0} (accord {:.2}, support {:.2})",
+                pair.score(),
+                pair.consistency,
+                pair.support
+            );
+            [label.clone(), label]
+        }))
+        .with_show_labels(hide_labels()),
+    )?;
+    Ok(())
+}
+
+/// Rectangle du terrain dans le repère du terrain, à envoyer une seule fois
+pub fn log_field_model(rec: &rerun::RecordingStream) -> Result<(), Box<dyn Error>> {
+    let (x, y) = ((FIELD_LENGTH / 2.0).0 as f32, (FIELD_WIDTH / 2.0).0 as f32);
+    rec.log_static(
+        "field/walls",
+        &rerun::LineStrips2D::new([[(-x, -y), (x, -y), (x, y), (-x, y), (-x, -y)]])
+            .with_colors([colors::WHITE]),
+    )?;
+    Ok(())
+}
+
+/// Envoie toutes les étapes de l'analyse du tour `scan_index` à rerun.
+/// Les entités absentes de ce tour (par exemple les murs s'ils n'ont pas été trouvés)
+/// sont vidées pour ne pas afficher celles d'un tour précédent.
+pub fn log_scan(
+    rec: &rerun::RecordingStream,
+    scan_index: u64,
+    sensor_time: Duration,
+    points: &[LidarPoint],
+    analysis: &WallsAnalysis,
+) -> Result<(), Box<dyn Error>> {
+    rec.set_time_sequence("scan_index", scan_index as i64);
+    rec.set_duration_secs("sensor_time", sensor_time.as_secs_f64());
+
+    let (in_range, out_of_range): (Vec<&LidarPoint>, Vec<&LidarPoint>) = points
+        .iter()
+        .partition(|point| point.point.distance <= LIDAR_DISTANCE_MAX);
+    rec.log(
+        "lidar/points",
+        &rerun::Points2D::new(
+            in_range
+                .iter()
+                .map(|point| Point::from(&point.point).to_f32()),
+        )
+        .with_radii([0.005]),
+    )?;
+    rec.log(
+        "lidar/points/out_of_range",
+        &rerun::Points2D::new(
+            out_of_range
+                .iter()
+                .map(|point| Point::from(&point.point).to_f32()),
+        )
+        .with_colors([colors::DARK_RED])
+        .with_radii([0.005]),
+    )?;
+
+    rec.log(
+        "lidar/hough/accumulator",
+        &accumulator_image(&analysis.accumulator),
+    )?;
+    log_candidates(
+        rec,
+        "lidar/candidates/width",
+        &analysis.width_candidates,
+        colors::PURPLE,
+    )?;
+    log_candidates(
+        rec,
+        "lidar/candidates/length",
+        &analysis.length_candidates,
+        colors::PINK,
+    )?;
+    // les perpendiculaires que cherche fallback_on_3_walls
+    let best_pair = analysis
+        .width_candidates
+        .iter()
+        .chain(analysis.length_candidates.iter())
+        .max_by(|a, b| a.score().total_cmp(&b.score()));
+    let perpendiculars = match best_pair {
+        Some(pair) => {
+            search_perpendicular_lines_of(&analysis.peaks, pair.first.line, &analysis.config)
+        }
+        None => Vec::new(),
+    };
+    rec.log(
+        "lidar/candidates/perpendicular",
+        &rerun::LineStrips2D::new(perpendiculars.iter().map(|hough| line_strip(hough.line)))
+            .with_colors([PERPENDICULAR_COLOR])
+            .with_labels(
+                perpendiculars
+                    .iter()
+                    .map(|hough| format!("poids {}", hough.weight)),
+            )
+            .with_show_labels(hide_labels()),
+    )?;
+
+    let walls = analysis.walls.as_ref();
+    let named_walls: Vec<(&str, WallLine)> = walls
+        .map(|walls| {
+            vec![
+                ("width1", walls.width1),
+                ("width2", walls.width2),
+                ("length1", walls.length1),
+                ("length2", walls.length2),
+            ]
+        })
+        .unwrap_or_default();
+    rec.log(
+        "lidar/walls",
+        &rerun::LineStrips2D::new(named_walls.iter().map(|(_, wall)| line_strip(wall.line())))
+            .with_colors(named_walls.iter().map(|(_, wall)| wall_color(wall)))
+            .with_labels(
+                named_walls
+                    .iter()
+                    .map(|(name, wall)| wall_label(name, wall)),
+            ),
+    )?;
+
+    let corners = walls.map(|walls| walls.corners());
+    let corner_points: Vec<Point> = match &corners {
+        Some(Ok(corners)) => corners.as_array().to_vec(),
+        _ => Vec::new(),
+    };
+    rec.log(
+        "lidar/corners",
+        &rerun::Points2D::new(corner_points.iter().map(|corner| corner.to_f32()))
+            .with_colors([colors::ORANGE])
+            .with_radii([0.05]),
+    )?;
+    let center: Vec<Point> = match &corners {
+        Some(Ok(corners)) => vec![corners.center()],
+        _ => Vec::new(),
+    };
+    rec.log(
+        "lidar/field_center",
+        &rerun::Points2D::new(center.iter().map(|center| center.to_f32()))
+            .with_colors([colors::MAGENTA])
+            .with_radii([0.01])
+            .with_labels(["centre du terrain calculé"])
+            .with_show_labels(hide_labels()),
+    )?;
+
+    let pose = walls.map(RobotPose::from_walls);
+    let status = match &pose {
+        Some(Ok(_)) => "localisé".to_owned(),
+        Some(Err(err)) => format!("les murs trouvés ne forment pas un terrain : {}", err),
+        None => "murs non trouvés".to_owned(),
+    };
+    rec.log("lidar/status", &rerun::TextLog::new(status))?;
+
+    let (Some(Ok(pose)), Some(walls)) = (pose, walls) else {
+        rec.log(
+            "field/robot",
+            &rerun::Arrows2D::from_vectors(Vec::<(f32, f32)>::new()),
+        )?;
+        rec.log(
+            "field/robot/covariance",
+            &rerun::LineStrips2D::new(Vec::<Vec<(f32, f32)>>::new()),
+        )?;
+        return Ok(());
+    };
+    let covariance = PoseCovariance::estimate(points, walls);
+    let heading = Vector::from_polar(Meters(0.15), pose.orientation);
+    rec.log(
+        "field/robot",
+        &rerun::Arrows2D::from_vectors([(heading.x.0 as f32, heading.y.0 as f32)])
+            .with_origins([(pose.x.0 as f32, pose.y.0 as f32)])
+            .with_colors([colors::GREEN])
+            .with_labels([format!("{:?}", pose)])
+            .with_show_labels(hide_labels()),
+    )?;
+    // ellipse à 2 écarts types, les corrélations étant ignorées ses axes suivent ceux du terrain
+    let ellipse: Vec<(f32, f32)> = if covariance.x.is_finite() && covariance.y.is_finite() {
+        let (sigma_x, sigma_y) = (covariance.x.sqrt(), covariance.y.sqrt());
+        (0..=ELLIPSE_SIDES)
+            .map(|side| {
+                let angle = Rad::FULL_TURN * (side as f64 / ELLIPSE_SIDES as f64);
+                (
+                    (pose.x.0 + 2.0 * sigma_x * angle.cos()) as f32,
+                    (pose.y.0 + 2.0 * sigma_y * angle.sin()) as f32,
+                )
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    rec.log(
+        "field/robot/covariance",
+        &rerun::LineStrips2D::new([ellipse]).with_colors([colors::YELLOW]),
+    )?;
+    for (name, value) in [
+        ("x", pose.x.0),
+        ("y", pose.y.0),
+        ("orientation", pose.orientation.val()),
+        ("sigma_x", covariance.x.sqrt()),
+        ("sigma_y", covariance.y.sqrt()),
+        ("sigma_orientation", covariance.orientation.sqrt()),
+    ] {
+        if value.is_finite() {
+            rec.log(
+                format!("field/pose/{}", name),
+                &rerun::Scalars::single(value),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::synthetic_scan;
+
+    #[test]
+    fn test_log_scan() {
+        let rec = rerun::RecordingStream::disabled();
+        log_field_model(&rec).unwrap();
+        let points = synthetic_scan(Point::new(Meters(0.3), Meters(-0.2)), &[]);
+        let analysis = WallsAnalysis::run(&points);
+        assert!(analysis.walls.is_some());
+        log_scan(&rec, 1, Duration::from_millis(100), &points, &analysis).unwrap();
+        // aucun mur : les entités sont vidées sans erreur
+        let analysis = WallsAnalysis::run(&[]);
+        assert!(analysis.walls.is_none());
+        log_scan(&rec, 2, Duration::from_millis(200), &[], &analysis).unwrap();
+    }
+
+    #[test]
+    fn test_line_strip_centered_on_foot() {
+        let [(x1, y1), (x2, y2)] = line_strip(PolarLine {
+            distance: Meters(1.0),
+            angle: Rad::QUARTER_TURN,
+        });
+        assert!((x1 + x2).abs() < 1e-6 && (y1 - 1.0).abs() < 1e-6 && (y2 - 1.0).abs() < 1e-6);
+        assert!((x1 - x2).abs() > 5.9);
+    }
+}
@@ -0,0 +1,86 @@
+//! Tours de lidar simulés pour les tests
+
+use crate::analyze::{FIELD_LENGTH, FIELD_WIDTH, FieldWalls, WallLine};
+use crate::geometry::{Line, Point, Segment, Vector};
+use crate::parse::{LidarPoint, PolarPoint};
+use crate::prelude::*;
+
+/// au-delà, le lidar ne renvoit plus de points exploitables
+const SIMULATED_RANGE: Meters = Meters(3.0);
+
+/// Tour de lidar simulé : un point tous les 0,5° sur les murs d'un terrain aligné avec le lidar,
+/// le lidar étant en `robot` dans le repère du terrain. Les obstacles (dans le repère du terrain)
+/// masquent les murs.
+pub fn synthetic_scan(robot: Point, obstacles: &[Segment]) -> Vec<LidarPoint> {
+    let (half_length, half_width) = (FIELD_LENGTH.0 / 2.0, FIELD_WIDTH.0 / 2.0);
+    (0..720)
+        .filter_map(|n| {
+            let angle = Deg::new(f64::from(n) * 0.5).rad();
+            let (cos, sin) = (angle.cos(), angle.sin());
+            let along_x = if cos > 0.0 {
+                half_length - robot.x.0
+            } else {
+                -half_length - robot.x.0
+            } / cos;
+            let along_y = if sin > 0.0 {
+                half_width - robot.y.0
+            } else {
+                -half_width - robot.y.0
+            } / sin;
+            let ray = Vector::from_polar(Meters(1.0), angle);
+            let hit_obstacle = obstacles.iter().filter_map(|obstacle| {
+                let side = obstacle.end - obstacle.start;
+                let to_start = obstacle.start - robot;
+                let denominator = ray.cross(side);
+                let along_ray = to_start.cross(side) / denominator;
+                let along_side = to_start.cross(ray) / denominator;
+                (denominator != 0.0 && along_ray > 0.0 && (0.0..=1.0).contains(&along_side))
+                    .then_some(along_ray)
+            });
+            let distance = Meters(hit_obstacle.fold(along_x.abs().min(along_y.abs()), f64::min));
+            (distance <= SIMULATED_RANGE).then_some(LidarPoint {
+                point: PolarPoint { distance, angle },
+                intensity: Intensity::NULL,
+            })
+        })
+        .collect()
+}
+
+/// Murs du terrain dans le repère du lidar pour `synthetic_scan` : (largeur x2, longueur x2)
+pub fn synthetic_walls(robot: Point) -> [Line; 4] {
+    [
+        Line::new(FIELD_LENGTH / 2.0 - robot.x, Rad::ZERO),
+        Line::new(FIELD_LENGTH / 2.0 + robot.x, Rad::HALF_TURN),
+        Line::new(FIELD_WIDTH / 2.0 - robot.y, Rad::QUARTER_TURN),
+        Line::new(FIELD_WIDTH / 2.0 + robot.y, -Rad::QUARTER_TURN),
+    ]
+}
+
+/// Murs de `synthetic_walls` sous la forme renvoyée par la détection
+pub fn synthetic_field_walls(robot: Point) -> FieldWalls {
+    let [width1, width2, length1, length2] = synthetic_walls(robot);
+    FieldWalls {
+        width1: WallLine::GuessedLine(width1.into()),
+        width2: WallLine::GuessedLine(width2.into()),
+        length1: WallLine::GuessedLine(length1.into()),
+        length2: WallLine::GuessedLine(length2.into()),
+    }
+}
+
+/// Robot carré de côté `side` centré en `center` (repère du terrain), à passer en obstacle à `synthetic_scan`
+pub fn square_robot(center: Point, side: Meters) -> [Segment; 4] {
+    let half = side / 2.0;
+    let corner = |x: Meters, y: Meters| center + Vector::new(x, y);
+    let corners = [
+        corner(-half, -half),
+        corner(half, -half),
+        corner(half, half),
+        corner(-half, half),
+    ];
+    [
+        Segment::new(corners[0], corners[1]),
+        Segment::new(corners[1], corners[2]),
+        Segment::new(corners[2], corners[3]),
+        Segment::new(corners[3], corners[0]),
+    ]
+}
@@ -0,0 +1,357 @@
+//! Accès au matériel des moteurs et du kicker.
+//!
+//! Les moteurs ne manipulent pas directement les pins du Raspberry : ils passent par le trait
+//! `Output`, créé par un `Hal`. Le `Hal` fournit aussi le `Timer` qui coupe le kicker.
+//! Trois implémentations :
+//!  - `RppalHal` : les vraies pins GPIO, sur le robot
+//!  - `NoopHal` : ne fait rien, pour lancer le code sur un ordinateur
+//!  - `RecordingHal` : garde toutes les commandes avec leur date, et ses minuteurs ne se
+//!    déclenchent qu'à la demande, pour les tests (compilé seulement pour les tests)
+
+use rppal::gpio::{Gpio, OutputPin};
+use std::error::Error;
+use std::fmt::Debug;
+use std::thread;
+use std::time::Instant;
+
+#[cfg(test)]
+pub use recording::{PinCommand, RecordingHal};
+
+/// Une pin de sortie : état haut/bas ou PWM logiciel.
+/// Les pins du kicker sont coupées depuis un autre thread, voir `BallControl::kick`.
+pub trait Output: Debug + Send {
+    fn set_high(&mut self);
+    fn set_low(&mut self);
+    /// `duty_cycle` entre 0 et 1
+    fn set_pwm_frequency(&mut self, frequency: f64, duty_cycle: f64) -> Result<(), Box<dyn Error>>;
+}
+
+/// Action lancée par un `Timer`
+pub type TimerAction = Box<dyn FnOnce() + Send>;
+
+/// Lance une action à une date donnée, sans bloquer l'appelant
+pub trait Timer: Debug + Send {
+    fn schedule(&mut self, at: Instant, action: TimerAction) -> Result<(), Box<dyn Error>>;
+}
+
+/// Un thread par action, qui dort jusqu'à sa date
+#[derive(Debug, Default)]
+pub struct ThreadTimer;
+
+impl Timer for ThreadTimer {
+    fn schedule(&mut self, at: Instant, action: TimerAction) -> Result<(), Box<dyn Error>> {
+        thread::Builder::new()
+            .name("timer".to_owned())
+            .spawn(move || {
+                thread::sleep(at.saturating_duration_since(Instant::now()));
+                action();
+            })?;
+        Ok(())
+    }
+}
+
+/// Fabrique les pins de sortie et les minuteurs
+pub trait Hal {
+    type Pin: Output;
+
+    fn output(&mut self, pin: u8, initial_high: bool) -> Result<Self::Pin, Box<dyn Error>>;
+
+    fn timer(&mut self) -> Box<dyn Timer> {
+        Box::new(ThreadTimer)
+    }
+}
+
+// Raspberry
+
+impl Output for OutputPin {
+    #[inline]
+    fn set_high(&mut self) {
+        OutputPin::set_high(self);
+    }
+
+    #[inline]
+    fn set_low(&mut self) {
+        OutputPin::set_low(self);
+    }
+
+    #[inline]
+    fn set_pwm_frequency(&mut self, frequency: f64, duty_cycle: f64) -> Result<(), Box<dyn Error>> {
+        Ok(OutputPin::set_pwm_frequency(self, frequency, duty_cycle)?)
+    }
+}
+
+/// Pins GPIO du Raspberry
+#[derive(Debug, Default)]
+pub struct RppalHal;
+
+impl Hal for RppalHal {
+    type Pin = OutputPin;
+
+    fn output(&mut self, pin: u8, initial_high: bool) -> Result<Self::Pin, Box<dyn Error>> {
+        let pin = Gpio::new()?.get(pin)?;
+        if initial_high {
+            Ok(pin.into_output_high())
+        } else {
+            Ok(pin.into_output_low())
+        }
+    }
+}
+
+// Sans matériel
+
+#[derive(Debug)]
+pub struct NoopPin;
+
+impl Output for NoopPin {
+    fn set_high(&mut self) {}
+
+    fn set_low(&mut self) {}
+
+    fn set_pwm_frequency(&mut self, _: f64, _: f64) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+}
+
+/// Accepte toutes les commandes sans rien faire
+#[derive(Debug, Default)]
+pub struct NoopHal;
+
+impl Hal for NoopHal {
+    type Pin = NoopPin;
+
+    fn output(&mut self, _: u8, _: bool) -> Result<Self::Pin, Box<dyn Error>> {
+        Ok(NoopPin)
+    }
+}
+
+/// Enregistrement
+#[cfg(test)]
+mod recording {
+    use super::{Hal, Output, Timer, TimerAction};
+    use std::error::Error;
+    use std::fmt::{self, Debug};
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum PinCommand {
+        High,
+        Low,
+        Pwm { frequency: f64, duty_cycle: f64 },
+    }
+
+    /// Commande envoyée à une pin
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Command {
+        pub at: Instant,
+        pub pin: u8,
+        pub command: PinCommand,
+    }
+
+    type CommandLog = Arc<Mutex<Vec<Command>>>;
+
+    #[derive(Debug)]
+    pub struct RecordingPin {
+        pin: u8,
+        log: CommandLog,
+    }
+
+    impl RecordingPin {
+        fn record(&self, command: PinCommand) {
+            self.log.lock().unwrap().push(Command {
+                at: Instant::now(),
+                pin: self.pin,
+                command,
+            });
+        }
+    }
+
+    impl Output for RecordingPin {
+        fn set_high(&mut self) {
+            self.record(PinCommand::High);
+        }
+
+        fn set_low(&mut self) {
+            self.record(PinCommand::Low);
+        }
+
+        fn set_pwm_frequency(
+            &mut self,
+            frequency: f64,
+            duty_cycle: f64,
+        ) -> Result<(), Box<dyn Error>> {
+            self.record(PinCommand::Pwm {
+                frequency,
+                duty_cycle,
+            });
+            Ok(())
+        }
+    }
+
+    /// Minuteur dont les actions attendent `RecordingHal::fire_timers`
+    #[derive(Clone, Default)]
+    pub struct ManualTimer {
+        pending: Arc<Mutex<Vec<(Instant, TimerAction)>>>,
+    }
+
+    impl Debug for ManualTimer {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("ManualTimer")
+                .field("pending", &self.pending.lock().unwrap().len())
+                .finish()
+        }
+    }
+
+    impl Timer for ManualTimer {
+        fn schedule(&mut self, at: Instant, action: TimerAction) -> Result<(), Box<dyn Error>> {
+            self.pending.lock().unwrap().push((at, action));
+            Ok(())
+        }
+    }
+
+    /// Garde toutes les commandes envoyées aux pins qu'il a créées, y compris leur état initial.
+    /// Les clones partagent le même historique et les mêmes minuteurs.
+    #[derive(Debug, Clone, Default)]
+    pub struct RecordingHal {
+        log: CommandLog,
+        timer: ManualTimer,
+    }
+
+    impl RecordingHal {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Toutes les commandes, dans l'ordre où elles ont été envoyées
+        pub fn commands(&self) -> Vec<Command> {
+            self.log.lock().unwrap().clone()
+        }
+
+        /// Commandes envoyées à la pin `pin`
+        pub fn commands_of(&self, pin: u8) -> Vec<Command> {
+            self.commands()
+                .into_iter()
+                .filter(|command| command.pin == pin)
+                .collect()
+        }
+
+        /// Dernière commande envoyée à la pin `pin`
+        pub fn last_command_of(&self, pin: u8) -> Option<PinCommand> {
+            self.commands_of(pin).last().map(|command| command.command)
+        }
+
+        pub fn clear(&self) {
+            self.log.lock().unwrap().clear();
+        }
+
+        /// Lance les actions des minuteurs prévues au plus tard à `now`.
+        /// Renvoit le nombre d'actions lancées.
+        pub fn fire_timers(&self, now: Instant) -> usize {
+            // les actions sont lancées sans garder le verrou : elles peuvent programmer un minuteur
+            let due = {
+                let mut pending = self.timer.pending.lock().unwrap();
+                let (due, later): (Vec<_>, Vec<_>) =
+                    pending.drain(..).partition(|(at, _)| *at <= now);
+                *pending = later;
+                due
+            };
+            let fired = due.len();
+            for (_, action) in due {
+                action();
+            }
+            fired
+        }
+    }
+
+    impl Hal for RecordingHal {
+        type Pin = RecordingPin;
+
+        fn output(&mut self, pin: u8, initial_high: bool) -> Result<Self::Pin, Box<dyn Error>> {
+            let mut output = RecordingPin {
+                pin,
+                log: Arc::clone(&self.log),
+            };
+            if initial_high {
+                output.set_high();
+            } else {
+                output.set_low();
+            }
+            Ok(output)
+        }
+
+        fn timer(&mut self) -> Box<dyn Timer> {
+            Box::new(self.timer.clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_recording_hal() {
+        let mut hal = RecordingHal::new();
+        let mut pwm = hal.output(4, true).unwrap();
+        let mut direction = hal.output(17, false).unwrap();
+        pwm.set_pwm_frequency(500.0, 0.25).unwrap();
+        direction.set_high();
+
+        let commands = hal.commands();
+        assert_eq!(commands.len(), 4);
+        assert!(commands.windows(2).all(|pair| pair[0].at <= pair[1].at));
+        assert_eq!(
+            hal.last_command_of(4),
+            Some(PinCommand::Pwm {
+                frequency: 500.0,
+                duty_cycle: 0.25
+            })
+        );
+        assert_eq!(
+            hal.commands_of(17)
+                .iter()
+                .map(|command| command.command)
+                .collect::<Vec<_>>(),
+            [PinCommand::Low, PinCommand::High]
+        );
+        assert_eq!(hal.last_command_of(5), None);
+
+        hal.clear();
+        assert!(hal.clone().commands().is_empty());
+    }
+
+    #[test]
+    fn test_recording_hal_timers() {
+        let hal = RecordingHal::new();
+        let mut timer = hal.clone().timer();
+        let start = Instant::now();
+        let mut pin = hal.clone().output(4, false).unwrap();
+        pin.set_high();
+        timer
+            .schedule(
+                start + Duration::from_millis(10),
+                Box::new(move || pin.set_low()),
+            )
+            .unwrap();
+
+        assert_eq!(hal.fire_timers(start), 0);
+        assert_eq!(hal.last_command_of(4), Some(PinCommand::High));
+        assert_eq!(hal.fire_timers(start + Duration::from_millis(10)), 1);
+        assert_eq!(hal.last_command_of(4), Some(PinCommand::Low));
+        assert_eq!(hal.fire_timers(start + Duration::from_secs(1)), 0);
+    }
+
+    #[test]
+    fn test_thread_timer() {
+        let (sender, receiver) = mpsc::channel();
+        let at = Instant::now() + Duration::from_millis(50);
+        ThreadTimer
+            .schedule(at, Box::new(move || sender.send(Instant::now()).unwrap()))
+            .unwrap();
+        // seul test sur l'horloge réelle : la borne haute est large pour ne pas dépendre de la charge
+        let fired = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(fired >= at);
+    }
+}
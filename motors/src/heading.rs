@@ -0,0 +1,221 @@
+//! Asservissement de l'orientation du robot.
+//!
+//! `HeadingController` est un PID sur l'écart entre l'orientation mesurée (lidar ou IMU)
+//! et l'orientation voulue. Il renvoit une vitesse de rotation à combiner avec la translation,
+//! le robot peut donc tourner en avançant.
+
+use robot_common::units::*;
+use std::f64::consts::{PI, TAU};
+use std::time::Duration;
+use tracing::debug;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PidGains {
+    /// (rad/s) par rad d'écart
+    pub kp: f64,
+    /// (rad/s) par rad·s d'écart accumulé
+    pub ki: f64,
+    /// (rad/s) par rad/s de variation de l'écart
+    pub kd: f64,
+}
+
+impl PidGains {
+    /// Valeurs de départ, à régler sur le robot
+    pub const DEFAULT: PidGains = PidGains {
+        kp: 6.0,
+        ki: 1.0,
+        kd: 0.3,
+    };
+}
+
+impl Default for PidGains {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// vitesse de rotation maximale demandée par défaut
+pub const MAX_ROTATION_SPEED: RadPerSecond = RadPerSecond(8.0);
+
+/// Ramène un angle dans ]-pi, pi]
+#[inline]
+pub fn wrap_angle(angle: Rad) -> Rad {
+    let wrapped = angle.val().rem_euclid(TAU);
+    Rad::new(if wrapped > PI { wrapped - TAU } else { wrapped })
+}
+
+/// PID d'orientation avec anti-windup : l'intégrale n'augmente plus quand la sortie est
+/// saturée dans le sens de l'écart, et elle est bornée pour ne jamais saturer seule.
+#[derive(Debug, Clone)]
+pub struct HeadingController {
+    pub gains: PidGains,
+    pub max_rotation_speed: RadPerSecond,
+    /// rad·s
+    integral: f64,
+    last_error: Option<Rad>,
+}
+
+impl HeadingController {
+    pub fn new(gains: PidGains, max_rotation_speed: RadPerSecond) -> Self {
+        Self {
+            gains,
+            max_rotation_speed,
+            integral: 0.0,
+            last_error: None,
+        }
+    }
+
+    /// Écart (voulu - mesuré) du dernier `update`, dans ]-pi, pi]
+    #[inline]
+    pub fn last_error(&self) -> Option<Rad> {
+        self.last_error
+    }
+
+    /// À appeler quand l'orientation voulue change brutalement ou après un arrêt,
+    /// pour ne pas garder l'intégrale et la dérivée de l'ancienne consigne
+    pub fn reset(&mut self) {
+        self.integral = 0.0;
+        self.last_error = None;
+    }
+
+    /// Vitesse de rotation (positive vers la gauche) pour passer de `heading` à `target`
+    /// par le plus court chemin. `dt` est le temps écoulé depuis l'appel précédent.
+    pub fn update(&mut self, heading: Rad, target: Rad, dt: Duration) -> RadPerSecond {
+        let error = wrap_angle(target - heading);
+        let dt = dt.as_secs_f64();
+        let derivative = match self.last_error {
+            // l'écart peut lui aussi passer de pi à -pi
+            Some(last_error) if dt > 0.0 => wrap_angle(error - last_error).val() / dt,
+            _ => 0.0,
+        };
+        self.last_error = Some(error);
+
+        let without_integral = self.gains.kp * error.val() + self.gains.kd * derivative;
+        let max = self.max_rotation_speed.0;
+        let saturated = without_integral + self.gains.ki * self.integral;
+        // anti-windup : on n'intègre que si ça ne pousse pas plus loin une sortie déjà saturée
+        if saturated.abs() < max || saturated.signum() != error.val().signum() {
+            self.integral += error.val() * dt;
+        }
+        if self.gains.ki > 0.0 {
+            let integral_limit = max / self.gains.ki;
+            self.integral = self.integral.clamp(-integral_limit, integral_limit);
+        }
+
+        let output = (without_integral + self.gains.ki * self.integral).clamp(-max, max);
+        debug!(
+            error = error.val(),
+            integral = self.integral,
+            output,
+            "asservissement de l'orientation"
+        );
+        RadPerSecond(output)
+    }
+}
+
+impl Default for HeadingController {
+    fn default() -> Self {
+        Self::new(PidGains::DEFAULT, MAX_ROTATION_SPEED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: Duration = Duration::from_millis(10);
+
+    /// simule un robot qui tourne exactement à la vitesse demandée,
+    /// avec une perturbation constante `disturbance`
+    fn simulate(
+        controller: &mut HeadingController,
+        mut heading: Rad,
+        target: Rad,
+        disturbance: RadPerSecond,
+        steps: usize,
+    ) -> Vec<Rad> {
+        (0..steps)
+            .map(|_| {
+                let omega = controller.update(heading, target, DT) + disturbance;
+                heading = wrap_angle(heading + omega * DT);
+                heading
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_wrap_angle() {
+        for (angle, wrapped) in [
+            (0.0, 0.0),
+            (PI, PI),
+            (-PI, PI),
+            (1.5 * PI, -0.5 * PI),
+            (7.0, 7.0 - TAU),
+        ] {
+            assert!(
+                (wrap_angle(Rad::new(angle)).val() - wrapped).abs() < 1e-12,
+                "{angle}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_converges_by_the_shortest_way() {
+        // de 170° à -170° : il faut tourner de 20° vers la gauche, pas de 340° vers la droite
+        let mut controller = HeadingController::default();
+        let start = Deg::new(170.0).rad();
+        let target = Deg::new(-170.0).rad();
+        let first = controller.update(start, target, DT);
+        assert!(first > RadPerSecond::ZERO);
+        let error = controller.last_error().unwrap();
+        assert!((error - Deg::new(20.0).rad()).val().abs() < 1e-9);
+
+        let headings = simulate(&mut controller, start, target, RadPerSecond::ZERO, 300);
+        let last = *headings.last().unwrap();
+        assert!(wrap_angle(last - target).val().abs() < 1e-2, "{last:?}");
+    }
+
+    #[test]
+    fn test_rejects_disturbance_without_windup() {
+        let mut controller = HeadingController::default();
+        let target = Rad::new(1.0);
+        // perturbation constante (ex. roue qui frotte) : seul l'intégrateur peut la compenser
+        let headings = simulate(&mut controller, Rad::ZERO, target, RadPerSecond(-0.5), 2000);
+        let last = *headings.last().unwrap();
+        assert!(wrap_angle(last - target).val().abs() < 1e-2, "{last:?}");
+
+        // longue saturation : l'intégrale reste bornée et le dépassement faible
+        let mut controller = HeadingController::default();
+        let mut heading = Rad::ZERO;
+        for _ in 0..500 {
+            // robot bloqué, l'écart reste à 3 rad
+            controller.update(heading, Rad::new(3.0), DT);
+        }
+        let headings = simulate(
+            &mut controller,
+            heading,
+            Rad::new(3.0),
+            RadPerSecond::ZERO,
+            500,
+        );
+        heading = *headings.last().unwrap();
+        let overshoot = headings
+            .iter()
+            .map(|heading| wrap_angle(*heading - Rad::new(3.0)).val())
+            .fold(f64::MIN, f64::max);
+        assert!(overshoot < 0.1, "{overshoot}");
+        // l'intégrale accumulée pendant l'approche se vide lentement (kp / ki)
+        assert!(wrap_angle(heading - Rad::new(3.0)).val().abs() < 5e-2);
+    }
+
+    #[test]
+    fn test_output_is_bounded() {
+        let mut controller = HeadingController::default();
+        let omega = controller.update(Rad::ZERO, Rad::new(3.0), DT);
+        assert_eq!(omega, MAX_ROTATION_SPEED);
+        controller.reset();
+        assert!(controller.last_error().is_none());
+        let omega = controller.update(Rad::ZERO, Rad::new(-3.0), DT);
+        assert_eq!(omega, -MAX_ROTATION_SPEED);
+    }
+}
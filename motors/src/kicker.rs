@@ -0,0 +1,164 @@
+//! Machine à états du kicker : repos → tir → recharge → repos.
+//!
+//! Le tir ne bloque plus la boucle de contrôle : `request` le déclenche et `tick`, appelé à chaque
+//! tour de boucle avec l'heure actuelle, l'arrête au bout de `fire_time`. Le kicker reste ensuite
+//! inutilisable jusqu'à `time_between_kicks` après le début du tir.
+//! L'heure est toujours passée en paramètre, les tests utilisent donc une horloge factice.
+//!
+//! Cette machine à états ne coupe rien elle-même : si `tick` est appelé en retard, le tir
+//! se termine en retard. La durée maximale du tir est garantie par le minuteur lancé par
+//! `motors::BallControl::kick`.
+
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KickerState {
+    Idle,
+    /// pins du kicker alimentées depuis `since`
+    Firing {
+        since: Instant,
+    },
+    /// tir terminé, en attente de la fin du délai entre deux tirs
+    Cooldown {
+        last_kick: Instant,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Kicker {
+    state: KickerState,
+    /// temps maximal pendant lequel les pins sont alimentées
+    fire_time: Duration,
+    /// temps minimal entre le début de deux tirs
+    time_between_kicks: Duration,
+}
+
+impl Kicker {
+    pub fn new(fire_time: Duration, time_between_kicks: Duration) -> Self {
+        Self {
+            state: KickerState::Idle,
+            fire_time,
+            time_between_kicks,
+        }
+    }
+
+    #[cfg(test)]
+    #[inline]
+    pub fn state(&self) -> KickerState {
+        self.state
+    }
+
+    /// Est-ce que les pins du kicker doivent être alimentées
+    #[inline]
+    pub fn is_firing(&self) -> bool {
+        matches!(self.state, KickerState::Firing { .. })
+    }
+
+    /// Demande un tir. Renvoit `false` si le kicker est déjà en train de tirer ou de recharger.
+    pub fn request(&mut self, now: Instant) -> bool {
+        self.tick(now);
+        if self.state == KickerState::Idle {
+            self.state = KickerState::Firing { since: now };
+            true
+        } else {
+            debug!("kick ignoré, kicker dans l'état {:?}", self.state);
+            false
+        }
+    }
+
+    /// Fait avancer la machine à états jusqu'à `now` et renvoit `is_firing`
+    pub fn tick(&mut self, now: Instant) -> bool {
+        if let KickerState::Firing { since } = self.state {
+            let fired = now.saturating_duration_since(since);
+            if fired >= self.fire_time {
+                if fired > self.fire_time * 2 {
+                    warn!("kicker alimenté pendant {fired:?} : tick appelé trop rarement");
+                }
+                self.state = KickerState::Cooldown { last_kick: since };
+            }
+        }
+        if let KickerState::Cooldown { last_kick } = self.state {
+            if now.saturating_duration_since(last_kick) >= self.time_between_kicks {
+                self.state = KickerState::Idle;
+            }
+        }
+        self.is_firing()
+    }
+
+    /// Arrête le tir en cours, le délai avant le prochain tir est conservé
+    pub fn abort(&mut self) {
+        if let KickerState::Firing { since } = self.state {
+            self.state = KickerState::Cooldown { last_kick: since };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// horloge factice : `at(ms)` est l'instant `ms` millisecondes après le début du test
+    struct FakeClock(Instant);
+
+    impl FakeClock {
+        fn new() -> Self {
+            Self(Instant::now())
+        }
+
+        fn at(&self, millis: u64) -> Instant {
+            self.0 + Duration::from_millis(millis)
+        }
+    }
+
+    fn kicker() -> Kicker {
+        Kicker::new(Duration::from_millis(500), Duration::from_secs(2))
+    }
+
+    #[test]
+    fn test_fire_then_cooldown() {
+        let clock = FakeClock::new();
+        let mut kicker = kicker();
+        assert!(!kicker.tick(clock.at(0)));
+        assert!(kicker.request(clock.at(0)));
+        assert!(kicker.tick(clock.at(10)));
+        assert!(kicker.tick(clock.at(499)));
+        assert!(!kicker.tick(clock.at(500)));
+        assert_eq!(
+            kicker.state(),
+            KickerState::Cooldown {
+                last_kick: clock.at(0)
+            }
+        );
+        // le délai entre deux tirs compte depuis le début du tir précédent
+        assert!(!kicker.request(clock.at(1000)));
+        assert!(!kicker.request(clock.at(1999)));
+        assert!(kicker.request(clock.at(2000)));
+        assert!(kicker.is_firing());
+    }
+
+    #[test]
+    fn test_late_tick_catches_up() {
+        let clock = FakeClock::new();
+        let mut kicker = kicker();
+        kicker.request(clock.at(0));
+        // un seul tick, bien après la fin du tir et du délai
+        assert!(!kicker.tick(clock.at(5000)));
+        assert_eq!(kicker.state(), KickerState::Idle);
+        // une deuxième demande pendant le tir ne le prolonge pas
+        kicker.request(clock.at(6000));
+        assert!(!kicker.request(clock.at(6400)));
+        assert!(!kicker.tick(clock.at(6500)));
+    }
+
+    #[test]
+    fn test_abort() {
+        let clock = FakeClock::new();
+        let mut kicker = kicker();
+        kicker.request(clock.at(0));
+        kicker.abort();
+        assert!(!kicker.is_firing());
+        assert!(!kicker.request(clock.at(100)));
+        assert!(kicker.request(clock.at(2000)));
+    }
+}
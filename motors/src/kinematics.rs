@@ -0,0 +1,248 @@
+//! Cinématique des 4 roues omnidirectionnelles.
+//!
+//! Repère `Local` : x à droite du robot, y vers le kicker, rotation positive vers la gauche.
+//! Une roue placée à l'angle `θ` du kicker (sens trigonométrique) est à la position
+//! `R (-sin θ, cos θ)` et fait avancer le robot dans la direction `(cos θ, sin θ)` quand elle
+//! tourne dans le sens positif. Sa vitesse au sol est donc
+//!
+//! `v = cos θ · vx + sin θ · vy - R · ω`
+//!
+//! ce qui donne une matrice 4×3 (une ligne par roue), inversée au sens des moindres carrés
+//! pour retrouver la vitesse du robot depuis celles des roues (odométrie).
+
+use robot_common::units::*;
+
+/// rayon des roues
+pub const WHEEL_RADIUS: Meters = Meters::cm(3.0);
+/// distance entre le centre du robot et le point de contact des roues
+pub const ROBOT_RADIUS: Meters = Meters::cm(8.0);
+/// vitesse de rotation d'une roue quand son moteur est à fond (PWM à 1), à mesurer sur le robot
+pub const MAX_WHEEL_SPEED: RadPerSecond = RadPerSecond(100.0);
+
+/// Vitesse du robot dans son repère
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Velocity {
+    pub vx: MetersPerSecond,
+    pub vy: MetersPerSecond,
+    pub omega: RadPerSecond,
+}
+
+impl Velocity {
+    pub const ZERO: Velocity = Velocity::new(
+        MetersPerSecond(0.0),
+        MetersPerSecond(0.0),
+        RadPerSecond(0.0),
+    );
+
+    #[inline]
+    pub const fn new(vx: MetersPerSecond, vy: MetersPerSecond, omega: RadPerSecond) -> Self {
+        Self { vx, vy, omega }
+    }
+
+    /// Même mouvement, `factor` fois plus vite
+    #[inline]
+    pub fn scale(&self, factor: f64) -> Self {
+        Self::new(self.vx * factor, self.vy * factor, self.omega * factor)
+    }
+}
+
+/// Matrice de cinématique d'un robot à 4 roues, dans l'ordre avant droite, avant gauche,
+/// arrière droite, arrière gauche
+#[derive(Debug, Clone, Copy)]
+pub struct Kinematics {
+    /// `[cos θ, sin θ, -R]` pour chaque roue
+    rows: [[f64; 3]; 4],
+    wheel_radius: Meters,
+    max_wheel_speed: RadPerSecond,
+}
+
+impl Kinematics {
+    /// `wheel_angles` : angle de chaque roue par rapport au kicker, comme les constantes `*_ANGLE`
+    pub fn new(
+        wheel_angles: [Rad; 4],
+        wheel_radius: Meters,
+        robot_radius: Meters,
+        max_wheel_speed: RadPerSecond,
+    ) -> Self {
+        Self {
+            rows: wheel_angles.map(|angle| [angle.cos(), angle.sin(), -robot_radius.0]),
+            wheel_radius,
+            max_wheel_speed,
+        }
+    }
+
+    /// Vitesse de rotation de chaque roue pour obtenir `velocity` (sans saturation)
+    pub fn inverse(&self, velocity: Velocity) -> [RadPerSecond; 4] {
+        self.rows.map(|[cos, sin, minus_radius]| {
+            let ground_speed = velocity.vx * cos
+                + velocity.vy * sin
+                + MetersPerSecond(velocity.omega.0 * minus_radius);
+            RadPerSecond(ground_speed.0 / self.wheel_radius.0)
+        })
+    }
+
+    /// Vitesse du robot la plus proche (moindres carrés) des vitesses de roues mesurées ou commandées
+    pub fn forward(&self, wheel_speeds: [RadPerSecond; 4]) -> Velocity {
+        let ground_speeds = wheel_speeds.map(|speed| speed.0 * self.wheel_radius.0);
+        // (JᵀJ) x = Jᵀ v
+        let mut jtj = [[0.0; 3]; 3];
+        let mut jtv = [0.0; 3];
+        for (row, ground_speed) in self.rows.iter().zip(ground_speeds) {
+            for i in 0..3 {
+                jtv[i] += row[i] * ground_speed;
+                for j in 0..3 {
+                    jtj[i][j] += row[i] * row[j];
+                }
+            }
+        }
+        let [vx, vy, omega] = solve_3x3(jtj, jtv);
+        Velocity::new(
+            MetersPerSecond(vx),
+            MetersPerSecond(vy),
+            RadPerSecond(omega),
+        )
+    }
+
+    /// Commandes des moteurs (entre -1 et 1) pour `velocity`.
+    /// Si une roue devrait dépasser sa vitesse maximale, toutes les roues sont ralenties
+    /// du même facteur : le robot garde sa direction et sa courbure, mais va moins vite.
+    pub fn wheel_commands(&self, velocity: Velocity) -> [f64; 4] {
+        let commands = self
+            .inverse(velocity)
+            .map(|speed| speed.0 / self.max_wheel_speed.0);
+        let fastest = commands
+            .iter()
+            .fold(0.0f64, |max, command| max.max(command.abs()));
+        if fastest > 1.0 {
+            commands.map(|command| (command / fastest).clamp(-1.0, 1.0))
+        } else {
+            commands
+        }
+    }
+
+    /// Vitesse du robot quand les moteurs reçoivent `commands`, voir `forward`
+    pub fn velocity_of_commands(&self, commands: [f64; 4]) -> Velocity {
+        self.forward(commands.map(|command| self.max_wheel_speed * command))
+    }
+}
+
+/// Résout `a x = b` par la règle de Cramer, `a` doit être inversible
+fn solve_3x3(a: [[f64; 3]; 3], b: [f64; 3]) -> [f64; 3] {
+    let determinant = |m: [[f64; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+    let det = determinant(a);
+    [0, 1, 2].map(|column| {
+        let mut replaced = a;
+        for (row, value) in replaced.iter_mut().zip(b) {
+            row[column] = value;
+        }
+        determinant(replaced) / det
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const APPROX_LIMIT: f64 = 1e-9;
+
+    fn kinematics() -> Kinematics {
+        let angle = Rad::new(0.698132);
+        let back_angle = Rad::new(2.44346);
+        Kinematics::new(
+            [-angle, angle, -back_angle, back_angle],
+            WHEEL_RADIUS,
+            ROBOT_RADIUS,
+            MAX_WHEEL_SPEED,
+        )
+    }
+
+    fn assert_velocity_eq(a: Velocity, b: Velocity) {
+        assert!(
+            (a.vx - b.vx).abs() < MetersPerSecond(APPROX_LIMIT)
+                && (a.vy - b.vy).abs() < MetersPerSecond(APPROX_LIMIT)
+                && (a.omega - b.omega).abs() < RadPerSecond(APPROX_LIMIT),
+            "{a:?} != {b:?}"
+        );
+    }
+
+    #[test]
+    fn test_forward_inverts_inverse() {
+        let kinematics = kinematics();
+        for velocity in [
+            Velocity::ZERO,
+            Velocity::new(
+                MetersPerSecond(0.0),
+                MetersPerSecond(1.0),
+                RadPerSecond(0.0),
+            ),
+            Velocity::new(
+                MetersPerSecond(-0.4),
+                MetersPerSecond(0.3),
+                RadPerSecond(2.0),
+            ),
+            Velocity::new(
+                MetersPerSecond(0.7),
+                MetersPerSecond(-1.2),
+                RadPerSecond(-5.0),
+            ),
+        ] {
+            assert_velocity_eq(kinematics.forward(kinematics.inverse(velocity)), velocity);
+        }
+    }
+
+    #[test]
+    fn test_pure_rotation_and_translation() {
+        let kinematics = kinematics();
+        // rotation sur place : toutes les roues à la même vitesse, sens négatif pour tourner à gauche
+        let spin = kinematics.inverse(Velocity::new(
+            MetersPerSecond::ZERO,
+            MetersPerSecond::ZERO,
+            RadPerSecond(1.0),
+        ));
+        for speed in spin {
+            assert!((speed.0 + ROBOT_RADIUS.0 / WHEEL_RADIUS.0).abs() < APPROX_LIMIT);
+        }
+        // tout droit : les roues avant tournent en sens opposé, comme les roues arrière
+        let [fr, fl, br, bl] = kinematics.inverse(Velocity::new(
+            MetersPerSecond::ZERO,
+            MetersPerSecond(1.0),
+            RadPerSecond::ZERO,
+        ));
+        assert!(
+            (fr + fl).abs() < RadPerSecond(APPROX_LIMIT)
+                && (br + bl).abs() < RadPerSecond(APPROX_LIMIT)
+        );
+        assert!(fr < RadPerSecond::ZERO && br < RadPerSecond::ZERO);
+    }
+
+    #[test]
+    fn test_saturation_preserves_direction() {
+        let kinematics = kinematics();
+        let wanted = Velocity::new(
+            MetersPerSecond(3.0),
+            MetersPerSecond(4.0),
+            RadPerSecond(10.0),
+        );
+        let commands = kinematics.wheel_commands(wanted);
+        let fastest = commands
+            .iter()
+            .fold(0.0f64, |max, command| max.max(command.abs()));
+        assert!((fastest - 1.0).abs() < APPROX_LIMIT, "{commands:?}");
+
+        let obtained = kinematics.velocity_of_commands(commands);
+        let factor = obtained.vx / wanted.vx;
+        assert!(0.0 < factor && factor < 1.0);
+        assert_velocity_eq(obtained, wanted.scale(factor));
+
+        // pas de saturation si ce n'est pas nécessaire
+        let slow = wanted.scale(0.01);
+        assert_velocity_eq(
+            kinematics.velocity_of_commands(kinematics.wheel_commands(slow)),
+            slow,
+        );
+    }
+}
@@ -1,48 +1,77 @@
-
+mod hal;
+mod heading;
+mod kicker;
+mod kinematics;
+mod motion_profile;
 mod motors;
-mod vector2;
-use motors::{Bogie, BallControl};
+mod position;
+use hal::{NoopHal, Output};
+use motion_profile::CONTROL_PERIOD;
+use motors::{BallControl, Bogie};
 use std::error::Error;
 
-use crate::vector2::Vector2;
-use radians::{Angle, Rad32};
-use std::fs::OpenOptions;
-use tracing::{info, subscriber};
-use tracing_panic::panic_hook;
-use tracing_subscriber::layer::SubscriberExt;
-use tracing_subscriber::{self, fmt, Registry};
+use robot_common::frames::{Global, GlobalPoint, Pose2, Vector2};
+use robot_common::units::Rad;
+use robot_logging::{set_up_logging, LogConfig};
 use std::thread::sleep;
 use std::time::{Duration, Instant};
+use tracing::info;
 
-#[inline]
-fn set_up_logging() -> Result<(), Box<dyn Error>> {
-    std::fs::create_dir_all("log")?;
-    let log_file = OpenOptions::new()
-        .append(true)
-        .create(true)
-        .open("./log/motors.log")?;
-    let subscriber_param = Registry::default()
-        .with(fmt::layer().with_ansi(true))
-        .with(fmt::layer().with_ansi(false).with_writer(log_file));
-    subscriber::set_global_default(subscriber_param)?;
+/// pose à rejoindre au démarrage, pour essayer l'asservissement sur le terrain
+const DEMO_TARGET: Pose2<Global> = Pose2::new(GlobalPoint::cm(100.0, 70.0), Rad::ZERO);
+/// temps entre deux kicks de démonstration
+const DEMO_KICK_PERIOD: Duration = Duration::from_millis(4000);
 
-    std::panic::set_hook(Box::new(panic_hook));
-    info!("NEW START");
-    Ok(())
+/// `motors --noop` fait tourner la boucle de contrôle sans toucher aux pins,
+/// pour l'essayer sur un ordinateur.
+fn main() -> Result<(), Box<dyn Error>> {
+    set_up_logging(&LogConfig::new("motors"))?;
+    if std::env::args().skip(1).any(|arg| arg == "--noop") {
+        info!("lancé sans matériel");
+        run(
+            Bogie::with_hal(&mut NoopHal)?,
+            BallControl::with_hal(&mut NoopHal)?,
+        )
+    } else {
+        run(Bogie::default()?, BallControl::default()?)
+    }
 }
 
-
-fn main() -> Result<(), Box<dyn Error>> {
-    set_up_logging()?;
-    let mut bogie = Bogie::default().unwrap();
-    let mut ball_control = BallControl::default().unwrap();
+/// Boucle de contrôle des moteurs, un tour toutes les `CONTROL_PERIOD`.
+///
+/// En attendant de recevoir la pose du lidar et les actions des stratégies, le robot rejoint
+/// `DEMO_TARGET` en estimant sa pose par odométrie (depuis l'origine) et tire toutes les
+/// `DEMO_KICK_PERIOD`.
+fn run<P: Output + 'static>(
+    mut bogie: Bogie<P>,
+    mut ball_control: BallControl<P>,
+) -> Result<(), Box<dyn Error>> {
     info!("init ok");
-    //bogie.front_right.rotate(0.5);
-    bogie.go_to(Vector2::new(1.0, 0.7), 1.0, Rad32::new(0.0));
     ball_control.dribble(0.5);
+    let mut pose = Pose2::new(GlobalPoint::ORIGIN, Rad::ZERO);
+    let mut last_kick = Instant::now();
+    let mut next_tick = Instant::now();
     loop {
-         ball_control.perform_kick();
-         sleep(Duration::from_millis(4000));
+        let now = Instant::now();
+        if !bogie.position.arrived() && bogie.go_to_pose(pose, DEMO_TARGET, None) {
+            info!(
+                "pose estimée à l'arrivée {pose:?}, écart d'orientation {:?}",
+                bogie.heading.last_error()
+            );
+            bogie.full_stop();
+        }
+        bogie.tick();
+        let velocity = bogie.estimated_velocity();
+        let local_move = Vector2::new(velocity.vx * CONTROL_PERIOD, velocity.vy * CONTROL_PERIOD);
+        pose.position = pose.position + local_move.to_global(pose);
+        pose.heading += velocity.omega * CONTROL_PERIOD;
+
+        if now - last_kick >= DEMO_KICK_PERIOD && ball_control.kick(now) {
+            last_kick = now;
+        }
+        ball_control.tick(now);
+
+        next_tick += CONTROL_PERIOD;
+        sleep(next_tick.saturating_duration_since(Instant::now()));
     }
-    Ok(())
 }
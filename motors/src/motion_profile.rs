@@ -0,0 +1,260 @@
+//! Rampes de vitesse du robot.
+//!
+//! Les roues ne reçoivent jamais directement la vitesse demandée : `MotionProfile` la rejoint
+//! progressivement, à chaque pas de `CONTROL_PERIOD`, en limitant sur chaque axe (x, y et rotation)
+//! l'accélération, la décélération et le jerk (variation de l'accélération).
+//! Sans ça, les changements de direction font patiner les roues et faussent les scans du lidar.
+
+use crate::kinematics::Velocity;
+use robot_common::units::*;
+use std::time::Duration;
+
+/// période de la boucle de contrôle des moteurs (100 Hz)
+pub const CONTROL_PERIOD: Duration = Duration::from_millis(10);
+
+/// Limites d'un axe, dans l'unité de l'axe (m ou rad) par s², s² et s³
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisLimits {
+    /// quand la vitesse augmente en valeur absolue
+    pub acceleration: f64,
+    /// quand la vitesse diminue en valeur absolue ou change de sens
+    pub deceleration: f64,
+    /// variation maximale de l'accélération
+    pub jerk: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotionLimits {
+    pub x: AxisLimits,
+    pub y: AxisLimits,
+    pub rotation: AxisLimits,
+}
+
+impl MotionLimits {
+    /// Valeurs de départ, à ajuster sur le terrain
+    pub const DEFAULT: MotionLimits = MotionLimits {
+        x: AxisLimits {
+            acceleration: 2.0,
+            deceleration: 4.0,
+            jerk: 40.0,
+        },
+        y: AxisLimits {
+            acceleration: 2.0,
+            deceleration: 4.0,
+            jerk: 40.0,
+        },
+        rotation: AxisLimits {
+            acceleration: 20.0,
+            deceleration: 40.0,
+            jerk: 400.0,
+        },
+    };
+}
+
+impl Default for MotionLimits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Vitesse et accélération d'un axe
+#[derive(Debug, Clone, Copy, Default)]
+struct AxisRamp {
+    velocity: f64,
+    acceleration: f64,
+}
+
+impl AxisRamp {
+    fn step(&mut self, target: f64, limits: &AxisLimits, dt: f64) {
+        let error = target - self.velocity;
+        if error == 0.0 {
+            self.acceleration = 0.0;
+            return;
+        }
+        let limit = if self.velocity * error < 0.0 {
+            limits.deceleration
+        } else {
+            limits.acceleration
+        };
+        // il faut commencer à réduire l'accélération avant d'arriver à la cible :
+        // passer de `a` à 0 avec un jerk `j` ajoute encore a² / 2j à la vitesse
+        let wanted = (error.abs() / dt)
+            .min(limit)
+            .min((2.0 * limits.jerk * error.abs()).sqrt())
+            .copysign(error);
+        let max_change = limits.jerk * dt;
+        // en passant de la décélération à l'accélération, la limite baisse d'un coup :
+        // l'accélération la rejoint elle aussi en respectant le jerk
+        self.acceleration += (wanted - self.acceleration).clamp(-max_change, max_change);
+
+        let velocity = self.velocity + self.acceleration * dt;
+        if (target - velocity) * error <= 0.0 {
+            // cible atteinte ou dépassée pendant ce pas
+            self.velocity = target;
+            self.acceleration = 0.0;
+        } else {
+            self.velocity = velocity;
+        }
+    }
+}
+
+/// Vitesse du robot qui rejoint progressivement la vitesse demandée.
+/// `step` doit être appelé toutes les `CONTROL_PERIOD`.
+#[derive(Debug, Clone)]
+pub struct MotionProfile {
+    limits: MotionLimits,
+    target: Velocity,
+    x: AxisRamp,
+    y: AxisRamp,
+    rotation: AxisRamp,
+}
+
+impl MotionProfile {
+    pub fn new(limits: MotionLimits) -> Self {
+        Self {
+            limits,
+            target: Velocity::ZERO,
+            x: AxisRamp::default(),
+            y: AxisRamp::default(),
+            rotation: AxisRamp::default(),
+        }
+    }
+
+    /// Vitesse à rejoindre, atteinte après plusieurs `step`
+    #[inline]
+    pub fn set_target(&mut self, target: Velocity) {
+        self.target = target;
+    }
+
+    /// Vitesse actuelle de la rampe
+    #[inline]
+    pub fn velocity(&self) -> Velocity {
+        Velocity::new(
+            MetersPerSecond(self.x.velocity),
+            MetersPerSecond(self.y.velocity),
+            RadPerSecond(self.rotation.velocity),
+        )
+    }
+
+    #[cfg(test)]
+    #[inline]
+    pub fn reached_target(&self) -> bool {
+        self.velocity() == self.target
+    }
+
+    /// Avance d'une période de contrôle et renvoit la vitesse à envoyer aux roues
+    pub fn step(&mut self) -> Velocity {
+        let dt = CONTROL_PERIOD.as_secs_f64();
+        self.x.step(self.target.vx.0, &self.limits.x, dt);
+        self.y.step(self.target.vy.0, &self.limits.y, dt);
+        self.rotation
+            .step(self.target.omega.0, &self.limits.rotation, dt);
+        self.velocity()
+    }
+
+    /// Arrêt immédiat, sans rampe (arrêt d'urgence)
+    pub fn reset(&mut self) {
+        self.target = Velocity::ZERO;
+        self.x = AxisRamp::default();
+        self.y = AxisRamp::default();
+        self.rotation = AxisRamp::default();
+    }
+}
+
+impl Default for MotionProfile {
+    fn default() -> Self {
+        Self::new(MotionLimits::DEFAULT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f64 = 0.01;
+    const APPROX_LIMIT: f64 = 1e-9;
+
+    /// vitesses successives de l'axe x jusqu'à atteindre la cible
+    fn run_x(profile: &mut MotionProfile, max_steps: usize) -> Vec<f64> {
+        let mut velocities = vec![profile.velocity().vx.0];
+        for _ in 0..max_steps {
+            velocities.push(profile.step().vx.0);
+            if profile.reached_target() {
+                return velocities;
+            }
+        }
+        panic!("cible non atteinte : {:?}", profile.velocity());
+    }
+
+    fn check_limits(velocities: &[f64], limits: &AxisLimits) {
+        let accelerations: Vec<f64> = velocities
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]) / DT)
+            .collect();
+        let max_limit = limits.acceleration.max(limits.deceleration);
+        for acceleration in &accelerations {
+            assert!(
+                acceleration.abs() <= max_limit + APPROX_LIMIT,
+                "{acceleration}"
+            );
+        }
+        // le jerk n'est limité que jusqu'à l'arrivée, où la rampe s'arrête net
+        for pair in accelerations[..accelerations.len() - 1].windows(2) {
+            let jerk = (pair[1] - pair[0]) / DT;
+            assert!(jerk.abs() <= limits.jerk + 1e-6, "{jerk}");
+        }
+    }
+
+    #[test]
+    fn test_ramp_up_respects_limits() {
+        let mut profile = MotionProfile::default();
+        profile.set_target(Velocity::new(
+            MetersPerSecond(1.0),
+            MetersPerSecond::ZERO,
+            RadPerSecond::ZERO,
+        ));
+        let velocities = run_x(&mut profile, 1000);
+        check_limits(&velocities, &MotionLimits::DEFAULT.x);
+        // 1 m/s à 2 m/s² : au moins 0.5 s
+        assert!(velocities.len() as f64 * DT >= 0.5);
+        assert!(velocities.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(profile.velocity().vy, MetersPerSecond::ZERO);
+    }
+
+    #[test]
+    fn test_direction_change_decelerates_first() {
+        let mut profile = MotionProfile::default();
+        profile.set_target(Velocity::new(
+            MetersPerSecond(1.0),
+            MetersPerSecond::ZERO,
+            RadPerSecond(3.0),
+        ));
+        while !profile.reached_target() {
+            profile.step();
+        }
+        profile.set_target(Velocity::new(
+            MetersPerSecond(-1.0),
+            MetersPerSecond::ZERO,
+            RadPerSecond(3.0),
+        ));
+        let velocities = run_x(&mut profile, 1000);
+        check_limits(&velocities, &MotionLimits::DEFAULT.x);
+        assert!(velocities.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert_eq!(profile.velocity().omega, RadPerSecond(3.0));
+    }
+
+    #[test]
+    fn test_reset_stops_immediately() {
+        let mut profile = MotionProfile::default();
+        profile.set_target(Velocity::new(
+            MetersPerSecond(0.5),
+            MetersPerSecond(0.5),
+            RadPerSecond(1.0),
+        ));
+        profile.step();
+        assert_ne!(profile.velocity(), Velocity::ZERO);
+        profile.reset();
+        assert_eq!(profile.velocity(), Velocity::ZERO);
+        assert_eq!(profile.step(), Velocity::ZERO);
+    }
+}
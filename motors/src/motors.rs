@@ -1,8 +1,15 @@
-use crate::vector2::Vector2;
+use crate::hal::{Hal, Output, RppalHal, Timer};
+use crate::heading::HeadingController;
+use crate::kicker::Kicker;
+use crate::kinematics::*;
+use crate::motion_profile::{MotionProfile, CONTROL_PERIOD};
+use crate::position::PositionController;
 use radians::{Angle, Rad32};
-use rppal::gpio::{Gpio, OutputPin};
+use robot_common::frames::{Global, Local, Pose2, Vector2};
+use robot_common::units::*;
+use rppal::gpio::OutputPin;
 use std::error::Error;
-use std::thread::sleep;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 use std::time::{Duration, Instant};
 use tracing::{debug, error, info, instrument, warn};
 
@@ -31,28 +38,31 @@ const KICKER_PIN2: u8 = 24;
 
 /// temps min entre deux kicks
 const TIME_BETWEEN_KICK: Duration = Duration::from_secs(2);
-/// temps d'un kick (temps où on donne de l'énergie aux pins)
+/// temps d'un kick (temps maximal où on donne de l'énergie aux pins)
 const KICK_TIME: Duration = Duration::from_millis(500);
 
 const COEF_ROTATION_SPEED: f32 = 0.6;
 
 /// Représente un moteur entrainant une roue
 /// Ancien nom MotorMov
+///
+/// Les pins passent par `hal::Output` (`OutputPin` sur le robot)
 #[derive(Debug)]
-pub struct Wheel {
-    pwm: OutputPin,
-    cwccw: OutputPin,
+pub struct Wheel<P: Output = OutputPin> {
+    pwm: P,
+    cwccw: P,
     pub angle_axis_kicker: Rad32,
 }
 
-impl Wheel {
-    pub fn new(
+impl<P: Output> Wheel<P> {
+    pub fn new<H: Hal<Pin = P>>(
+        hal: &mut H,
         pin_pwm: u8,
         pin_cwccw: u8,
         angle_axis_kicker: Rad32,
     ) -> Result<Self, Box<dyn Error>> {
-        let pwm = Gpio::new()?.get(pin_pwm)?.into_output_high();
-        let cwccw = Gpio::new()?.get(pin_cwccw)?.into_output_high();
+        let pwm = hal.output(pin_pwm, true)?;
+        let cwccw = hal.output(pin_cwccw, true)?;
         Ok(Self {
             pwm,
             cwccw,
@@ -61,31 +71,31 @@ impl Wheel {
     }
 
     #[inline]
-    pub fn default_fr() -> Result<Self, Box<dyn Error>> {
-        Self::new(FR_PWM, FR_CWCCW, Rad32::new(FR_ANGLE))
+    pub fn default_fr<H: Hal<Pin = P>>(hal: &mut H) -> Result<Self, Box<dyn Error>> {
+        Self::new(hal, FR_PWM, FR_CWCCW, Rad32::new(FR_ANGLE))
     }
 
     #[inline]
-    pub fn default_fl() -> Result<Self, Box<dyn Error>> {
-        Self::new(FL_PWM, FL_CWCCW, Rad32::new(FL_ANGLE))
+    pub fn default_fl<H: Hal<Pin = P>>(hal: &mut H) -> Result<Self, Box<dyn Error>> {
+        Self::new(hal, FL_PWM, FL_CWCCW, Rad32::new(FL_ANGLE))
     }
 
     #[inline]
-    pub fn default_br() -> Result<Self, Box<dyn Error>> {
-        Self::new(BR_PWM, BR_CWCCW, Rad32::new(BR_ANGLE))
+    pub fn default_br<H: Hal<Pin = P>>(hal: &mut H) -> Result<Self, Box<dyn Error>> {
+        Self::new(hal, BR_PWM, BR_CWCCW, Rad32::new(BR_ANGLE))
     }
 
     #[inline]
-    pub fn default_bl() -> Result<Self, Box<dyn Error>> {
-        Self::new(BL_PWM, BL_CWCCW, Rad32::new(BL_ANGLE))
+    pub fn default_bl<H: Hal<Pin = P>>(hal: &mut H) -> Result<Self, Box<dyn Error>> {
+        Self::new(hal, BL_PWM, BL_CWCCW, Rad32::new(BL_ANGLE))
     }
 
     /// Change le PWM du moteur
     /// vitesse entre -1 et 1
     #[instrument]
-    pub fn rotate(&mut self, speed: f32) -> () {
-        if -1.0 <= speed && speed <= 1.0 {
-            if 0.0 <= speed && speed <= 1.0 {
+    pub fn rotate(&mut self, speed: f32) {
+        if (-1.0..=1.0).contains(&speed) {
+            if (0.0..=1.0).contains(&speed) {
                 self.cwccw.set_high();
             } else {
                 self.cwccw.set_low();
@@ -102,146 +112,546 @@ impl Wheel {
         }
     }
 
-    pub fn stop(&mut self) -> () {
+    pub fn stop(&mut self) {
         self.rotate(0.0);
     }
+
+    /// Angle de la roue par rapport au kicker, comme dans les constantes `*_ANGLE`
+    #[inline]
+    pub fn angle(&self) -> Rad {
+        Rad::new(f64::from(
+            (self.angle_axis_kicker + Angle::QUARTER_TURN).val(),
+        ))
+    }
 }
 
 #[derive(Debug)]
-pub struct Bogie {
-    pub front_right: Wheel,
-    pub front_left: Wheel,
-    pub back_right: Wheel,
-    pub back_left: Wheel,
+pub struct Bogie<P: Output = OutputPin> {
+    pub front_right: Wheel<P>,
+    pub front_left: Wheel<P>,
+    pub back_right: Wheel<P>,
+    pub back_left: Wheel<P>,
+    kinematics: Kinematics,
+    /// dernières commandes envoyées aux roues, dans l'ordre de `Kinematics`
+    last_commands: [f64; 4],
+    /// rampe utilisée par `set_target_velocity` et `tick`
+    pub profile: MotionProfile,
+    /// asservissement utilisé par `steer`
+    pub heading: HeadingController,
+    /// asservissement utilisé par `go_to_pose`
+    pub position: PositionController,
 }
 
 impl Bogie {
+    /// Moteurs branchés sur les pins par défaut du Raspberry
     pub fn default() -> Result<Self, Box<dyn Error>> {
+        Self::with_hal(&mut RppalHal)
+    }
+}
+
+impl<P: Output> Bogie<P> {
+    pub fn with_hal<H: Hal<Pin = P>>(hal: &mut H) -> Result<Self, Box<dyn Error>> {
+        let front_right = Wheel::default_fr(hal)?;
+        let front_left = Wheel::default_fl(hal)?;
+        let back_left = Wheel::default_br(hal)?;
+        let back_right = Wheel::default_bl(hal)?;
+        let kinematics = Kinematics::new(
+            [&front_right, &front_left, &back_right, &back_left].map(Wheel::angle),
+            WHEEL_RADIUS,
+            ROBOT_RADIUS,
+            MAX_WHEEL_SPEED,
+        );
         Ok(Self {
-            front_right: Wheel::default_fr()?,
-            front_left: Wheel::default_fl()?,
-            back_left: Wheel::default_br()?,
-            back_right: Wheel::default_bl()?,
+            front_right,
+            front_left,
+            back_right,
+            back_left,
+            kinematics,
+            last_commands: [0.0; 4],
+            profile: MotionProfile::default(),
+            heading: HeadingController::default(),
+            position: PositionController::default(),
         })
     }
 
-    pub fn full_stop(&mut self) -> () {
+    pub fn full_stop(&mut self) {
         self.front_right.stop();
         self.front_left.stop();
         self.back_right.stop();
         self.back_left.stop();
+        self.last_commands = [0.0; 4];
+        self.profile.reset();
+        self.heading.reset();
     }
 
-    pub fn go_to(&mut self, to_local: Vector2, speed: f32, orientation: Rad32) -> () {
-        const ABCSISSE: Vector2 = Vector2::new(0.0, 1.0);
-
-        // The speed to be sent to the motors is calculated
-        let to_local_angle = Rad32::new(to_local.angle(&ABCSISSE)) * {
-            if to_local.x > 0.0 {
-                -1.0
-            } else {
-                1.0
-            }
+    /// Avance dans la direction `to_local` (seule sa direction compte).
+    /// `speed` est la commande de la roue la plus rapide (entre 0 et 1) et `orientation` est
+    /// ajoutée comme rotation proportionnelle, voir `set_velocity` pour une commande en m/s
+    /// et `steer` pour asservir l'orientation.
+    #[allow(dead_code)] // remplacée par `go_to_pose` dans la boucle de contrôle, gardée pour les essais
+    pub fn go_to(&mut self, to_local: Vector2<Local>, speed: f32, orientation: Rad32) {
+        let direction = to_local.norm();
+        let translation = if direction > Meters::ZERO {
+            // vitesse de 1 m/s dans la direction demandée, ramenée à `speed` pour la roue la plus rapide
+            let unit = Velocity::new(
+                MetersPerSecond(to_local.x / direction),
+                MetersPerSecond(to_local.y / direction),
+                RadPerSecond::ZERO,
+            );
+            let fastest = self
+                .kinematics
+                .inverse(unit)
+                .iter()
+                .fold(0.0f64, |max, wheel| max.max(wheel.0.abs()));
+            unit.scale(f64::from(speed) * MAX_WHEEL_SPEED.0 / fastest)
+        } else {
+            Velocity::ZERO
         };
-        debug!("to local angle {to_local_angle}");
-        let mut fr_speed = (to_local_angle - self.front_right.angle_axis_kicker).cos();
-        let mut fl_speed = (to_local_angle - self.front_left.angle_axis_kicker).cos();
-        let mut br_speed = (to_local_angle - self.back_right.angle_axis_kicker).cos();
-        let mut bl_speed = (to_local_angle - self.back_left.angle_axis_kicker).cos();
-
-        // The ratio to be used to calculate the speeds to be sent to the motors is calculated, taking into account the desired speed.
-        let maximum = fr_speed
-            .abs()
-            .max(fl_speed.abs().max(br_speed.abs().max(bl_speed.abs())));
-        let rapport = speed / maximum;
-
-        // Speeds are recalculated taking into account the desired speed and
-        // Sends speeds to motors
-        fr_speed *= rapport;
-        fl_speed *= rapport;
-        br_speed *= rapport;
-        bl_speed *= rapport;
-
-        let minimum = fr_speed.min(fl_speed.min(br_speed.min(bl_speed)));
-        let rotation = orientation * speed * COEF_ROTATION_SPEED;
-
-        if minimum - rotation.val() < -1.0 {
-            let rapport = (rotation.val() - 1.0) / minimum;
-            fr_speed *= rapport;
-            fl_speed *= rapport;
-            br_speed *= rapport;
-            bl_speed *= rapport;
-        }
+        // la rotation donne la même commande à toutes les roues
+        let rotation = f64::from((orientation * speed * COEF_ROTATION_SPEED).val());
+        let omega = RadPerSecond(rotation * MAX_WHEEL_SPEED.0 * WHEEL_RADIUS.0 / ROBOT_RADIUS.0);
+        debug!("go to {to_local:?} : {translation:?}, omega {omega:?}");
+        self.set_velocity(translation.vx, translation.vy, omega);
+    }
 
-        let fr_val = fr_speed - rotation.val();
-        let fl_val = fl_speed - rotation.val();
-        let br_val = br_speed - rotation.val();
-        let bl_val = bl_speed - rotation.val();
-        debug!("fr: {fr_val}");
-        debug!("fl: {fl_val}");
-        debug!("br: {br_val}");
-        debug!("bl: {bl_val}");
+    /// Fait avancer le robot à la vitesse `(vx, vy)` dans son repère en tournant à `omega`
+    /// (positif vers la gauche). Si c'est trop rapide pour une des roues, le robot suit
+    /// la même trajectoire moins vite.
+    pub fn set_velocity(&mut self, vx: MetersPerSecond, vy: MetersPerSecond, omega: RadPerSecond) {
+        let commands = self.kinematics.wheel_commands(Velocity::new(vx, vy, omega));
+        debug!("commandes des roues {commands:?}");
+        let [fr, fl, br, bl] = commands.map(|command| command as f32);
+        self.front_right.rotate(fr);
+        self.front_left.rotate(fl);
+        self.back_right.rotate(br);
+        self.back_left.rotate(bl);
+        self.last_commands = commands;
+    }
+
+    /// Vitesse que le robot rejoindra progressivement, à chaque `tick`, en respectant les
+    /// limites d'accélération de `profile`
+    #[inline]
+    pub fn set_target_velocity(&mut self, target: Velocity) {
+        self.profile.set_target(target);
+    }
+
+    /// Avance à `(vx, vy)` dans le repère du robot en tournant vers `target_heading`.
+    /// `heading` est l'orientation mesurée (lidar ou IMU), dans le même repère que la cible.
+    /// À appeler à chaque période de contrôle, avant `tick`.
+    pub fn steer(
+        &mut self,
+        vx: MetersPerSecond,
+        vy: MetersPerSecond,
+        heading: Rad,
+        target_heading: Rad,
+    ) -> RadPerSecond {
+        let omega = self.heading.update(heading, target_heading, CONTROL_PERIOD);
+        self.set_target_velocity(Velocity::new(vx, vy, omega));
+        omega
+    }
 
-        self.front_right.rotate(fr_speed - rotation.val());
-        self.front_left.rotate(fl_speed - rotation.val());
-        self.back_right.rotate(br_speed - rotation.val());
-        self.back_left.rotate(bl_speed - rotation.val());
+    /// Rejoint `target` depuis `pose` (donnée par le lidar), en asservissant la position et
+    /// l'orientation. `nearest_wall` ralentit le robot près des murs.
+    /// À appeler à chaque période de contrôle, avant `tick`. Renvoit `true` une fois arrivé.
+    pub fn go_to_pose(
+        &mut self,
+        pose: Pose2<Global>,
+        target: Pose2<Global>,
+        nearest_wall: Option<Meters>,
+    ) -> bool {
+        let command = self.position.update(pose, target, nearest_wall);
+        self.steer(
+            command.velocity.vx,
+            command.velocity.vy,
+            pose.heading,
+            target.heading,
+        );
+        command.arrived
+    }
+
+    /// Un pas de la boucle de contrôle, à appeler toutes les `motion_profile::CONTROL_PERIOD` :
+    /// avance la rampe et envoie la nouvelle vitesse aux roues
+    pub fn tick(&mut self) -> Velocity {
+        let velocity = self.profile.step();
+        self.set_velocity(velocity.vx, velocity.vy, velocity.omega);
+        velocity
+    }
+
+    /// Vitesse du robot estimée depuis les dernières commandes des moteurs (odométrie),
+    /// en supposant que les roues ne glissent pas et suivent leur commande
+    pub fn estimated_velocity(&self) -> Velocity {
+        self.kinematics.velocity_of_commands(self.last_commands)
     }
 }
 
 /// S'occupe du dribler et du kicker
 /// ancien DriblerKicker
 #[derive(Debug)]
-pub struct BallControl {
-    dribbler: Wheel,
-    kicker1: OutputPin,
-    kicker2: OutputPin,
-    last_kick_time: Option<Instant>,
+pub struct BallControl<P: Output = OutputPin> {
+    dribbler: Wheel<P>,
+    /// partagé avec les minuteurs qui coupent les pins à la fin de chaque kick
+    kicker: Arc<Mutex<KickerPins<P>>>,
+    /// fourni par le `Hal`
+    timer: Box<dyn Timer>,
+}
+
+/// Le kicker et ses deux pins, qui changent toujours ensemble
+#[derive(Debug)]
+struct KickerPins<P: Output> {
+    kicker: Kicker,
+    pin1: P,
+    pin2: P,
+}
+
+impl<P: Output> KickerPins<P> {
+    fn set_high(&mut self) {
+        self.pin1.set_high();
+        self.pin2.set_high();
+    }
+
+    fn set_low(&mut self) {
+        self.pin1.set_low();
+        self.pin2.set_low();
+    }
+
+    /// Fait avancer le kicker jusqu'à `now` et coupe les pins si le kick est terminé.
+    /// Renvoit `true` si les pins viennent d'être coupées.
+    fn tick(&mut self, now: Instant) -> bool {
+        let was_firing = self.kicker.is_firing();
+        if was_firing && !self.kicker.tick(now) {
+            self.set_low();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Même si un thread a paniqué en tenant le verrou, il faut pouvoir couper les pins
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
 }
 
 impl BallControl {
-    pub fn new(
+    /// Dribbler et kicker branchés sur les pins par défaut du Raspberry
+    pub fn default() -> Result<Self, Box<dyn Error>> {
+        Self::with_hal(&mut RppalHal)
+    }
+}
+
+impl<P: Output> BallControl<P> {
+    pub fn new<H: Hal<Pin = P>>(
+        hal: &mut H,
         pin_dribbler_pwm: u8,
         pin_dribbler_cwccw: u8,
         pin_kicker1: u8,
         pin_kicker2: u8,
     ) -> Result<Self, Box<dyn Error>> {
-        let kicker1 = Gpio::new()?.get(pin_kicker1)?.into_output_low();
-        let kicker2 = Gpio::new()?.get(pin_kicker2)?.into_output_low();
+        let pin1 = hal.output(pin_kicker1, false)?;
+        let pin2 = hal.output(pin_kicker2, false)?;
         Ok(Self {
-            dribbler: Wheel::new(pin_dribbler_pwm, pin_dribbler_cwccw, Rad32::ZERO)?,
-            kicker1,
-            kicker2,
-            last_kick_time: None,
+            dribbler: Wheel::new(hal, pin_dribbler_pwm, pin_dribbler_cwccw, Rad32::ZERO)?,
+            kicker: Arc::new(Mutex::new(KickerPins {
+                kicker: Kicker::new(KICK_TIME, TIME_BETWEEN_KICK),
+                pin1,
+                pin2,
+            })),
+            timer: hal.timer(),
         })
     }
 
-    pub fn default() -> Result<Self, Box<dyn Error>> {
-        Self::new(DRIBBLER_PWM, DRIBBLER_CWCCW, KICKER_PIN1, KICKER_PIN2)
+    pub fn with_hal<H: Hal<Pin = P>>(hal: &mut H) -> Result<Self, Box<dyn Error>> {
+        Self::new(hal, DRIBBLER_PWM, DRIBBLER_CWCCW, KICKER_PIN1, KICKER_PIN2)
     }
 
     pub fn dribble(&mut self, speed: f32) {
         self.dribbler.rotate(speed);
     }
 
-    pub fn kick(&mut self) {
-        let now = Instant::now();
-        if let Some(last_kick_time) = self.last_kick_time {
-            if (last_kick_time - now) > TIME_BETWEEN_KICK {
-                self.last_kick_time = Some(now);
-                self.perform_kick();
+    /// Lance un kick sans attendre sa fin. Le minuteur coupe les pins `KICK_TIME` après `now`,
+    /// même si la boucle de contrôle est en retard pour appeler `tick`.
+    /// Renvoit `false` si un kick a déjà été lancé il y a moins de `TIME_BETWEEN_KICK`.
+    pub fn kick(&mut self, now: Instant) -> bool
+    where
+        P: 'static,
+    {
+        let mut kicker = lock(&self.kicker);
+        if !kicker.kicker.request(now) {
+            return false;
+        }
+        let shared = Arc::clone(&self.kicker);
+        let end = now + KICK_TIME;
+        let timer = self.timer.schedule(
+            end,
+            Box::new(move || {
+                // `tick` ne coupe rien si le kick a déjà été arrêté
+                if lock(&shared).tick(end) {
+                    debug!("pins du kicker coupées par le minuteur");
+                }
+            }),
+        );
+        if let Err(err) = timer {
+            // sans minuteur, rien ne garantit que les pins seront coupées à temps
+            error!("kick annulé, impossible de lancer le minuteur : {err}");
+            kicker.kicker.abort();
+            return false;
+        }
+        info!("kick");
+        kicker.set_high();
+        true
+    }
+
+    /// À appeler à chaque tour de la boucle de contrôle
+    pub fn tick(&mut self, now: Instant) {
+        lock(&self.kicker).tick(now);
+    }
+
+    /// Coupe immédiatement les pins du kicker
+    pub fn stop_kick(&mut self) {
+        let mut kicker = lock(&self.kicker);
+        kicker.kicker.abort();
+        kicker.set_low();
+    }
+}
+
+impl<P: Output> Drop for BallControl<P> {
+    /// Les pins du kicker ne doivent jamais rester alimentées
+    fn drop(&mut self) {
+        self.stop_kick();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hal::{NoopHal, PinCommand, RecordingHal};
+
+    /// vitesse envoyée à un moteur, retrouvée depuis les commandes de ses pins
+    fn wheel_speed(hal: &RecordingHal, pin_pwm: u8, pin_cwccw: u8) -> f32 {
+        let Some(PinCommand::Pwm { duty_cycle, .. }) = hal.last_command_of(pin_pwm) else {
+            panic!("pas de PWM sur la pin {pin_pwm}");
+        };
+        let speed = (1.0 - duty_cycle) as f32;
+        match hal.last_command_of(pin_cwccw) {
+            Some(PinCommand::High) => speed,
+            Some(PinCommand::Low) => -speed,
+            other => panic!("direction inconnue {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_go_to_drives_every_wheel() {
+        let mut hal = RecordingHal::new();
+        let mut bogie = Bogie::with_hal(&mut hal).unwrap();
+        bogie.go_to(Vector2::new(Meters(0.0), Meters(1.0)), 0.8, Rad32::ZERO);
+
+        let speeds = [
+            wheel_speed(&hal, FR_PWM, FR_CWCCW),
+            wheel_speed(&hal, FL_PWM, FL_CWCCW),
+            wheel_speed(&hal, BR_PWM, BR_CWCCW),
+            wheel_speed(&hal, BL_PWM, BL_CWCCW),
+        ];
+        // tout droit : la plus rapide des roues va à la vitesse demandée
+        let fastest = speeds
+            .iter()
+            .fold(0.0f32, |max, speed| max.max(speed.abs()));
+        assert!((fastest - 0.8).abs() < 1e-5, "{speeds:?}");
+
+        bogie.full_stop();
+        for (pwm, cwccw) in [
+            (FR_PWM, FR_CWCCW),
+            (FL_PWM, FL_CWCCW),
+            (BR_PWM, BR_CWCCW),
+            (BL_PWM, BL_CWCCW),
+        ] {
+            assert_eq!(wheel_speed(&hal, pwm, cwccw), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_set_velocity_and_odometry() {
+        let mut hal = RecordingHal::new();
+        let mut bogie = Bogie::with_hal(&mut hal).unwrap();
+        let (vx, vy, omega) = (
+            MetersPerSecond(0.3),
+            MetersPerSecond(-0.2),
+            RadPerSecond(1.5),
+        );
+        bogie.set_velocity(vx, vy, omega);
+        let estimated = bogie.estimated_velocity();
+        // les commandes passent par des f32 : on ne retrouve la vitesse qu'à peu près
+        assert!(
+            (estimated.vx - vx).abs() < MetersPerSecond(1e-4),
+            "{estimated:?}"
+        );
+        assert!(
+            (estimated.vy - vy).abs() < MetersPerSecond(1e-4),
+            "{estimated:?}"
+        );
+        assert!(
+            (estimated.omega - omega).abs() < RadPerSecond(1e-4),
+            "{estimated:?}"
+        );
+
+        // trop rapide : les roues saturent mais le robot garde sa direction
+        bogie.set_velocity(vx * 100.0, vy * 100.0, RadPerSecond::ZERO);
+        let estimated = bogie.estimated_velocity();
+        assert!((estimated.vy / estimated.vx - vy / vx).abs() < 1e-4);
+        assert!(estimated.omega.abs() < RadPerSecond(1e-4));
+        let fastest = [
+            wheel_speed(&hal, FR_PWM, FR_CWCCW),
+            wheel_speed(&hal, FL_PWM, FL_CWCCW),
+            wheel_speed(&hal, BR_PWM, BR_CWCCW),
+            wheel_speed(&hal, BL_PWM, BL_CWCCW),
+        ]
+        .iter()
+        .fold(0.0f32, |max, speed| max.max(speed.abs()));
+        assert!((fastest - 1.0).abs() < 1e-5);
+
+        bogie.full_stop();
+        assert_eq!(bogie.estimated_velocity(), Velocity::ZERO);
+    }
+
+    #[test]
+    fn test_tick_ramps_wheel_commands() {
+        let mut hal = RecordingHal::new();
+        let mut bogie = Bogie::with_hal(&mut hal).unwrap();
+        let target = Velocity::new(
+            MetersPerSecond::ZERO,
+            MetersPerSecond(0.5),
+            RadPerSecond::ZERO,
+        );
+        bogie.set_target_velocity(target);
+        let first = bogie.tick();
+        assert!(MetersPerSecond::ZERO < first.vy && first.vy < MetersPerSecond(0.01));
+        let first_speed = wheel_speed(&hal, FR_PWM, FR_CWCCW).abs();
+        assert!(0.0 < first_speed && first_speed < 0.01, "{first_speed}");
+
+        let mut ticks = 1;
+        while bogie.tick() != target {
+            ticks += 1;
+            assert!(ticks < 1000);
+        }
+        assert!((bogie.estimated_velocity().vy - target.vy).abs() < MetersPerSecond(1e-4));
+
+        bogie.full_stop();
+        assert_eq!(bogie.tick(), Velocity::ZERO);
+    }
+
+    #[test]
+    fn test_steer_turns_toward_target_while_translating() {
+        let mut bogie = Bogie::with_hal(&mut NoopHal).unwrap();
+        let target = Rad::new(-1.0);
+        let mut heading = Rad::ZERO;
+        for _ in 0..1000 {
+            bogie.steer(MetersPerSecond::ZERO, MetersPerSecond(0.3), heading, target);
+            let velocity = bogie.tick();
+            heading += velocity.omega * CONTROL_PERIOD;
+        }
+        assert!((heading - target).val().abs() < 2e-2, "{heading:?}");
+        assert!(
+            (bogie.estimated_velocity().vy - MetersPerSecond(0.3)).abs() < MetersPerSecond(1e-3)
+        );
+    }
+
+    #[test]
+    fn test_go_to_pose_arrives() {
+        use robot_common::frames::GlobalPoint;
+
+        let mut bogie = Bogie::with_hal(&mut NoopHal).unwrap();
+        let mut pose = Pose2::new(GlobalPoint::cm(-30.0, 20.0), Rad::new(0.5));
+        let target = Pose2::new(GlobalPoint::cm(40.0, -10.0), Rad::new(-1.0));
+        let mut arrived = false;
+        for _ in 0..1000 {
+            if bogie.go_to_pose(pose, target, None) {
+                arrived = true;
+                break;
             }
-        } else {
-            self.last_kick_time = Some(now);
-            self.perform_kick();
+            // odométrie parfaite : le robot suit les commandes des roues
+            let velocity = bogie.tick();
+            let local_move =
+                Vector2::new(velocity.vx * CONTROL_PERIOD, velocity.vy * CONTROL_PERIOD);
+            pose.position = pose.position + local_move.to_global(pose);
+            pose.heading += velocity.omega * CONTROL_PERIOD;
+        }
+        assert!(arrived, "{pose:?}");
+        assert!(bogie.position.arrived());
+    }
+
+    #[test]
+    fn test_kick_does_not_block() {
+        let mut hal = RecordingHal::new();
+        let mut ball_control = BallControl::with_hal(&mut hal).unwrap();
+        hal.clear();
+        // horloge factice
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let kicker1 = |hal: &RecordingHal| {
+            hal.commands_of(KICKER_PIN1)
+                .iter()
+                .map(|command| command.command)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(ball_control.kick(at(0)));
+        assert_eq!(kicker1(&hal), [PinCommand::High]);
+        ball_control.tick(at(250));
+        assert_eq!(kicker1(&hal), [PinCommand::High]);
+        ball_control.tick(at(500));
+        assert_eq!(kicker1(&hal), [PinCommand::High, PinCommand::Low]);
+        assert_eq!(hal.last_command_of(KICKER_PIN2), Some(PinCommand::Low));
+        // le minuteur arrive après `tick` et ne coupe rien de plus
+        assert_eq!(hal.fire_timers(at(500)), 1);
+        assert_eq!(kicker1(&hal).len(), 2);
+
+        // recharge : les kicks sont ignorés jusqu'à TIME_BETWEEN_KICK après le précédent
+        ball_control.tick(at(1000));
+        assert!(!ball_control.kick(at(1000)));
+        assert_eq!(kicker1(&hal).len(), 2);
+        assert!(ball_control.kick(at(2000)));
+        assert_eq!(hal.last_command_of(KICKER_PIN1), Some(PinCommand::High));
+
+        // les pins sont coupées quand `BallControl` est détruit, même en plein kick
+        drop(ball_control);
+        assert_eq!(hal.last_command_of(KICKER_PIN1), Some(PinCommand::Low));
+        assert_eq!(hal.last_command_of(KICKER_PIN2), Some(PinCommand::Low));
+    }
+
+    #[test]
+    fn test_kick_is_cut_on_time_without_tick() {
+        let mut hal = RecordingHal::new();
+        let mut ball_control = BallControl::with_hal(&mut hal).unwrap();
+        hal.clear();
+        let start = Instant::now();
+        let kicker = |hal: &RecordingHal, pin| {
+            hal.commands_of(pin)
+                .iter()
+                .map(|command| command.command)
+                .collect::<Vec<_>>()
+        };
+
+        // la boucle de contrôle est bloquée : `tick` n'est jamais appelé
+        assert!(ball_control.kick(start));
+        assert_eq!(
+            hal.fire_timers(start + KICK_TIME - Duration::from_millis(1)),
+            0
+        );
+        assert_eq!(kicker(&hal, KICKER_PIN1), [PinCommand::High]);
+        assert_eq!(hal.fire_timers(start + KICK_TIME), 1);
+        for pin in [KICKER_PIN1, KICKER_PIN2] {
+            assert_eq!(kicker(&hal, pin), [PinCommand::High, PinCommand::Low]);
         }
+
+        // le `tick` en retard ne touche plus aux pins
+        ball_control.tick(start + Duration::from_secs(1));
+        assert_eq!(hal.commands_of(KICKER_PIN1).len(), 2);
     }
 
-    pub fn perform_kick(&mut self) {
-        self.kicker1.set_high();
-        self.kicker2.set_high();
-        sleep(KICK_TIME); // TODO : C'est pas bien d'arrêter tout le code pour le kick
-        self.kicker1.set_low();
-        self.kicker2.set_low();
+    #[test]
+    fn test_noop_hal() {
+        let mut bogie = Bogie::with_hal(&mut NoopHal).unwrap();
+        bogie.go_to(Vector2::new(Meters(1.0), Meters(0.0)), 1.0, Rad32::ZERO);
+        let mut ball_control = BallControl::with_hal(&mut NoopHal).unwrap();
+        ball_control.dribble(0.5);
     }
 }
@@ -0,0 +1,246 @@
+//! Asservissement de la position du robot sur le terrain.
+//!
+//! Les stratégies donnent une position à atteindre (`Action::move_to`, dans le repère du robot :
+//! `move_to.to_global(pose)` la place sur le terrain) et le lidar donne la pose actuelle.
+//! `PositionController` en déduit la vitesse de translation dans le repère du robot :
+//! proportionnelle à la distance restante, bornée, et réduite près des murs.
+//! L'orientation est asservie à part par `heading::HeadingController` (voir `Bogie::go_to_pose`).
+
+use crate::heading::wrap_angle;
+use crate::kinematics::Velocity;
+use robot_common::frames::{Global, Pose2};
+use robot_common::units::*;
+use tracing::{debug, info};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionConfig {
+    pub max_speed: MetersPerSecond,
+    /// vitesse (m/s) par m restant, règle la douceur de l'arrivée
+    pub gain: f64,
+    /// distance à la cible en dessous de laquelle on considère être arrivé
+    pub position_tolerance: Meters,
+    /// écart d'orientation en dessous duquel on considère être arrivé, en radians
+    pub heading_tolerance: f64,
+    /// en dessous de cette distance à un mur, la vitesse maximale diminue...
+    pub wall_slowdown_distance: Meters,
+    /// ... jusqu'à cette vitesse contre le mur
+    pub wall_speed: MetersPerSecond,
+}
+
+impl PositionConfig {
+    /// Valeurs de départ, à régler sur le terrain
+    pub const DEFAULT: PositionConfig = PositionConfig {
+        max_speed: MetersPerSecond(1.0),
+        gain: 3.0,
+        position_tolerance: Meters::cm(2.0),
+        heading_tolerance: 0.05,
+        wall_slowdown_distance: Meters::cm(25.0),
+        wall_speed: MetersPerSecond(0.2),
+    };
+}
+
+impl Default for PositionConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Résultat d'un pas d'asservissement
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionCommand {
+    /// vitesse de translation dans le repère du robot (`omega` est toujours nul)
+    pub velocity: Velocity,
+    /// distance restante jusqu'à la cible
+    pub distance: Meters,
+    pub arrived: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct PositionController {
+    pub config: PositionConfig,
+    arrived: bool,
+}
+
+impl PositionController {
+    pub fn new(config: PositionConfig) -> Self {
+        Self {
+            config,
+            arrived: false,
+        }
+    }
+
+    /// Est-ce que la dernière cible a été atteinte (position et orientation)
+    #[inline]
+    pub fn arrived(&self) -> bool {
+        self.arrived
+    }
+
+    /// Vitesse maximale autorisée à `nearest_wall` du mur le plus proche
+    pub fn speed_limit(&self, nearest_wall: Option<Meters>) -> MetersPerSecond {
+        let config = &self.config;
+        match nearest_wall {
+            Some(distance) if distance < config.wall_slowdown_distance => {
+                let ratio = (distance / config.wall_slowdown_distance).max(0.0);
+                config.wall_speed + (config.max_speed - config.wall_speed) * ratio
+            }
+            _ => config.max_speed,
+        }
+    }
+
+    /// Vitesse de translation pour aller de `pose` à `target`.
+    /// `nearest_wall` est la distance au mur le plus proche, si elle est connue.
+    pub fn update(
+        &mut self,
+        pose: Pose2<Global>,
+        target: Pose2<Global>,
+        nearest_wall: Option<Meters>,
+    ) -> PositionCommand {
+        let to_target = target.position - pose.position;
+        let distance = to_target.norm();
+        let heading_error = wrap_angle(target.heading - pose.heading).val().abs();
+        let arrived = distance <= self.config.position_tolerance
+            && heading_error <= self.config.heading_tolerance;
+        if arrived && !self.arrived {
+            info!("arrivé en {:?}", target.position);
+        }
+        self.arrived = arrived;
+
+        if distance <= self.config.position_tolerance {
+            return PositionCommand {
+                velocity: Velocity::ZERO,
+                distance,
+                arrived,
+            };
+        }
+        let speed = self
+            .speed_limit(nearest_wall)
+            .min(MetersPerSecond(self.config.gain * distance.0));
+        let direction = to_target.to_local(pose) * (1.0 / distance.0);
+        debug!(
+            distance = distance.0,
+            speed = speed.0,
+            "asservissement de la position"
+        );
+        PositionCommand {
+            velocity: Velocity::new(
+                MetersPerSecond(speed.0 * direction.x.0),
+                MetersPerSecond(speed.0 * direction.y.0),
+                RadPerSecond::ZERO,
+            ),
+            distance,
+            arrived,
+        }
+    }
+}
+
+impl Default for PositionController {
+    fn default() -> Self {
+        Self::new(PositionConfig::DEFAULT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use robot_common::frames::{GlobalPoint, LocalPoint, Vector2};
+    use std::time::Duration;
+
+    const DT: Duration = Duration::from_millis(10);
+
+    /// robot qui se déplace exactement à la vitesse demandée, sans tourner
+    fn simulate(
+        controller: &mut PositionController,
+        mut pose: Pose2<Global>,
+        target: Pose2<Global>,
+        steps: usize,
+    ) -> (Pose2<Global>, Vec<PositionCommand>) {
+        let mut commands = Vec::new();
+        for _ in 0..steps {
+            let command = controller.update(pose, target, None);
+            commands.push(command);
+            if command.arrived {
+                break;
+            }
+            let local_move = Vector2::new(command.velocity.vx * DT, command.velocity.vy * DT);
+            pose.position = pose.position + local_move.to_global(pose);
+        }
+        (pose, commands)
+    }
+
+    fn speed(command: &PositionCommand) -> MetersPerSecond {
+        MetersPerSecond(command.velocity.vx.0.hypot(command.velocity.vy.0))
+    }
+
+    #[test]
+    fn test_reaches_target_with_limited_speed() {
+        let mut controller = PositionController::default();
+        let heading = Rad::new(2.0);
+        let start = Pose2::new(GlobalPoint::cm(-50.0, -60.0), heading);
+        let target = Pose2::new(GlobalPoint::cm(40.0, 70.0), heading);
+        let (pose, commands) = simulate(&mut controller, start, target, 1000);
+
+        assert!(controller.arrived());
+        assert!(
+            pose.position.distance_to(target.position)
+                <= PositionConfig::DEFAULT.position_tolerance
+        );
+        assert!(commands
+            .iter()
+            .all(|command| speed(command)
+                <= PositionConfig::DEFAULT.max_speed + MetersPerSecond(1e-9)));
+        // plein régime loin de la cible, ralentissement à l'approche
+        assert!(
+            (speed(&commands[0]) - PositionConfig::DEFAULT.max_speed).abs() < MetersPerSecond(1e-9)
+        );
+        let last_moves = &commands[commands.len() - 5..commands.len() - 1];
+        assert!(last_moves
+            .windows(2)
+            .all(|pair| speed(&pair[1]) <= speed(&pair[0])));
+    }
+
+    #[test]
+    fn test_direction_is_in_robot_frame() {
+        let mut controller = PositionController::default();
+        // robot tourné vers la gauche (vers -x du terrain), cible devant lui sur le terrain
+        let pose = Pose2::new(GlobalPoint::ORIGIN, Rad::QUARTER_TURN);
+        let target_on_field = LocalPoint::cm(0.0, 50.0).to_global(pose);
+        let command = controller.update(pose, Pose2::new(target_on_field, Rad::QUARTER_TURN), None);
+        assert!(command.velocity.vx.abs() < MetersPerSecond(1e-9));
+        assert!(command.velocity.vy > MetersPerSecond::ZERO);
+        assert!(!command.arrived);
+    }
+
+    #[test]
+    fn test_arrival_needs_heading() {
+        let mut controller = PositionController::default();
+        let pose = Pose2::new(GlobalPoint::cm(10.0, 10.0), Rad::ZERO);
+        let command = controller.update(pose, Pose2::new(pose.position, Rad::new(1.0)), None);
+        assert_eq!(command.velocity, Velocity::ZERO);
+        assert!(!command.arrived);
+        // l'écart d'orientation passe par -pi / pi
+        let pose = Pose2::new(pose.position, Rad::new(3.13));
+        let command = controller.update(pose, Pose2::new(pose.position, Rad::new(-3.13)), None);
+        assert!(command.arrived);
+    }
+
+    #[test]
+    fn test_slows_down_near_walls() {
+        let controller = PositionController::default();
+        let config = PositionConfig::DEFAULT;
+        assert_eq!(controller.speed_limit(None), config.max_speed);
+        assert_eq!(controller.speed_limit(Some(Meters(1.0))), config.max_speed);
+        assert_eq!(
+            controller.speed_limit(Some(Meters::ZERO)),
+            config.wall_speed
+        );
+        let halfway = controller.speed_limit(Some(config.wall_slowdown_distance / 2.0));
+        assert!(config.wall_speed < halfway && halfway < config.max_speed);
+
+        let mut controller = PositionController::default();
+        let pose = Pose2::new(GlobalPoint::ORIGIN, Rad::ZERO);
+        let target = Pose2::new(GlobalPoint::cm(0.0, 80.0), Rad::ZERO);
+        let near_wall = controller.update(pose, target, Some(Meters::cm(5.0)));
+        let far = controller.update(pose, target, None);
+        assert!(speed(&near_wall) < speed(&far));
+    }
+}
@@ -1,4 +0,0 @@
-//! Code permettant l'utilisation de Vector2 (un moyen de stocker des duos de donnés)
-
-/// Définition du type Vector2 comme étant le Vector2 de nalgebra pour des f32
-pub type Vector2 = nalgebra::Vector2<f32>;
\ No newline at end of file
@@ -0,0 +1,433 @@
+//! Positions et orientations étiquetées par leur repère.
+//!
+//! Le repère fait partie du type (`Point2<Local>`, `Point2<Global>`) : une position relative
+//! au robot ne peut pas être utilisée comme une position sur le terrain sans conversion explicite.
+//!
+//! | repère | origine | axe y | axe x |
+//! | --- | --- | --- | --- |
+//! | `Local` | centre du robot | vers l'avant du robot (le kicker) | à droite du robot |
+//! | `Global` | centre du terrain | vers le goal adverse | à droite en regardant le goal adverse |
+//!
+//! Les angles sont mesurés dans le sens trigonométrique, comme dans le reste du code.
+//!
+//! Le passage d'un repère à l'autre se fait avec la pose du robot (`Pose2<Global>`) :
+//! `to_global` et `to_local` appliquent la rotation *et* la translation.
+//! Le lidar, lui, donne ses points en polaire avec l'angle 0 vers le kicker :
+//! `LocalPoint::from_lidar` les ramène dans le repère `Local`.
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::units::*;
+
+/// Repère dans lequel sont exprimées des coordonnées, voir le tableau en tête du module
+pub trait Frame: Debug + Clone + Copy + PartialEq + Default {
+    const NAME: &'static str;
+}
+
+/// Repère du robot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Local;
+
+impl Frame for Local {
+    const NAME: &'static str = "local";
+}
+
+/// Repère du terrain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Global;
+
+impl Frame for Global {
+    const NAME: &'static str = "global";
+}
+
+/// Déplacement dans le repère `F`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct Vector2<F: Frame> {
+    pub x: Meters,
+    pub y: Meters,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    frame: PhantomData<F>,
+}
+
+impl<F: Frame> Vector2<F> {
+    pub const ZERO: Self = Self::new(Meters(0.0), Meters(0.0));
+
+    #[inline]
+    pub const fn new(x: Meters, y: Meters) -> Self {
+        Self {
+            x,
+            y,
+            frame: PhantomData,
+        }
+    }
+
+    /// Coordonnées *en cm*, comme dans les anciennes constantes des stratégies
+    #[inline]
+    pub const fn cm(x: f64, y: f64) -> Self {
+        Self::new(Meters::cm(x), Meters::cm(y))
+    }
+
+    /// Vecteur de longueur `norm` faisant l'angle `angle` avec l'axe x
+    #[inline]
+    pub fn from_polar(norm: Meters, angle: Rad) -> Self {
+        Self::new(norm * angle.cos(), norm * angle.sin())
+    }
+
+    #[inline]
+    pub fn norm(&self) -> Meters {
+        Meters(self.x.0.hypot(self.y.0))
+    }
+
+    /// Angle avec l'axe x, dans ]-pi, pi]
+    #[inline]
+    pub fn angle(&self) -> Rad {
+        Rad::atan2(self.y.0, self.x.0)
+    }
+
+    /// Produit scalaire, en m²
+    #[inline]
+    pub fn dot(&self, other: Self) -> f64 {
+        self.x.0 * other.x.0 + self.y.0 * other.y.0
+    }
+
+    /// Vecteur tourné de `angle`
+    #[inline]
+    pub fn rotate(&self, angle: Rad) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    /// Mêmes coordonnées dans un autre repère, à n'utiliser qu'après avoir fait la conversion
+    #[inline]
+    const fn with_frame<G: Frame>(self) -> Vector2<G> {
+        Vector2::new(self.x, self.y)
+    }
+}
+
+impl Vector2<Local> {
+    /// Direction sur le terrain d'un déplacement relatif au robot (rotation seule)
+    #[inline]
+    pub fn to_global(self, pose: Pose2<Global>) -> Vector2<Global> {
+        self.rotate(pose.heading).with_frame()
+    }
+}
+
+impl Vector2<Global> {
+    /// Direction relative au robot d'un déplacement sur le terrain (rotation seule)
+    #[inline]
+    pub fn to_local(self, pose: Pose2<Global>) -> Vector2<Local> {
+        self.rotate(-pose.heading).with_frame()
+    }
+}
+
+impl<F: Frame> Add for Vector2<F> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<F: Frame> Sub for Vector2<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<F: Frame> Neg for Vector2<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<F: Frame> Mul<f64> for Vector2<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+/// Position dans le repère `F`
+///
+/// Le repère n'est pas sérialisé : `{"x": .., "y": ..}` se relit dans n'importe quel repère,
+/// c'est au type qui contient le point de le fixer.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct Point2<F: Frame> {
+    pub x: Meters,
+    pub y: Meters,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    frame: PhantomData<F>,
+}
+
+pub type LocalPoint = Point2<Local>;
+pub type GlobalPoint = Point2<Global>;
+
+impl<F: Frame> Point2<F> {
+    /// Origine du repère : le centre du robot en `Local`, le centre du terrain en `Global`
+    pub const ORIGIN: Self = Self::new(Meters(0.0), Meters(0.0));
+
+    #[inline]
+    pub const fn new(x: Meters, y: Meters) -> Self {
+        Self {
+            x,
+            y,
+            frame: PhantomData,
+        }
+    }
+
+    /// Coordonnées *en cm*, comme dans les anciennes constantes des stratégies
+    #[inline]
+    pub const fn cm(x: f64, y: f64) -> Self {
+        Self::new(Meters::cm(x), Meters::cm(y))
+    }
+
+    /// Déplacement depuis l'origine du repère
+    #[inline]
+    pub fn to_vector(self) -> Vector2<F> {
+        Vector2::new(self.x, self.y)
+    }
+
+    #[inline]
+    pub fn distance_to(&self, other: Self) -> Meters {
+        (other - *self).norm()
+    }
+
+    /// Coordonnées `(x, y)` *en cm*
+    #[inline]
+    pub fn to_cm(&self) -> (f64, f64) {
+        (self.x.0 * 100.0, self.y.0 * 100.0)
+    }
+}
+
+impl Point2<Local> {
+    /// Position sur le terrain d'un point vu par le robot placé en `pose`
+    #[inline]
+    pub fn to_global(self, pose: Pose2<Global>) -> Point2<Global> {
+        pose.position + self.to_vector().to_global(pose)
+    }
+
+    /// Point donné par le lidar : `angle` est mesuré depuis la direction du kicker
+    /// (l'axe y du repère `Local`), dans le sens trigonométrique
+    #[inline]
+    pub fn from_lidar(distance: Meters, angle: Rad) -> Self {
+        Self::ORIGIN + Vector2::from_polar(distance, angle + Rad::QUARTER_TURN)
+    }
+
+    /// Inverse de `from_lidar` : distance et angle depuis le kicker, dans [0, 2pi[
+    pub fn to_lidar(&self) -> (Meters, Rad) {
+        let vector = self.to_vector();
+        let angle = vector.angle() - Rad::QUARTER_TURN;
+        if angle < Rad::ZERO {
+            (vector.norm(), angle + Rad::FULL_TURN)
+        } else {
+            (vector.norm(), angle)
+        }
+    }
+}
+
+impl Point2<Global> {
+    /// Position relative au robot placé en `pose` d'un point du terrain
+    #[inline]
+    pub fn to_local(self, pose: Pose2<Global>) -> Point2<Local> {
+        Point2::ORIGIN + (self - pose.position).to_local(pose)
+    }
+}
+
+impl<F: Frame> Sub for Point2<F> {
+    type Output = Vector2<F>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<F: Frame> Add<Vector2<F>> for Point2<F> {
+    type Output = Self;
+
+    fn add(self, rhs: Vector2<F>) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<F: Frame> Sub<Vector2<F>> for Point2<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: Vector2<F>) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+/// Position et orientation du robot dans le repère `F`.
+///
+/// `heading` est l'angle dont il faut tourner les axes de `F` pour obtenir ceux du robot :
+/// 0 quand l'avant du robot suit l'axe y de `F`, positif quand le robot a tourné vers la gauche.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct Pose2<F: Frame> {
+    pub position: Point2<F>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::units::serde_rad"))]
+    pub heading: Rad,
+}
+
+impl<F: Frame> Pose2<F> {
+    #[inline]
+    pub const fn new(position: Point2<F>, heading: Rad) -> Self {
+        Self { position, heading }
+    }
+}
+
+impl Pose2<Global> {
+    /// Orientation sur le terrain d'une direction donnée par rapport au robot
+    #[inline]
+    pub fn heading_to_global(&self, local_heading: Rad) -> Rad {
+        local_heading + self.heading
+    }
+
+    /// Orientation par rapport au robot d'une direction donnée sur le terrain
+    #[inline]
+    pub fn heading_to_local(&self, global_heading: Rad) -> Rad {
+        global_heading - self.heading
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const APPROX_LIMIT: f64 = 1e-9;
+
+    #[test]
+    fn test_points_and_vectors() {
+        let robot = GlobalPoint::cm(30.0, -40.0);
+        let ball = GlobalPoint::new(Meters(0.6), Meters(0.0));
+        let to_ball = ball - robot;
+        assert!((to_ball.norm() - Meters(0.5)).abs() < Meters(APPROX_LIMIT));
+        assert!((robot.distance_to(ball) - Meters(0.5)).abs() < Meters(APPROX_LIMIT));
+        assert_eq!(robot + to_ball, ball);
+        assert_eq!(ball - to_ball, robot);
+        assert_eq!(GlobalPoint::ORIGIN + robot.to_vector(), robot);
+        assert_eq!(to_ball * 2.0 - to_ball, to_ball);
+        assert_eq!(-to_ball + to_ball, Vector2::ZERO);
+        assert!((to_ball.dot(to_ball) - 0.25).abs() < APPROX_LIMIT);
+    }
+
+    #[test]
+    fn test_polar_and_rotation() {
+        let forward = Vector2::<Local>::from_polar(Meters(2.0), Rad::QUARTER_TURN);
+        assert!(forward.x.abs() < Meters(APPROX_LIMIT));
+        assert!((forward.y - Meters(2.0)).abs() < Meters(APPROX_LIMIT));
+        assert!((forward.angle() - Rad::QUARTER_TURN).val().abs() < APPROX_LIMIT);
+
+        // vers la gauche du robot
+        let left = forward.rotate(Rad::QUARTER_TURN);
+        assert!((left.x + Meters(2.0)).abs() < Meters(APPROX_LIMIT));
+        assert!(left.y.abs() < Meters(APPROX_LIMIT));
+        assert!((left.norm() - forward.norm()).abs() < Meters(APPROX_LIMIT));
+    }
+
+    /// différence entre deux angles, ramenée dans ]-pi, pi]
+    fn angle_gap(a: Rad, b: Rad) -> f64 {
+        let gap = a - b;
+        gap.sin().atan2(gap.cos()).abs()
+    }
+
+    #[test]
+    fn test_to_global() {
+        // robot en (1, 0.5) tourné d'un quart de tour vers la gauche : son avant regarde vers -x
+        let pose = Pose2::new(
+            GlobalPoint::new(Meters(1.0), Meters(0.5)),
+            Rad::QUARTER_TURN,
+        );
+        let in_front = LocalPoint::cm(0.0, 20.0).to_global(pose);
+        assert!(in_front.distance_to(GlobalPoint::cm(80.0, 50.0)) < Meters(APPROX_LIMIT));
+        let on_the_right = LocalPoint::cm(20.0, 0.0).to_global(pose);
+        assert!(on_the_right.distance_to(GlobalPoint::cm(100.0, 70.0)) < Meters(APPROX_LIMIT));
+        assert!(LocalPoint::ORIGIN.to_global(pose) == pose.position);
+        assert!(angle_gap(pose.heading_to_global(Rad::ZERO), Rad::QUARTER_TURN) < APPROX_LIMIT);
+    }
+
+    #[test]
+    fn test_round_trip_for_many_headings() {
+        let points = [
+            LocalPoint::ORIGIN,
+            LocalPoint::cm(5.0, 5.0),
+            LocalPoint::cm(-30.0, 12.5),
+            LocalPoint::new(Meters(1.2), Meters(-0.8)),
+        ];
+        for n_heading in -36..=36 {
+            let heading = Rad::new(f64::from(n_heading) * 0.1);
+            let pose = Pose2::new(GlobalPoint::cm(-40.0, 75.0), heading);
+            for local in points {
+                let global = local.to_global(pose);
+                assert!(
+                    global.to_local(pose).distance_to(local) < Meters(APPROX_LIMIT),
+                    "{:?} {:?}",
+                    heading,
+                    local
+                );
+                // la rotation conserve les distances au robot
+                assert!(
+                    (global.distance_to(pose.position) - local.to_vector().norm()).abs()
+                        < Meters(APPROX_LIMIT)
+                );
+                let direction = local.to_vector();
+                let back = direction.to_global(pose).to_local(pose);
+                assert!((back - direction).norm() < Meters(APPROX_LIMIT));
+            }
+            let local_heading = Rad::new(0.3);
+            assert!(
+                angle_gap(
+                    pose.heading_to_local(pose.heading_to_global(local_heading)),
+                    local_heading
+                ) < APPROX_LIMIT
+            );
+        }
+    }
+
+    #[test]
+    fn test_lidar_frame() {
+        // angle 0 du lidar : vers le kicker, donc l'axe y du repère local
+        let kicker = LocalPoint::from_lidar(Meters(0.5), Rad::ZERO);
+        assert!(kicker.distance_to(LocalPoint::cm(0.0, 50.0)) < Meters(APPROX_LIMIT));
+        // quart de tour dans le sens trigonométrique : à gauche du robot
+        let left = LocalPoint::from_lidar(Meters(0.5), Rad::QUARTER_TURN);
+        assert!(left.distance_to(LocalPoint::cm(-50.0, 0.0)) < Meters(APPROX_LIMIT));
+        let (x, y) = left.to_cm();
+        assert!((x + 50.0).abs() < APPROX_LIMIT && y.abs() < APPROX_LIMIT);
+
+        for n_angle in 0..72 {
+            let angle = Rad::new(f64::from(n_angle) * 0.0872);
+            let (distance, read_angle) = LocalPoint::from_lidar(Meters(2.0), angle).to_lidar();
+            assert!((distance - Meters(2.0)).abs() < Meters(APPROX_LIMIT));
+            assert!(angle_gap(read_angle, angle) < APPROX_LIMIT, "{:?}", angle);
+            assert!(Rad::ZERO <= read_angle && read_angle < Rad::FULL_TURN);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let pose = Pose2::new(GlobalPoint::cm(30.0, -40.0), Rad::new(0.5));
+        let json = serde_json::to_string(&pose).unwrap();
+        assert_eq!(json, r#"{"position":{"x":0.3,"y":-0.4},"heading":0.5}"#);
+        assert_eq!(serde_json::from_str::<Pose2<Global>>(&json).unwrap(), pose);
+        let vector = Vector2::<Local>::cm(1.0, 2.0);
+        let json = serde_json::to_string(&vector).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vector2<Local>>(&json).unwrap(),
+            vector
+        );
+    }
+}
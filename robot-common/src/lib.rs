@@ -0,0 +1,8 @@
+//! Types partagés par tous les programmes du robot : unités (`units`) et repères (`frames`).
+//!
+//! Les distances sont toujours en mètres (`Meters`, f64) et les angles en radians (`Rad`, f64).
+//! Les valeurs en cm ou en mm (capteurs, anciennes constantes) sont converties dès leur lecture
+//! avec `Meters::cm` et `Meters::mm`, pour ne jamais mélanger les unités.
+
+pub mod frames;
+pub mod units;
@@ -0,0 +1,655 @@
+//! Contient tous les types unités utilisés par l'ensemble du code.
+
+use std::error::Error;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use std::time::Duration;
+
+pub type Rad = radians::Rad64;
+pub type Deg = radians::Deg64;
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Meters(pub f64);
+
+impl Meters {
+    #[inline]
+    pub const fn cm(distance: f64) -> Self {
+        Self(distance / 100.0)
+    }
+
+    #[inline]
+    pub const fn mm(distance: f64) -> Self {
+        Self(distance / 1000.0)
+    }
+
+    /// Distance entière en cm, comme celles des capteurs et des anciennes stratégies
+    #[inline]
+    pub const fn from_cm(distance: i32) -> Self {
+        Self(distance as f64 / 100.0)
+    }
+
+    /// Distance entière en mm, comme celles renvoyées par le lidar
+    #[inline]
+    pub const fn from_mm(distance: i32) -> Self {
+        Self(distance as f64 / 1000.0)
+    }
+
+    /// Distance arrondie au cm le plus proche
+    #[inline]
+    pub fn as_cm(&self) -> i32 {
+        (self.0 * 100.0).round() as i32
+    }
+
+    /// Distance arrondie au mm le plus proche
+    #[inline]
+    pub fn as_mm(&self) -> i32 {
+        (self.0 * 1000.0).round() as i32
+    }
+
+    pub const fn const_div(self, rhs: Self) -> Self {
+        Self(self.0 / rhs.0)
+    }
+
+    /// Teste si cette distance est dans les environs de celle passée en paramètres
+    /// entre 0.9 et 1.1 fois
+    pub fn in_the_aera_of(&self, other: Meters) -> bool {
+        ((other.0 * 0.9)..=(other.0 * 1.1)).contains(&self.0)
+    }
+}
+
+impl Div for Meters {
+    type Output = f64;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.0 / rhs.0
+    }
+}
+
+impl Div<f64> for Meters {
+    type Output = Meters;
+
+    fn div(self, rhs: f64) -> Self::Output {
+        Meters(self.0 / rhs)
+    }
+}
+
+impl Neg for Meters {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl Add for Meters {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Meters {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl Mul<f64> for Meters {
+    type Output = Meters;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Self(self.0 * rhs)
+    }
+}
+
+impl Mul for Meters {
+    type Output = SquareMeters;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        SquareMeters(self.0 * rhs.0)
+    }
+}
+
+impl AddAssign for Meters {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign for Meters {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Sum for Meters {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self(iter.map(|distance| distance.0).sum())
+    }
+}
+
+impl<'a> Sum<&'a Meters> for Meters {
+    fn sum<I: Iterator<Item = &'a Meters>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl Meters {
+    pub const ZERO: Meters = Meters(0.0);
+
+    pub fn abs(&self) -> Self {
+        Self(self.0.abs())
+    }
+
+    #[inline]
+    pub fn min(self, other: Self) -> Self {
+        Self(self.0.min(other.0))
+    }
+
+    #[inline]
+    pub fn max(self, other: Self) -> Self {
+        Self(self.0.max(other.0))
+    }
+
+    #[inline]
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        Self(self.0.clamp(min.0, max.0))
+    }
+
+    /// Teste si les deux distances sont égales à `tolerance` près
+    #[inline]
+    pub fn approx_eq(&self, other: Self, tolerance: Self) -> bool {
+        (*self - other).abs() <= tolerance
+    }
+
+    #[inline]
+    pub fn squared(self) -> SquareMeters {
+        self * self
+    }
+
+    /// Hypoténuse du triangle rectangle de côtés `self` et `other`
+    #[inline]
+    pub fn hypot(self, other: Self) -> Self {
+        Self(self.0.hypot(other.0))
+    }
+}
+
+/// Affiche la distance en mètres, en respectant la précision demandée (`{:.2}` → `1.50 m`)
+impl fmt::Display for Meters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)?;
+        write!(f, " m")
+    }
+}
+
+/// Surface ou distance au carré, en m², pour comparer des distances sans racine carrée
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SquareMeters(pub f64);
+
+impl SquareMeters {
+    #[inline]
+    pub fn sqrt(self) -> Meters {
+        Meters(self.0.sqrt())
+    }
+}
+
+impl Add for SquareMeters {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for SquareMeters {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl Div<Meters> for SquareMeters {
+    type Output = Meters;
+
+    fn div(self, rhs: Meters) -> Self::Output {
+        Meters(self.0 / rhs.0)
+    }
+}
+
+impl fmt::Display for SquareMeters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)?;
+        write!(f, " m²")
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum IntensityError {
+    /// la valeur donnée n'est pas entre 0 et 1 (ou n'est pas un nombre)
+    OutOfRange(f64),
+}
+
+impl fmt::Display for IntensityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Error for IntensityError {}
+
+/// Représente une intensité entre 0 et 1 inclus.
+/// Sérialisée comme un nombre, vérifié à la désérialisation.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "f64", into = "f64"))]
+pub struct Intensity(f64);
+
+impl Intensity {
+    pub const NULL: Intensity = Intensity(0.0);
+    pub const FULL: Intensity = Intensity(1.0);
+
+    pub fn new(intensity: f64) -> Result<Intensity, IntensityError> {
+        if (0.0..=1.0).contains(&intensity) {
+            Ok(Self(intensity))
+        } else {
+            Err(IntensityError::OutOfRange(intensity))
+        }
+    }
+
+    pub fn from_u8(intensity: u8) -> Intensity {
+        Self(f64::from(intensity) / 255.0)
+    }
+
+    /// Valeur entre 0 et 1
+    #[inline]
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+
+    /// Valeur entre 0 et 255, comme celle renvoyée par le lidar
+    #[inline]
+    pub fn as_u8(&self) -> u8 {
+        (self.0 * 255.0).round() as u8
+    }
+}
+
+impl TryFrom<f64> for Intensity {
+    type Error = IntensityError;
+
+    fn try_from(intensity: f64) -> Result<Self, Self::Error> {
+        Self::new(intensity)
+    }
+}
+
+impl From<Intensity> for f64 {
+    fn from(intensity: Intensity) -> Self {
+        intensity.0
+    }
+}
+
+impl fmt::Display for Intensity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&(self.0 * 100.0), f)?;
+        write!(f, " %")
+    }
+}
+
+/// Vitesse linéaire, en m/s
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetersPerSecond(pub f64);
+
+impl MetersPerSecond {
+    pub const ZERO: MetersPerSecond = MetersPerSecond(0.0);
+
+    #[inline]
+    pub fn abs(&self) -> Self {
+        Self(self.0.abs())
+    }
+
+    #[inline]
+    pub fn min(self, other: Self) -> Self {
+        Self(self.0.min(other.0))
+    }
+
+    #[inline]
+    pub fn max(self, other: Self) -> Self {
+        Self(self.0.max(other.0))
+    }
+
+    #[inline]
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        Self(self.0.clamp(min.0, max.0))
+    }
+}
+
+/// Vitesse angulaire, en rad/s, positive dans le sens trigonométrique
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RadPerSecond(pub f64);
+
+impl RadPerSecond {
+    pub const ZERO: RadPerSecond = RadPerSecond(0.0);
+
+    /// Vitesse pour tourner de `angle` en `duration`
+    #[inline]
+    pub fn from_turn(angle: Rad, duration: Duration) -> Self {
+        Self(angle.val() / duration.as_secs_f64())
+    }
+
+    #[inline]
+    pub fn abs(&self) -> Self {
+        Self(self.0.abs())
+    }
+
+    #[inline]
+    pub fn min(self, other: Self) -> Self {
+        Self(self.0.min(other.0))
+    }
+
+    #[inline]
+    pub fn max(self, other: Self) -> Self {
+        Self(self.0.max(other.0))
+    }
+
+    #[inline]
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        Self(self.0.clamp(min.0, max.0))
+    }
+}
+
+/// Opérations communes aux vitesses, qui se comportent comme des `f64` avec une unité
+macro_rules! speed_ops {
+    ($speed:ident, $unit:literal) => {
+        impl Add for $speed {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $speed {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl Neg for $speed {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<f64> for $speed {
+            type Output = Self;
+
+            fn mul(self, rhs: f64) -> Self::Output {
+                Self(self.0 * rhs)
+            }
+        }
+
+        impl Div<f64> for $speed {
+            type Output = Self;
+
+            fn div(self, rhs: f64) -> Self::Output {
+                Self(self.0 / rhs)
+            }
+        }
+
+        impl Div for $speed {
+            type Output = f64;
+
+            fn div(self, rhs: Self) -> Self::Output {
+                self.0 / rhs.0
+            }
+        }
+
+        impl AddAssign for $speed {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl SubAssign for $speed {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl fmt::Display for $speed {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)?;
+                write!(f, $unit)
+            }
+        }
+    };
+}
+
+speed_ops!(MetersPerSecond, " m/s");
+speed_ops!(RadPerSecond, " rad/s");
+
+/// Vitesse au bord d'un cercle de rayon `rhs` qui tourne à cette vitesse angulaire
+impl Mul<Meters> for RadPerSecond {
+    type Output = MetersPerSecond;
+
+    fn mul(self, rhs: Meters) -> Self::Output {
+        MetersPerSecond(self.0 * rhs.0)
+    }
+}
+
+/// Vitesse angulaire d'un point qui parcourt un cercle de rayon `rhs` à cette vitesse
+impl Div<Meters> for MetersPerSecond {
+    type Output = RadPerSecond;
+
+    fn div(self, rhs: Meters) -> Self::Output {
+        RadPerSecond(self.0 / rhs.0)
+    }
+}
+
+impl Div<Duration> for Meters {
+    type Output = MetersPerSecond;
+
+    fn div(self, rhs: Duration) -> Self::Output {
+        MetersPerSecond(self.0 / rhs.as_secs_f64())
+    }
+}
+
+impl Mul<Duration> for MetersPerSecond {
+    type Output = Meters;
+
+    fn mul(self, rhs: Duration) -> Self::Output {
+        Meters(self.0 * rhs.as_secs_f64())
+    }
+}
+
+impl Mul<Duration> for RadPerSecond {
+    type Output = Rad;
+
+    fn mul(self, rhs: Duration) -> Self::Output {
+        Rad::new(self.0 * rhs.as_secs_f64())
+    }
+}
+
+/// Sérialisation des `Rad` comme un nombre de radians,
+/// à utiliser avec `#[serde(with = "robot_common::units::serde_rad")]`
+#[cfg(feature = "serde")]
+pub mod serde_rad {
+    use super::Rad;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(angle: &Rad, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(angle.val())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Rad, D::Error> {
+        f64::deserialize(deserializer).map(Rad::new)
+    }
+}
+
+/// Comme `serde_rad`, pour les `Option<Rad>` (`null` si l'angle est inconnu)
+#[cfg(feature = "serde")]
+pub mod serde_option_rad {
+    use super::Rad;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(angle: &Option<Rad>, serializer: S) -> Result<S::Ok, S::Error> {
+        angle.map(|angle| angle.val()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Rad>, D::Error> {
+        Ok(Option::<f64>::deserialize(deserializer)?.map(Rad::new))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_velocities() {
+        let speed = Meters(1.5) / Duration::from_millis(500);
+        assert_eq!(speed, MetersPerSecond(3.0));
+        assert_eq!(speed * Duration::from_secs(2), Meters(6.0));
+        let rotation = RadPerSecond::from_turn(Rad::HALF_TURN, Duration::from_secs(2));
+        assert!(
+            (rotation * Duration::from_secs(1) - Rad::QUARTER_TURN)
+                .val()
+                .abs()
+                < 1e-12
+        );
+        assert_eq!(Meters::cm(150.0), Meters::mm(1500.0));
+        assert_eq!(RadPerSecond(2.0) * Meters(0.5), MetersPerSecond(1.0));
+        assert_eq!(MetersPerSecond(1.0) / Meters(0.5), RadPerSecond(2.0));
+        assert_eq!(format!("{:.1}", -speed), "-3.0 m/s");
+    }
+
+    #[test]
+    fn test_meters_helpers() {
+        assert_eq!(Meters::from_mm(1234).as_cm(), 123);
+        assert_eq!(Meters::from_cm(-57).as_mm(), -570);
+        assert_eq!(Meters(0.3).hypot(Meters(0.4)), Meters(0.5));
+        assert_eq!(
+            (Meters(3.0).squared() + Meters(4.0).squared()).sqrt(),
+            Meters(5.0)
+        );
+        assert_eq!(Meters(2.0).clamp(Meters::ZERO, Meters(1.0)), Meters(1.0));
+        assert!(Meters(1.0).approx_eq(Meters(1.04), Meters::cm(5.0)));
+        assert!(!Meters(1.0).approx_eq(Meters(1.1), Meters::cm(5.0)));
+        assert_eq!(format!("{:.2}", Meters(1.5)), "1.50 m");
+        assert_eq!(format!("{}", Meters(4.0).squared()), "16 m²");
+    }
+
+    #[test]
+    fn test_intensity() {
+        assert_eq!(Intensity::new(1.5), Err(IntensityError::OutOfRange(1.5)));
+        assert!(Intensity::try_from(f64::NAN).is_err());
+        let half = Intensity::new(0.5).unwrap();
+        assert_eq!(half.value(), 0.5);
+        assert_eq!(Intensity::from_u8(200).as_u8(), 200);
+        assert_eq!(Intensity::FULL.as_u8(), 255);
+        assert_eq!(half.to_string(), "50 %");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let distance: Meters =
+            serde_json::from_str(&serde_json::to_string(&Meters(1.25)).unwrap()).unwrap();
+        assert_eq!(distance, Meters(1.25));
+        assert_eq!(serde_json::to_string(&Meters(1.25)).unwrap(), "1.25");
+        let intensity = Intensity::from_u8(100);
+        let json = serde_json::to_string(&intensity).unwrap();
+        assert_eq!(serde_json::from_str::<Intensity>(&json).unwrap(), intensity);
+        assert!(serde_json::from_str::<Intensity>("1.5").is_err());
+        let speed: RadPerSecond = serde_json::from_str("-0.5").unwrap();
+        assert_eq!(speed, RadPerSecond(-0.5));
+    }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// distances raisonnables pour le robot, assez petites pour ne pas perdre de précision
+        fn meters() -> impl Strategy<Value = Meters> {
+            (-100.0..100.0f64).prop_map(Meters)
+        }
+
+        const APPROX_LIMIT: Meters = Meters(1e-9);
+
+        proptest! {
+            #[test]
+            fn add_sub_are_inverse(a in meters(), b in meters()) {
+                prop_assert!((a + b - b).approx_eq(a, APPROX_LIMIT));
+                let mut c = a;
+                c += b;
+                c -= b;
+                prop_assert!(c.approx_eq(a, APPROX_LIMIT));
+                prop_assert_eq!(a + b, b + a);
+                prop_assert_eq!(-(-a), a);
+            }
+
+            #[test]
+            fn sum_matches_additions(distances in prop::collection::vec(meters(), 0..20)) {
+                let added = distances.iter().fold(Meters::ZERO, |total, &d| total + d);
+                prop_assert!(distances.iter().sum::<Meters>().approx_eq(added, APPROX_LIMIT));
+                prop_assert!(distances.into_iter().sum::<Meters>().approx_eq(added, APPROX_LIMIT));
+            }
+
+            #[test]
+            fn squared_and_hypot(a in meters(), b in meters()) {
+                prop_assert!(a.squared().sqrt().approx_eq(a.abs(), APPROX_LIMIT));
+                let hypot = a.hypot(b);
+                prop_assert!((a.squared() + b.squared()).sqrt().approx_eq(hypot, APPROX_LIMIT));
+                prop_assert!(hypot >= a.abs().max(b.abs()));
+                prop_assert!(hypot <= a.abs() + b.abs() + APPROX_LIMIT);
+            }
+
+            #[test]
+            fn integer_conversions_round_trip(mm in -100_000..100_000i32, cm in -10_000..10_000i32) {
+                prop_assert_eq!(Meters::from_mm(mm).as_mm(), mm);
+                prop_assert_eq!(Meters::from_cm(cm).as_cm(), cm);
+                prop_assert_eq!(Meters::from_cm(cm).as_mm(), cm * 10);
+            }
+
+            #[test]
+            fn comparisons(a in meters(), b in meters()) {
+                prop_assert!(a.min(b) <= a.max(b));
+                let clamped = a.clamp(Meters(-1.0), Meters(1.0));
+                prop_assert!(Meters(-1.0) <= clamped && clamped <= Meters(1.0));
+                prop_assert!(a.approx_eq(a, Meters::ZERO));
+            }
+
+            #[test]
+            fn speeds_integrate_back(distance in meters(), millis in 1..10_000u64) {
+                let duration = Duration::from_millis(millis);
+                prop_assert!(((distance / duration) * duration).approx_eq(distance, APPROX_LIMIT));
+                let radius = Meters(0.1);
+                let speed = (distance / duration) / radius * radius;
+                prop_assert!((speed - distance / duration).abs() < MetersPerSecond(1e-9));
+            }
+
+            #[test]
+            fn intensity_range(value in -2.0..2.0f64) {
+                let intensity = Intensity::new(value);
+                prop_assert_eq!(intensity.is_ok(), (0.0..=1.0).contains(&value));
+                if let Ok(intensity) = intensity {
+                    prop_assert_eq!(intensity.value(), value);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,360 @@
+//! Mise en place des logs commune à tous les programmes du robot.
+//!
+//! Les messages sont écrits dans le terminal et dans un fichier du dossier `log`.
+//! Le filtre est lu dans la variable d'environnement `RUST_LOG` (syntaxe de `EnvFilter`,
+//! par exemple `RUST_LOG=warn,motors=debug`), à défaut celui de `LogConfig::default_filter` est utilisé.
+//! Attention, dans le filtre les `-` des noms de crates sont remplacés par des `_`.
+//!
+//! Les fichiers sont découpés selon `LogConfig::rotation` et seuls les `LogConfig::max_files`
+//! plus récents de chaque programme sont gardés pour ne pas remplir la carte SD.
+
+use std::error::Error;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing::{info, subscriber};
+use tracing_panic::panic_hook;
+use tracing_subscriber::layer::{Identity, SubscriberExt};
+use tracing_subscriber::{fmt, EnvFilter, Layer, Registry};
+
+/// Découpage des fichiers de log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    /// un fichier `<programme>-<date>.log` par exécution
+    PerRun,
+    /// écrit dans `<programme>.log`, renommé en `<programme>-<date>.log` quand il dépasse la taille donnée *en octets*
+    Size(u64),
+}
+
+#[derive(Debug, Clone)]
+pub struct LogConfig {
+    /// nom du programme, utilisé pour nommer les fichiers
+    pub app_name: String,
+    /// filtre utilisé si `RUST_LOG` n'est pas définie
+    pub default_filter: String,
+    pub dir: PathBuf,
+    pub rotation: Rotation,
+    /// nombre de fichiers gardés pour ce programme, y compris celui en cours
+    pub max_files: usize,
+}
+
+impl LogConfig {
+    pub fn new(app_name: &str) -> Self {
+        Self {
+            app_name: app_name.to_owned(),
+            default_filter: format!("warn,{}=trace", app_name.replace('-', "_")),
+            dir: PathBuf::from("log"),
+            rotation: Rotation::PerRun,
+            max_files: 20,
+        }
+    }
+
+    /// `RUST_LOG` si elle est définie, sinon `default_filter`
+    pub fn filter(&self) -> Result<EnvFilter, Box<dyn Error>> {
+        let directives = match std::env::var(EnvFilter::DEFAULT_ENV) {
+            Ok(directives) => directives,
+            Err(std::env::VarError::NotPresent) => self.default_filter.clone(),
+            Err(err) => return Err(Box::new(err)),
+        };
+        Ok(EnvFilter::try_new(directives)?)
+    }
+}
+
+/// Met en place les logs dans le terminal et dans les fichiers
+#[inline]
+pub fn set_up_logging(config: &LogConfig) -> Result<(), Box<dyn Error>> {
+    set_up_logging_with_layer(config, Identity::new())
+}
+
+/// Comme `set_up_logging`, en envoyant aussi les messages à `layer` (par exemple rerun)
+pub fn set_up_logging_with_layer<L>(config: &LogConfig, layer: L) -> Result<(), Box<dyn Error>>
+where
+    L: Layer<Registry> + Send + Sync + 'static,
+{
+    let log_file = LogFile::open(config)?;
+    let log_path = log_file.path();
+    let subscriber_param = Registry::default()
+        .with(layer)
+        .with(fmt::layer().with_ansi(true))
+        .with(
+            fmt::layer()
+                .with_ansi(false)
+                .with_writer(move || log_file.clone()),
+        )
+        .with(config.filter()?);
+    subscriber::set_global_default(subscriber_param)?;
+
+    std::panic::set_hook(Box::new(panic_hook));
+    info!("NEW START, logs dans {}", log_path.display());
+    Ok(())
+}
+
+/// Fichier de log partagé entre les threads, qui change de fichier selon `Rotation`
+#[derive(Debug, Clone)]
+pub struct LogFile {
+    inner: Arc<Mutex<RotatingFile>>,
+}
+
+#[derive(Debug)]
+struct RotatingFile {
+    config: LogConfig,
+    path: PathBuf,
+    file: File,
+    written: u64,
+}
+
+impl LogFile {
+    pub fn open(config: &LogConfig) -> io::Result<Self> {
+        std::fs::create_dir_all(&config.dir)?;
+        let path = match config.rotation {
+            Rotation::PerRun => dated_path(config, SystemTime::now()),
+            Rotation::Size(_) => config.dir.join(format!("{}.log", config.app_name)),
+        };
+        let file = OpenOptions::new().append(true).create(true).open(&path)?;
+        let written = file.metadata()?.len();
+        remove_old_files(config)?;
+        Ok(Self {
+            inner: Arc::new(Mutex::new(RotatingFile {
+                config: config.clone(),
+                path,
+                file,
+                written,
+            })),
+        })
+    }
+
+    /// Fichier en cours d'écriture
+    pub fn path(&self) -> PathBuf {
+        self.lock().path.clone()
+    }
+
+    #[inline]
+    fn lock(&self) -> std::sync::MutexGuard<'_, RotatingFile> {
+        // un panic pendant une écriture ne doit pas empêcher les suivantes
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl RotatingFile {
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let archive = dated_path(&self.config, SystemTime::now());
+        std::fs::rename(&self.path, &archive)?;
+        self.file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&self.path)?;
+        self.written = 0;
+        remove_old_files(&self.config)
+    }
+}
+
+impl Write for LogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut rotating = self.lock();
+        if let Rotation::Size(max_bytes) = rotating.config.rotation {
+            if rotating.written > 0 && rotating.written + buf.len() as u64 > max_bytes {
+                rotating.rotate()?;
+            }
+        }
+        let written = rotating.file.write(buf)?;
+        rotating.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.lock().file.flush()
+    }
+}
+
+/// Fichier `<programme>-<date>.log` qui n'existe pas encore
+fn dated_path(config: &LogConfig, time: SystemTime) -> PathBuf {
+    let stem = format!("{}-{}", config.app_name, utc_timestamp(time));
+    let mut path = config.dir.join(format!("{}.log", stem));
+    let mut n_duplicate = 1;
+    while path.exists() {
+        // `_` se trie après `.` : les doublons restent dans l'ordre chronologique
+        path = config.dir.join(format!("{}_{:03}.log", stem, n_duplicate));
+        n_duplicate += 1;
+    }
+    path
+}
+
+/// Date UTC au format `AAAAMMJJ-HHMMSS`, qui se trie dans l'ordre chronologique
+fn utc_timestamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let (days, seconds_of_day) = (seconds / 86_400, seconds % 86_400);
+    // conversion d'un nombre de jours en date du calendrier grégorien (algorithme de H. Hinnant)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60
+    )
+}
+
+/// Garde les `max_files` fichiers les plus récents du programme.
+/// Les noms contenant la date, l'ordre alphabétique est l'ordre chronologique.
+fn remove_old_files(config: &LogConfig) -> io::Result<()> {
+    let dated_prefix = format!("{}-", config.app_name);
+    let mut dated_files: Vec<PathBuf> = std::fs::read_dir(&config.dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| is_dated_log(path, &dated_prefix))
+        .collect();
+    dated_files.sort();
+    // avec Rotation::Size, le fichier en cours n'est pas daté mais compte aussi
+    let current_is_undated = config.dir.join(format!("{}.log", config.app_name)).exists();
+    let keep = config
+        .max_files
+        .saturating_sub(usize::from(current_is_undated));
+    let n_removed = dated_files.len().saturating_sub(keep);
+    for path in dated_files.iter().take(n_removed) {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[inline]
+fn is_dated_log(path: &Path, dated_prefix: &str) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    // `motors-20250101-...` mais pas `motors-test-20250101-...` qui appartient à un autre programme
+    name.ends_with(".log")
+        && name
+            .strip_prefix(dated_prefix)
+            .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn test_config(name: &str, rotation: Rotation, max_files: usize) -> LogConfig {
+        let dir =
+            std::env::temp_dir().join(format!("robot-logging-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        LogConfig {
+            dir,
+            rotation,
+            max_files,
+            ..LogConfig::new("app")
+        }
+    }
+
+    fn log_files(config: &LogConfig) -> Vec<String> {
+        let mut files: Vec<String> = std::fs::read_dir(&config.dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn test_utc_timestamp() {
+        assert_eq!(utc_timestamp(UNIX_EPOCH), "19700101-000000");
+        assert_eq!(
+            utc_timestamp(UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+            "20231114-221320"
+        );
+        // 29 février d'une année bissextile
+        assert_eq!(
+            utc_timestamp(UNIX_EPOCH + Duration::from_secs(1_709_208_000)),
+            "20240229-120000"
+        );
+    }
+
+    #[test]
+    fn test_default_filter() {
+        let config = LogConfig::new("lidar-analyzer");
+        assert_eq!(config.default_filter, "warn,lidar_analyzer=trace");
+        if std::env::var_os(EnvFilter::DEFAULT_ENV).is_none() {
+            assert!(config.filter().is_ok());
+        }
+        let invalid = LogConfig {
+            default_filter: "warn,=[".to_owned(),
+            ..config
+        };
+        if std::env::var_os(EnvFilter::DEFAULT_ENV).is_none() {
+            assert!(invalid.filter().is_err());
+        }
+    }
+
+    #[test]
+    fn test_rotation_by_size() {
+        let config = test_config("size", Rotation::Size(10), 3);
+        let mut log_file = LogFile::open(&config).unwrap();
+        for _ in 0..6 {
+            log_file.write_all(b"0123456789").unwrap();
+        }
+        log_file.flush().unwrap();
+        let files = log_files(&config);
+        // le fichier en cours et les deux archives les plus récentes
+        assert_eq!(files.len(), 3, "{:?}", files);
+        assert!(files.contains(&"app.log".to_owned()));
+        assert_eq!(
+            std::fs::read(config.dir.join("app.log")).unwrap(),
+            b"0123456789"
+        );
+        std::fs::remove_dir_all(&config.dir).unwrap();
+    }
+
+    #[test]
+    fn test_rotation_per_run() {
+        let config = test_config("run", Rotation::PerRun, 2);
+        std::fs::create_dir_all(&config.dir).unwrap();
+        for old in [
+            "app-20240101-000000.log",
+            "app-20240102-000000.log",
+            "app-test-20240101-000000.log",
+        ] {
+            std::fs::write(config.dir.join(old), b"old").unwrap();
+        }
+        let mut log_file = LogFile::open(&config).unwrap();
+        log_file.write_all(b"new run").unwrap();
+        let current = log_file
+            .path()
+            .file_name()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_owned();
+        assert_eq!(
+            log_files(&config),
+            [
+                "app-20240102-000000.log",
+                current.as_str(),
+                "app-test-20240101-000000.log"
+            ]
+        );
+        std::fs::remove_dir_all(&config.dir).unwrap();
+    }
+}
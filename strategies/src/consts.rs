@@ -1,5 +1,6 @@
 //! Ce fichier contient l'ensemble des constantes utilisés par tout le code
 
+use robot_common::units::Meters;
 
 /// Constantes du terrain :
-pub const BORDER_LENGTH: f32 = 5.0;
\ No newline at end of file
+pub const BORDER_LENGTH: Meters = Meters::cm(5.0);
\ No newline at end of file
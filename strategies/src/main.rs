@@ -1,8 +1,10 @@
 mod consts;
-mod vector2;
 use consts::*;
-use radians::Rad32;
-use vector2::{GlobalCoord, LocalCoord, Vector2};
+use robot_common::frames::{Global, GlobalPoint, LocalPoint, Pose2};
+use robot_common::units::*;
+use robot_logging::{set_up_logging, LogConfig};
+use std::f64::consts::FRAC_PI_4;
+use tracing::info;
 
 /// Ce struct contient l'ensemble des données qui sont passés
 /// à l'algorithme des stratégies.
@@ -18,50 +20,66 @@ use vector2::{GlobalCoord, LocalCoord, Vector2};
 /// Pour les coordonées relatives au robot, l'origine est le centre du robot, les y positifs
 /// vers le devant du robot et les x positifs à droite.
 ///
-/// Toutes les distances sont en cm et tous les angles en radians.
+/// Les repères sont ceux de `robot_common::frames` (`GlobalPoint` et `LocalPoint`),
+/// les distances sont des `Meters` et les angles des `Rad`.
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(dead_code)] // les stratégies n'utilisent pas encore toutes les informations
 struct Informations {
     /// position *absolue* du robot sur le terrain.
     /// # Dépendances :
     /// - lidar analyzer complet
-    robot_position: Option<GlobalCoord>,
+    robot_position: Option<GlobalPoint>,
 
     /// angle du robot *en radians* par rapport à la droite notre goal / leur goal,
     /// vu depuis notre goal, augmente dans le sens des aiguilles d'une montre
     /// # Dépendances :
     /// - lidar analyzer complet
-    robot_angle: Option<Rad32>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "robot_common::units::serde_option_rad")
+    )]
+    robot_angle: Option<Rad>,
 
     /// distance au mur le plus proche.
     /// Cette valeur est quasiment toujours calculée par lidar analyzer. Elle permet d'éviter de
     /// sortir du terrain y compris lorsque la détermination des murs (lidar analyzer complet) échoue.
     /// # Dépendances :
     /// - lidar analyzer dégradé
-    nearest_wall_distance: Option<f32>,
+    nearest_wall_distance: Option<Meters>,
 
     /// angle au mur le plus proche.
     /// Cette valeur est quasiment toujours calculée par lidar analyzer. Elle permet d'éviter de
     /// sortir du terrain y compris lorsque la détermination des murs (lidar analyzer complet) échoue.
     /// # Dépendances :
     /// - lidar analyzer dégradé
-    nearest_wall_angle: Option<Rad32>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "robot_common::units::serde_option_rad")
+    )]
+    nearest_wall_angle: Option<Rad>,
 
     /// position *absolue* du robot ami (coéquipier) sur le terrain.
     /// # Dépendances :
     /// - bluetooth entre les deux robots
     /// - lidar analyzer du robot ami
-    friend_position: Option<GlobalCoord>,
+    friend_position: Option<GlobalPoint>,
 
     /// angle du robot ami *en radians* par rapport à la droite notre goal / leur goal,
     /// # Dépendances :
     /// - bluetooth entre les deux robots
     /// - lidar analyzer du robot ami
-    friend_angle: Option<Rad32>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "robot_common::units::serde_option_rad")
+    )]
+    friend_angle: Option<Rad>,
 
     /// position *relative* de la balle par rapport au robot,
     /// # Dépendances :
     /// - la caméra arrive à détecter la balle
-    ball_relative_position: Option<LocalCoord>,
+    ball_relative_position: Option<LocalPoint>,
 
     /// position *absolue* de la balle calculée par le robot ami,
     /// Cette valeur sert surtout si la balle est trop loin pour être bien
@@ -69,7 +87,7 @@ struct Informations {
     /// # Dépendances :
     /// - bluetooth entre les deux robots
     /// - la caméra de l'autre robot arrive à détecter la balle
-    friend_ball_position: Option<GlobalCoord>,
+    friend_ball_position: Option<GlobalPoint>,
 
     robot_has_ball: bool,
     friend_has_ball: Option<bool>,
@@ -80,17 +98,33 @@ struct Informations {
     /// l'est aussi.
     /// # Dépendances :
     /// - lidar analyzer NG
-    enemy_positions: (Option<GlobalCoord>, Option<GlobalCoord>),
+    enemy_positions: (Option<GlobalPoint>, Option<GlobalPoint>),
+}
+
+impl Informations {
+    /// Pose du robot sur le terrain, pour passer les positions du repère local au repère global
+    /// (`LocalPoint::to_global`) et inversement.
+    /// `robot_angle` augmente dans le sens des aiguilles d'une montre, le cap de `Pose2` dans
+    /// le sens trigonométrique.
+    fn robot_pose(&self) -> Option<Pose2<Global>> {
+        Some(Pose2::new(self.robot_position?, -self.robot_angle?))
+    }
 }
 
 /// Représente l'ensemble des actions à faire par le robot qui ont été décidés par les stratégies
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(dead_code)] // lue par les moteurs, pas encore envoyée
 struct Action {
     /// la position vers laquelle on voudrait aller
-    move_to: LocalCoord, //TODO : prise en charge Action sans pos du robot
+    move_to: LocalPoint, //TODO : prise en charge Action sans pos du robot
     /// l'orientation final que l'on voudra avoir lorsque l'on aura atteint cette position
     /// Si None, on garde la même qu'avant
-    final_orientation: Option<Rad32>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "robot_common::units::serde_option_rad")
+    )]
+    final_orientation: Option<Rad>,
     /// est-ce que on active le kicker pour lancer la balle
     kick: bool,
     /// vitesse que l'on donne au dribbler :
@@ -100,17 +134,20 @@ struct Action {
     dribbler: f32,
 }
 
-fn main() {
-    println!("Vector2 {:?}", Vector2::new(10., 5.));
-    println!("décision prise : {:?}", decision(&Informations::default()));
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    set_up_logging(&LogConfig::new("strategies"))?;
+    info!("position {:?}", GlobalPoint::cm(10., 5.));
+    info!("décision prise : {:?}", decision(&Informations::default()));
+    Ok(())
 }
 
 fn decision(info: &Informations) -> Action {
     if let Some(refrain) = is_outside(info) {
-        return refrain;
+        refrain
     } else {
-        detecter_obstacles(choose_position(info));
-    };
+        // TODO : faire passer l'action par detecter_obstacles quand elle sera terminée
+        choose_position(info)
+    }
 }
 
 /// On teste si le robot est en dehors du terrain.
@@ -121,144 +158,151 @@ fn is_outside(info: &Informations) -> Option<Action> {
             return None;
         }
 
-        //let mut future_local_position = LocalCoord(Vector2::new(0.0, 0.0));
+        //let mut future_local_position = LocalPoint::cm(0.0, 0.0);
 
         let future_local_position = if let Some(robot_angle) = info.robot_angle {
             // Comme on a l'angle du robot on calcule de combien revenir en arrière
 
             //-> bug pour 0; 90; 180 et 270  pt choisir vers cages adverses pour majorité des cas
-            if robot_angle <= Rad32::QUARTER_TURN {
+            if robot_angle <= Rad::QUARTER_TURN {
                 // o entre 0 et 90
                 // pos : x-5 ; y + 5
                 //o : o - 90
-                LocalCoord(Vector2::new(5.0, 5.0))
-            } else if robot_angle > Rad32::QUARTER_TURN && robot_angle < Rad32::HALF_TURN {
+                LocalPoint::cm(5.0, 5.0)
+            } else if robot_angle > Rad::QUARTER_TURN && robot_angle < Rad::HALF_TURN {
                 // o entre 90 et 180
                 // pos : x-5 ; y - 5
                 //o : o + 90
-                LocalCoord(Vector2::new(-5.0, -5.0))
-            } else if robot_angle >= Rad32::HALF_TURN
-                && robot_angle < (Rad32::HALF_TURN + Rad32::QUARTER_TURN)
+                LocalPoint::cm(-5.0, -5.0)
+            } else if robot_angle >= Rad::HALF_TURN
+                && robot_angle < (Rad::HALF_TURN + Rad::QUARTER_TURN)
             {
                 // o entre 180 et 270
                 // pos : x+5 ; y - 5
                 //o : o - 90
-                LocalCoord(Vector2::new(5.0, -5.0))
-            } else if robot_angle >= (Rad32::HALF_TURN + Rad32::QUARTER_TURN)
-                && robot_angle < Rad32::FULL_TURN
+                LocalPoint::cm(5.0, -5.0)
+            } else if robot_angle >= (Rad::HALF_TURN + Rad::QUARTER_TURN)
+                && robot_angle < Rad::FULL_TURN
             {
                 // o entre 270 et 0
                 // pos : x+5 ; y + 5
                 //o : o + 90
-                LocalCoord(Vector2::new(5.0, 5.0))
+                LocalPoint::cm(5.0, 5.0)
             } else {
                 // TODO log error
-                LocalCoord(Vector2::zeros())
+                LocalPoint::ORIGIN
             }
         } else {
             // TODO discussion
-            LocalCoord(Vector2::zeros())
+            LocalPoint::ORIGIN
         };
 
-        return Some(Action {
+        Some(Action {
             move_to: future_local_position,
             final_orientation: None, // On garde la même orientation
             kick: false,
             dribbler: if info.robot_has_ball { 1.0 } else { 0.0 },
-        });
+        })
     } else {
         // TODO log
-        return None;
+        None
     }
 }
 
+/// Les positions sont choisies sur le terrain puis ramenées dans le repère du robot
+/// avec `Informations::robot_pose`.
 fn choose_position(info: &Informations) -> Action {
-    if let Some(robot_position) = info.robot_position {
-        if info.robot_has_ball {
-            let mut new_position = Vector2::new(0.0, 0.0);
-            let mut new_orientation = Rad32::ZERO;
-            let mut kick_decision = 0;
-
-            if robot_position.y >= 70.0 {
+    if info.robot_has_ball {
+        if let Some(robot_pose) = info.robot_pose() {
+            let robot_position = robot_pose.position;
+            let mut new_position = GlobalPoint::ORIGIN;
+            let mut new_orientation = Rad::ZERO;
+            let mut kick_decision = false;
+
+            if robot_position.y >= Meters::cm(70.0) {
                 // coordonnées cages adverses  G : -53 ; 91.5    D : 53  ; 91.5 il faut que le robot soit en face des cages sinon changer orientation
-                if robot_position.x > -53 && robot_position.x < 53 {
+                if robot_position.x > Meters::cm(-53.0) && robot_position.x < Meters::cm(53.0) {
                     //pos = position actuelle
                     //o = orientation 0.0
                     //tirer
 
                     new_position = robot_position;
-                    new_orientation = 0.0;
-                    kick_decision = -1;
-                } else if robot_position.x >= 53.0 {
-                    let CdR = (robot_position - Vector2::new(53, 91.5)).length();
-                    let CdD =
-                        (Vector2::new(53, 91.5) - Vector2::new(53, robot_position.y)).length();
-                    let R = ((Vector2::new(53, robot_position.y) - robot_position).length() / CdR)
+                    new_orientation = Rad::ZERO;
+                    kick_decision = true;
+                } else if robot_position.x >= Meters::cm(53.0) {
+                    let cd_r = (robot_position - GlobalPoint::cm(53.0, 91.5)).norm();
+                    let r = ((GlobalPoint::new(Meters::cm(53.0), robot_position.y)
+                        - robot_position)
+                        .norm()
+                        / cd_r)
                         .acos();
 
-                    if R <= 45 {
+                    if r <= FRAC_PI_4 {
                         //pos = pos actuelle
-                        //o = 360 - 90 + R
+                        //o = 360 - 90 + r
                         //tirer
                         new_position = robot_position;
-                        new_orientation = 0.0; //à changer
-                        kick_decision = -1;
+                        new_orientation = Rad::ZERO; //à changer
+                        kick_decision = true;
                     } else {
-                        //pos = Vector2::new(50, robot_position.y),
+                        //pos = GlobalPoint::new(Meters::cm(50.0), robot_position.y),
                         //o = 0.0
                         //ne pas tirer
-                        new_position = Vector2::new(50, robot_position.y);
-                        new_orientation = 0.0;
-                        kick_decision = 0;
+                        new_position = GlobalPoint::new(Meters::cm(50.0), robot_position.y);
+                        new_orientation = Rad::ZERO;
+                        kick_decision = false;
                     }
-                } else if robot_position.x <= -53 {
-                    let CgR = (robot_position - Vector2::new(-53, 91.5)).length();
-                    let CgD =
-                        (Vector2::new(-53, 91.5) - Vector2::new(-53, robot_position.y)).length();
-                    let R = ((Vector2::new(-53, robot_position.y) - robot_position).length() / CgR)
+                } else if robot_position.x <= Meters::cm(-53.0) {
+                    let cg_r = (robot_position - GlobalPoint::cm(-53.0, 91.5)).norm();
+                    let r = ((GlobalPoint::new(Meters::cm(-53.0), robot_position.y)
+                        - robot_position)
+                        .norm()
+                        / cg_r)
                         .acos();
 
-                    if R <= 45 {
+                    if r <= FRAC_PI_4 {
                         //pos = robot.position
-                        //o = 90 - R
+                        //o = 90 - r
                         //tirer
                         new_position = robot_position;
-                        new_orientation = 0.0; //à changer en pi - R
-                        kick_decision = -1;
+                        new_orientation = Rad::ZERO; //à changer en pi - r
+                        kick_decision = true;
                     } else {
-                        //pos = Vector2::new(-50, robot_position.y),
+                        //pos = GlobalPoint::new(Meters::cm(-50.0), robot_position.y),
                         //o = 0.0
                         //ne pas tirer
-                        new_position = Vector2::new(-50, robot_position.y);
-                        new_orientation = 0.0;
-                        kick_decision = 0;
+                        new_position = GlobalPoint::new(Meters::cm(-50.0), robot_position.y);
+                        new_orientation = Rad::ZERO;
+                        kick_decision = false;
                     }
                 }
             }
             return Action {
-                move_to: new_position,
-                final_orientation: new_orientation,
+                move_to: new_position.to_local(robot_pose),
+                final_orientation: Some(new_orientation),
                 kick: kick_decision,
-                dribbler: 0,
+                dribbler: 0.0,
             };
-        } else {
-            
+        }
+    }
+
+    // la balle vue par le robot ami n'est utilisable que si on sait où on est
+    let ball_position = info
+        .ball_relative_position
+        .or_else(|| Some(info.friend_ball_position?.to_local(info.robot_pose()?)));
+    if let Some(ball_pos) = ball_position {
+        Action {
+            move_to: ball_pos,
+            final_orientation: Some(Rad::ZERO),
+            kick: false,
+            dribbler: 1.0,
         }
     } else {
-        if let Some(ball_pos) = info.ball_relative_position.or(info.friend_ball_position) {
-            return Action {
-                move_to: ball_pos,
-                final_orientation: 0.0,
-                kick: false,
-                dribbler: 255,
-            };
-        } else {
-            return Action {
-                move_to: Vector2::new(0.0, 0.0), //à changer
-                final_orientation: Rad32::new(0.0),
-                kick: false,
-                dribbler: 0,
-            };
+        Action {
+            move_to: LocalPoint::ORIGIN, //à changer
+            final_orientation: Some(Rad::ZERO),
+            kick: false,
+            dribbler: 0.0,
         }
     }
 }
@@ -313,7 +357,7 @@ fn choose_position(info: &Informations) -> Action {
 // }
 
 // fn tester_obstacles(x_r: f32, y_r: f32, x_o: f32, y_o: f32, x_d: f32, y_d: f32) -> Option<Action> {
-//     let mut new_position = Vector2::new(0.0, 0.0);
+//     let mut new_position = GlobalPoint::ORIGIN;
 //     if x_r = x_d {
 //         //si |x_r - x_o| <= 9 && |y_o - y_d|
 //         if (x_r - x_o).abs() <= 9 && (y_o - y_d).abs() <= 9 { //verif (y_o - y_d).abs() <= 9
@@ -335,3 +379,89 @@ fn choose_position(info: &Informations) -> Action {
 //     let y = ((yd - yr) * xo + yr * yd - yd * yr) / (xd - xr);
 //     (y)
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_to_is_relative_to_the_robot() {
+        // robot face au goal adverse, balle en main, en face des cages : il tire sur place
+        let informations = Informations {
+            robot_position: Some(GlobalPoint::cm(10.0, 80.0)),
+            robot_angle: Some(Rad::ZERO),
+            robot_has_ball: true,
+            ..Default::default()
+        };
+        let action = choose_position(&informations);
+        assert!(action.kick);
+        assert!(action.move_to.distance_to(LocalPoint::ORIGIN) < Meters(1e-9));
+
+        // trop sur la droite : il se décale vers x = 50 cm, soit 10 cm à sa gauche
+        let informations = Informations {
+            robot_position: Some(GlobalPoint::cm(60.0, 75.0)),
+            ..informations
+        };
+        let action = choose_position(&informations);
+        assert!(!action.kick);
+        assert!(action.move_to.distance_to(LocalPoint::cm(-10.0, 0.0)) < Meters(1e-9));
+
+        // la balle vue par le robot ami est ramenée dans le repère du robot
+        let informations = Informations {
+            robot_position: Some(GlobalPoint::cm(0.0, 0.0)),
+            robot_angle: Some(Rad::QUARTER_TURN),
+            friend_ball_position: Some(GlobalPoint::cm(0.0, 30.0)),
+            ..Default::default()
+        };
+        let action = decision(&informations);
+        let (x, y) = action.move_to.to_cm();
+        assert!(x.abs() > 29.0 && y.abs() < 1e-6, "{:?}", action.move_to);
+        // sans pose, elle est inutilisable
+        let informations = Informations {
+            robot_angle: None,
+            ..informations
+        };
+        assert_eq!(choose_position(&informations).move_to, LocalPoint::ORIGIN);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let informations = Informations {
+            robot_position: Some(GlobalPoint::cm(20.0, -50.0)),
+            robot_angle: Some(Rad::QUARTER_TURN),
+            nearest_wall_distance: Some(Meters(0.3)),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&informations).unwrap();
+        let read: Informations = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.robot_position, informations.robot_position);
+        assert_eq!(read.robot_angle, informations.robot_angle);
+        assert_eq!(
+            read.nearest_wall_distance,
+            informations.nearest_wall_distance
+        );
+        assert_eq!(read.friend_angle, None);
+        assert!(read.robot_pose().is_some());
+        // les champs absents sont inconnus
+        let partial: Informations = serde_json::from_str(r#"{"robot_angle":0.5}"#).unwrap();
+        assert_eq!(partial.robot_angle, Some(Rad::new(0.5)));
+        assert!(partial.robot_position.is_none());
+
+        let action = Action {
+            move_to: LocalPoint::cm(5.0, -5.0),
+            final_orientation: None,
+            kick: true,
+            dribbler: -1.0,
+        };
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(
+            json,
+            r#"{"move_to":{"x":0.05,"y":-0.05},"final_orientation":null,"kick":true,"dribbler":-1.0}"#
+        );
+        let read: Action = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.move_to, action.move_to);
+        assert_eq!(read.final_orientation, action.final_orientation);
+        assert_eq!((read.kick, read.dribbler), (action.kick, action.dribbler));
+    }
+}